@@ -1,13 +1,19 @@
 use crate::analyzer::resource_table::PathId;
 use crate::analyzer_error::AnalyzerError;
+use crate::attribute::{AllowItem, Attribute as Attr};
 use crate::attribute_table;
+use crate::comb_graph::CombGraph;
+use crate::elaborator::{Elaborator, InstanceTree};
 use crate::handlers::*;
 use crate::msb_table;
 use crate::namespace::Namespace;
 use crate::namespace_table;
+use crate::power_domain_table;
 use crate::symbol::{
-    Direction, DocComment, Symbol, SymbolId, SymbolKind, TypeKind, VariableAffiliation,
+    Direction, DocComment, ParameterKind, Symbol, SymbolId, SymbolKind, TypeKind,
+    VariableAffiliation,
 };
+use crate::symbol_path::SymbolPath;
 use crate::symbol_table;
 use crate::type_dag;
 use crate::var_ref::{
@@ -15,10 +21,10 @@ use crate::var_ref::{
     VarRefAffiliation, VarRefPath, VarRefType,
 };
 use itertools::Itertools;
-use std::collections::HashMap;
-use std::path::Path;
-use veryla_metadata::{Build, Lint, Metadata};
-use veryla_parser::resource_table;
+use std::collections::{HashMap, HashSet};
+use std::path::{Path, PathBuf};
+use veryla_metadata::{corner_constants, Build, Lint, LintLevel, Metadata};
+use veryla_parser::resource_table::{self, StrId};
 use veryla_parser::veryla_grammar_trait::*;
 use veryla_parser::veryla_token::{Token, TokenSource};
 use veryla_parser::veryla_walker::{Handler, VerylaWalker};
@@ -101,6 +107,125 @@ impl<'a> AnalyzerPass3<'a> {
         ret
     }
 
+    /// Ports, parameters, functions, entities and packages which are never referenced
+    pub fn check_unused(&self) -> Vec<AnalyzerError> {
+        let mut ret = Vec::new();
+
+        let top_level: HashMap<_, _> = self
+            .symbols
+            .iter()
+            .filter(|x| x.namespace.depth() == 1)
+            .map(|x| ((x.namespace.paths[0], x.token.text), &x.kind))
+            .collect();
+        let owner = |symbol: &Symbol| {
+            let paths = &symbol.namespace.paths;
+            if paths.len() < 2 {
+                None
+            } else {
+                top_level.get(&(paths[0], paths[1])).copied()
+            }
+        };
+
+        // Entities without body are stubs of external models
+        // whose ports and parameters are not checked
+        let has_body: HashSet<_> = self
+            .symbols
+            .iter()
+            .filter(|x| {
+                x.namespace.depth() >= 2
+                    && !matches!(
+                        x.kind,
+                        SymbolKind::Port(_)
+                            | SymbolKind::Parameter(_)
+                            | SymbolKind::GenericParameter(_)
+                            | SymbolKind::PowerDomain
+                    )
+            })
+            .map(|x| (x.namespace.paths[0], x.namespace.paths[1]))
+            .collect();
+        let has_body = |symbol: &Symbol| {
+            let paths = &symbol.namespace.paths;
+            has_body.contains(&(paths[0], paths[1]))
+        };
+
+        let mut implicit_used = Vec::new();
+        for symbol in &self.symbols {
+            match &symbol.kind {
+                SymbolKind::Entity(x) => {
+                    implicit_used.extend(x.default_power);
+                    implicit_used.extend(x.default_enable);
+                }
+                SymbolKind::Test(x) => {
+                    if let Some(top) = x.top {
+                        implicit_used.extend(
+                            self.symbols
+                                .iter()
+                                .filter(|x| x.namespace.depth() == 1 && x.token.text == top)
+                                .map(|x| x.id),
+                        );
+                    }
+                }
+                _ => (),
+            }
+        }
+
+        let mut symbols: Vec<_> = self.symbols.iter().collect();
+        symbols.sort_by_key(|x| x.token.pos);
+
+        for symbol in symbols {
+            if symbol.token.source != self.path
+                || !symbol.references.is_empty()
+                || symbol.allow_unused
+                || implicit_used.contains(&symbol.id)
+                || symbol.token.to_string().starts_with('_')
+            {
+                continue;
+            }
+
+            let identifier = symbol.token.to_string();
+            let token = &symbol.token.into();
+            let direct_member = symbol.namespace.depth() == 2;
+            match &symbol.kind {
+                SymbolKind::Port(x)
+                    if x.direction == Direction::Input
+                        && !x.is_proto
+                        && direct_member
+                        && has_body(symbol)
+                        && matches!(owner(symbol), Some(SymbolKind::Entity(_))) =>
+                {
+                    ret.push(AnalyzerError::unused_port(&identifier, self.text, token));
+                }
+                SymbolKind::Parameter(x)
+                    if matches!(x.kind, ParameterKind::Param)
+                        && direct_member
+                        && has_body(symbol)
+                        && matches!(
+                            owner(symbol),
+                            Some(SymbolKind::Entity(_) | SymbolKind::Interface(_))
+                        ) =>
+                {
+                    ret.push(AnalyzerError::unused_param(&identifier, self.text, token));
+                }
+                SymbolKind::Function(_) if matches!(owner(symbol), Some(SymbolKind::Entity(_))) => {
+                    ret.push(AnalyzerError::unused_function(
+                        &identifier,
+                        self.text,
+                        token,
+                    ));
+                }
+                SymbolKind::Entity(_) if !symbol.public => {
+                    ret.push(AnalyzerError::unused_entity(&identifier, self.text, token));
+                }
+                SymbolKind::Package(_) if !symbol.public => {
+                    ret.push(AnalyzerError::unused_package(&identifier, self.text, token));
+                }
+                _ => (),
+            }
+        }
+
+        ret
+    }
+
     pub fn check_assignment(&self) -> Vec<AnalyzerError> {
         let mut ret = Vec::new();
 
@@ -217,6 +342,29 @@ impl<'a> AnalyzerPass3<'a> {
 
         ret
     }
+
+    pub fn check_combinational_loop(&self) -> Vec<AnalyzerError> {
+        let mut ret = Vec::new();
+
+        let mut graph = CombGraph::new();
+        for symbol in &self.symbols {
+            if symbol.token.source == self.path && matches!(symbol.kind, SymbolKind::Entity(_)) {
+                for x in graph.loops(symbol.id) {
+                    let mut chain: Vec<_> = x.signals.iter().map(|x| x.to_string()).collect();
+                    if let Some(first) = chain.first().cloned() {
+                        chain.push(first);
+                    }
+                    ret.push(AnalyzerError::combinational_loop(
+                        &chain.join(" -> "),
+                        self.text,
+                        &x.token.into(),
+                    ));
+                }
+            }
+        }
+
+        ret
+    }
 }
 
 pub struct Analyzer {
@@ -256,6 +404,22 @@ impl Analyzer {
                 }
             }
         }
+
+        // Add symbols of the constants under $corner namespace
+        let mut corner_namespace = Namespace::new();
+        corner_namespace.push(resource_table::insert_str("$corner"));
+        for (name, _) in corner_constants(&metadata.corners, None) {
+            let token = Token::new(&name, 0, 0, 0, 0, TokenSource::Builtin);
+            let symbol = Symbol::new(
+                &token,
+                SymbolKind::SystemVerilog,
+                &corner_namespace,
+                false,
+                DocComment::default(),
+            );
+            symbol_table::insert(&token, symbol);
+        }
+
         Analyzer {
             build_opt: metadata.build.clone(),
             lint_opt: metadata.lint.clone(),
@@ -276,7 +440,7 @@ impl Analyzer {
         pass1.veryla(input);
         ret.append(&mut pass1.handlers.get_errors());
 
-        ret
+        self.filter_errors(ret)
     }
 
     pub fn analyze_post_pass1() {
@@ -297,7 +461,7 @@ impl Analyzer {
         pass2.veryla(input);
         ret.append(&mut pass2.handlers.get_errors());
 
-        ret
+        self.filter_errors(ret)
     }
 
     pub fn analyze_pass3<T: AsRef<Path>>(
@@ -312,16 +476,82 @@ impl Analyzer {
         namespace_table::set_default(&[project_name.into()]);
         let pass3 = AnalyzerPass3::new(path.as_ref(), text);
         ret.append(&mut pass3.check_variables());
+        ret.append(&mut pass3.check_unused());
         ret.append(&mut pass3.check_assignment());
         ret.append(&mut pass3.check_unassigned());
+        ret.append(&mut pass3.check_combinational_loop());
 
-        ret
+        self.filter_errors(ret)
+    }
+
+    /// Drop diagnostics which are allowed by `[lint.rules]` or `#[allow(...)]`
+    fn filter_errors(&self, errors: Vec<AnalyzerError>) -> Vec<AnalyzerError> {
+        errors.into_iter().filter(|x| !self.is_allowed(x)).collect()
+    }
+
+    fn is_allowed(&self, error: &AnalyzerError) -> bool {
+        let Some(code) = error.code_name() else {
+            return false;
+        };
+        if let Some(LintLevel::Allow) = self.lint_opt.level(&code) {
+            return true;
+        }
+        if !AnalyzerError::is_warning_code(&code) {
+            return false;
+        }
+
+        let Some((name, line, column)) = error.location() else {
+            return false;
+        };
+        let Some(path) = resource_table::get_path_id(PathBuf::from(name)) else {
+            return false;
+        };
+        let token = Token::new("", line, column, 0, 0, TokenSource::File(path));
+        attribute_table::get(&token)
+            .iter()
+            .any(|x| matches!(x, Attr::Allow(x) if x.allows(&code)))
+    }
+
+    /// Elaborate the design from `top`, or from every entity which is not instantiated
+    ///
+    /// `inputs` are pairs of the source text and the syntax tree of each file.
+    pub fn elaborate(
+        project_name: &str,
+        inputs: &[(&str, &Veryla)],
+        top: Option<&str>,
+    ) -> (InstanceTree, Vec<AnalyzerError>) {
+        let project_name: StrId = project_name.into();
+        let mut elaborator = Elaborator::new(inputs);
+        let tops = if let Some(top) = top {
+            let namespace = Namespace::from(&[project_name][..]);
+            let path = SymbolPath::new(&[top.into()]);
+            match symbol_table::resolve((&path, &namespace)) {
+                Ok(x) if matches!(x.found.kind, SymbolKind::Entity(_)) => vec![x.found.id],
+                _ => Vec::new(),
+            }
+        } else {
+            elaborator.tops(project_name)
+        };
+        let tree = elaborator.elaborate(&tops);
+        (tree, elaborator.errors())
+    }
+
+    /// Elaborate the whole design after pass3 and check the instance hierarchy
+    pub fn analyze_elaboration(
+        &self,
+        project_name: &str,
+        inputs: &[(&str, &Veryla)],
+    ) -> Vec<AnalyzerError> {
+        namespace_table::set_default(&[project_name.into()]);
+        let (_, errors) = Self::elaborate(project_name, inputs, None);
+        self.filter_errors(errors)
     }
 
     pub fn clear(&self) {
         attribute_table::clear();
         msb_table::clear();
         namespace_table::clear();
+        power_domain_table::clear();
         symbol_table::clear();
         type_dag::clear();
     }
@@ -537,13 +767,19 @@ fn check_assign_position_tree(
         tree.add(pos.clone());
     }
 
-    if let Some(token) = tree.check_always_comb_uncovered() {
-        ret.push(AnalyzerError::uncovered_branch(
-            &symbol.token.to_string(),
-            text,
-            &symbol.token.into(),
-            &token.into(),
-        ));
+    if let Some((token, condition)) = tree.check_always_comb_uncovered() {
+        let allow_latch = [&token, &symbol.token]
+            .iter()
+            .any(|x| attribute_table::contains(x, Attr::Allow(AllowItem::Latch)));
+        if !allow_latch {
+            ret.push(AnalyzerError::latch_inferred(
+                &symbol.token.to_string(),
+                &condition,
+                text,
+                &symbol.token.into(),
+                &token.into(),
+            ));
+        }
     }
 
     if let Some(token) = tree.check_sequence_missing_enable() {
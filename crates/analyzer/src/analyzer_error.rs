@@ -31,6 +31,36 @@ pub enum AnalyzerError {
         error_location: SourceSpan,
     },
 
+    #[diagnostic(
+        severity(Error),
+        code(combinational_loop),
+        help("break the loop by a register or remove one of the dependencies"),
+        url("https://doc.veryl-lang.org/book/07_appendix/02_semantic_error.html#combinational_loop")
+    )]
+    #[error("Combinational loop is detected: {chain}")]
+    CombinationalLoop {
+        chain: String,
+        #[source_code]
+        input: NamedSource<String>,
+        #[label("Error location")]
+        error_location: SourceSpan,
+    },
+
+    #[diagnostic(
+        severity(Warning),
+        code(constant_condition),
+        help("remove the condition or make it depend on a parameter or signal"),
+        url("https://doc.veryl-lang.org/book/07_appendix/02_semantic_error.html#constant_condition")
+    )]
+    #[error("condition is always {value}")]
+    ConstantCondition {
+        value: String,
+        #[source_code]
+        input: NamedSource<String>,
+        #[label("Error location")]
+        error_location: SourceSpan,
+    },
+
     #[diagnostic(
         severity(Error),
         code(cyclice_type_dependency),
@@ -62,6 +92,38 @@ pub enum AnalyzerError {
         error_location: SourceSpan,
     },
 
+    #[diagnostic(
+        severity(Error),
+        code(elaboration_limit_exceeded),
+        help("reduce the depth of instance hierarchy or the number of generate iterations"),
+        url("https://doc.veryl-lang.org/book/07_appendix/02_semantic_error.html#elaboration_limit_exceeded")
+    )]
+    #[error("elaboration of {identifier} exceeds the {limit} limit")]
+    ElaborationLimitExceeded {
+        identifier: String,
+        limit: String,
+        #[source_code]
+        input: NamedSource<String>,
+        #[label("Error location")]
+        error_location: SourceSpan,
+    },
+
+    #[diagnostic(
+        severity(Error),
+        code(evaluation_limit_exceeded),
+        help("reduce the depth of recursion or the number of iterations"),
+        url("https://doc.veryl-lang.org/book/07_appendix/02_semantic_error.html#evaluation_limit_exceeded")
+    )]
+    #[error("evaluation of function {function} exceeds the {limit} limit")]
+    EvaluationLimitExceeded {
+        function: String,
+        limit: String,
+        #[source_code]
+        input: NamedSource<String>,
+        #[label("Error location")]
+        error_location: SourceSpan,
+    },
+
     #[diagnostic(
         severity(Error),
         code(multiple_assignment),
@@ -368,6 +430,21 @@ pub enum AnalyzerError {
         error_location: SourceSpan,
     },
 
+    #[diagnostic(
+        severity(Error),
+        code(invalid_distribution),
+        help("use number literals as distribution arguments"),
+        url("")
+    )]
+    #[error("arguments of distribution \"{name}\" should be number literals")]
+    InvalidDistribution {
+        name: String,
+        #[source_code]
+        input: NamedSource<String>,
+        #[label("Error location")]
+        error_location: SourceSpan,
+    },
+
     #[diagnostic(
         severity(Error),
         code(invalid_test),
@@ -484,6 +561,93 @@ pub enum AnalyzerError {
         error_location: SourceSpan,
     },
 
+    #[diagnostic(
+        severity(Warning),
+        code(mismatch_port_width),
+        help("match the width of the connected expression to the port"),
+        url("https://doc.veryl-lang.org/book/07_appendix/02_semantic_error.html#mismatch_port_width")
+    )]
+    #[error("port \"{name}\" is {expected} bits wide, but the connected expression is {actual} bits wide")]
+    MismatchPortWidth {
+        name: String,
+        expected: usize,
+        actual: usize,
+        #[source_code]
+        input: NamedSource<String>,
+        #[label("Error location")]
+        error_location: SourceSpan,
+    },
+
+    #[diagnostic(
+        severity(Error),
+        code(mismatch_port_dimension),
+        help("match the array dimension of the connected expression to the port"),
+        url("https://doc.veryl-lang.org/book/07_appendix/02_semantic_error.html#mismatch_port_dimension")
+    )]
+    #[error(
+        "port \"{name}\" has array dimension {expected}, but the connected expression has {actual}"
+    )]
+    MismatchPortDimension {
+        name: String,
+        expected: String,
+        actual: String,
+        #[source_code]
+        input: NamedSource<String>,
+        #[label("Error location")]
+        error_location: SourceSpan,
+    },
+
+    #[diagnostic(
+        severity(Warning),
+        code(implicit_truncation),
+        help("select bits or cast the expression explicitly"),
+        url("https://doc.veryl-lang.org/book/07_appendix/02_semantic_error.html#implicit_truncation")
+    )]
+    #[error("{actual}-bit expression is truncated to {expected}-bit \"{name}\"")]
+    ImplicitTruncation {
+        name: String,
+        expected: usize,
+        actual: usize,
+        #[source_code]
+        input: NamedSource<String>,
+        #[label("Error location")]
+        error_location: SourceSpan,
+    },
+
+    #[diagnostic(
+        severity(Warning),
+        code(implicit_extension),
+        help("concatenate padding bits or cast the expression explicitly"),
+        url("https://doc.veryl-lang.org/book/07_appendix/02_semantic_error.html#implicit_extension")
+    )]
+    #[error("{actual}-bit expression is extended to {expected}-bit \"{name}\"")]
+    ImplicitExtension {
+        name: String,
+        expected: usize,
+        actual: usize,
+        #[source_code]
+        input: NamedSource<String>,
+        #[label("Error location")]
+        error_location: SourceSpan,
+    },
+
+    #[diagnostic(
+        severity(Warning),
+        code(mismatch_sign),
+        help("cast the expression to the signedness of the target explicitly"),
+        url("https://doc.veryl-lang.org/book/07_appendix/02_semantic_error.html#mismatch_sign")
+    )]
+    #[error("{actual} expression is assigned to {expected} \"{name}\"")]
+    MismatchSign {
+        name: String,
+        expected: String,
+        actual: String,
+        #[source_code]
+        input: NamedSource<String>,
+        #[label("Error location")]
+        error_location: SourceSpan,
+    },
+
     #[diagnostic(
         severity(Warning),
         code(mismatch_power_domain),
@@ -500,6 +664,57 @@ pub enum AnalyzerError {
         error_location: SourceSpan,
         #[label("power domain {other_domain}")]
         other_location: SourceSpan,
+        fixes: Vec<TextEdit>,
+    },
+
+    #[diagnostic(
+        severity(Warning),
+        code(unsynchronized_crossing),
+        help("register the signal by a synchronizer before using it in logic"),
+        url("https://doc.veryl-lang.org/book/07_appendix/02_semantic_error.html#unsynchronized_crossing")
+    )]
+    #[error("Signal of power domain {power_domain} is read combinationally in unsafe(pdc) before synchronization")]
+    UnsynchronizedCrossing {
+        power_domain: String,
+        other_domain: String,
+        #[source_code]
+        input: NamedSource<String>,
+        #[label("power domain {power_domain}")]
+        error_location: SourceSpan,
+        #[label("power domain {other_domain}")]
+        other_location: SourceSpan,
+    },
+
+    #[diagnostic(
+        severity(Error),
+        code(insufficient_synchronizer_stages),
+        help("add registers in the destination power domain or reduce `stages`"),
+        url("https://doc.veryl-lang.org/book/07_appendix/02_semantic_error.html#insufficient_synchronizer_stages")
+    )]
+    #[error("{identifier} passes through {actual} register stages, but the synchronizer requires {required}")]
+    InsufficientSynchronizerStages {
+        identifier: String,
+        required: usize,
+        actual: usize,
+        #[source_code]
+        input: NamedSource<String>,
+        #[label("Error location")]
+        error_location: SourceSpan,
+    },
+
+    #[diagnostic(
+        severity(Warning),
+        code(glitch_prone_crossing),
+        help("register the merged signal in its source power domain before the crossing"),
+        url("https://doc.veryl-lang.org/book/07_appendix/02_semantic_error.html#glitch_prone_crossing")
+    )]
+    #[error("Signal crossing power domain is merged combinationally from {signals}")]
+    GlitchProneCrossing {
+        signals: String,
+        #[source_code]
+        input: NamedSource<String>,
+        #[label("Crossing point")]
+        error_location: SourceSpan,
     },
 
     #[diagnostic(
@@ -530,6 +745,7 @@ pub enum AnalyzerError {
         input: NamedSource<String>,
         #[label("Error location")]
         error_location: SourceSpan,
+        fixes: Vec<TextEdit>,
     },
 
     #[diagnostic(
@@ -577,6 +793,21 @@ pub enum AnalyzerError {
         enable: SourceSpan,
     },
 
+    #[diagnostic(
+        severity(Warning),
+        code(incomplete_enable_initialisation),
+        help("assign initial values to them in the if_enable branch"),
+        url("https://doc.veryl-lang.org/book/07_appendix/02_semantic_error.html#incomplete_enable_initialisation")
+    )]
+    #[error("{names} are assigned in sequence but not initialised in if_enable branch")]
+    IncompleteEnableInitialisation {
+        names: String,
+        #[source_code]
+        input: NamedSource<String>,
+        #[label("Error location")]
+        error_location: SourceSpan,
+    },
+
     #[diagnostic(
         severity(Error),
         code(missing_tri),
@@ -589,6 +820,7 @@ pub enum AnalyzerError {
         input: NamedSource<String>,
         #[label("Error location")]
         error_location: SourceSpan,
+        fixes: Vec<TextEdit>,
     },
 
     #[diagnostic(
@@ -605,6 +837,21 @@ pub enum AnalyzerError {
         error_location: SourceSpan,
     },
 
+    #[diagnostic(
+        severity(Warning),
+        code(non_exhaustive_case),
+        help("add arms for the missing values or default arm"),
+        url("https://doc.veryl-lang.org/book/07_appendix/02_semantic_error.html#non_exhaustive_case")
+    )]
+    #[error("case doesn't cover all values: {missing}")]
+    NonExhaustiveCase {
+        missing: String,
+        #[source_code]
+        input: NamedSource<String>,
+        #[label("Error location")]
+        error_location: SourceSpan,
+    },
+
     #[diagnostic(
         severity(Error),
         code(sv_keyword_usage),
@@ -963,6 +1210,7 @@ pub enum AnalyzerError {
         input: NamedSource<String>,
         #[label("Error location")]
         error_location: SourceSpan,
+        fixes: Vec<TextEdit>,
     },
 
     #[diagnostic(
@@ -980,6 +1228,110 @@ pub enum AnalyzerError {
         error_location: SourceSpan,
     },
 
+    #[diagnostic(
+        severity(Warning),
+        code(unused_port),
+        help("remove the port or add prefix `_` to unused port name"),
+        url("https://doc.veryl-lang.org/book/07_appendix/02_semantic_error.html#unused_port")
+    )]
+    #[error("input port {identifier} is never read")]
+    UnusedPort {
+        identifier: String,
+        #[source_code]
+        input: NamedSource<String>,
+        #[label("Error location")]
+        error_location: SourceSpan,
+    },
+
+    #[diagnostic(
+        severity(Warning),
+        code(unused_param),
+        help("remove the parameter or add prefix `_` to unused parameter name"),
+        url("https://doc.veryl-lang.org/book/07_appendix/02_semantic_error.html#unused_param")
+    )]
+    #[error("parameter {identifier} is never referenced")]
+    UnusedParam {
+        identifier: String,
+        #[source_code]
+        input: NamedSource<String>,
+        #[label("Error location")]
+        error_location: SourceSpan,
+    },
+
+    #[diagnostic(
+        severity(Warning),
+        code(unused_function),
+        help("remove the function or add prefix `_` to unused function name"),
+        url("https://doc.veryl-lang.org/book/07_appendix/02_semantic_error.html#unused_function")
+    )]
+    #[error("function {identifier} is never called")]
+    UnusedFunction {
+        identifier: String,
+        #[source_code]
+        input: NamedSource<String>,
+        #[label("Error location")]
+        error_location: SourceSpan,
+        fixes: Vec<TextEdit>,
+    },
+
+    #[diagnostic(
+        severity(Warning),
+        code(unused_entity),
+        help("instantiate the entity, or mark it `pub` if it is used by other projects"),
+        url("https://doc.veryl-lang.org/book/07_appendix/02_semantic_error.html#unused_entity")
+    )]
+    #[error("entity {identifier} is never instantiated")]
+    UnusedEntity {
+        identifier: String,
+        #[source_code]
+        input: NamedSource<String>,
+        #[label("Error location")]
+        error_location: SourceSpan,
+    },
+
+    #[diagnostic(
+        severity(Warning),
+        code(unused_package),
+        help("import the package, or mark it `pub` if it is used by other projects"),
+        url("https://doc.veryl-lang.org/book/07_appendix/02_semantic_error.html#unused_package")
+    )]
+    #[error("package {identifier} is never referenced")]
+    UnusedPackage {
+        identifier: String,
+        #[source_code]
+        input: NamedSource<String>,
+        #[label("Error location")]
+        error_location: SourceSpan,
+    },
+
+    #[diagnostic(
+        severity(Warning),
+        code(unreachable_case_arm),
+        help("remove the arm"),
+        url("https://doc.veryl-lang.org/book/07_appendix/02_semantic_error.html#unreachable_case_arm")
+    )]
+    #[error("case arm is never matched because earlier arms cover it")]
+    UnreachableCaseArm {
+        #[source_code]
+        input: NamedSource<String>,
+        #[label("Error location")]
+        error_location: SourceSpan,
+    },
+
+    #[diagnostic(
+        severity(Warning),
+        code(unreachable_generate_block),
+        help("remove the generate block"),
+        url("https://doc.veryl-lang.org/book/07_appendix/02_semantic_error.html#unreachable_generate_block")
+    )]
+    #[error("generate block is never elaborated")]
+    UnreachableGenerateBlock {
+        #[source_code]
+        input: NamedSource<String>,
+        #[label("Error location")]
+        error_location: SourceSpan,
+    },
+
     #[diagnostic(
         severity(Warning),
         code(unassign_variable),
@@ -999,13 +1351,16 @@ pub enum AnalyzerError {
 
     #[diagnostic(
         severity(Warning),
-        code(uncovered_branch),
-        help(""),
-        url("https://doc.veryl-lang.org/book/07_appendix/02_semantic_error.html#uncovered_branch")
-    )]
-    #[error("{identifier} is not covered by all branches, it causes latch generation")]
-    UncoveredBranch {
+        code(latch_inferred),
+        help(
+            "assign {identifier} in all branches, or add #[allow(latch)] if the latch is intended"
+        ),
+        url("https://doc.veryl-lang.org/book/07_appendix/02_semantic_error.html#latch_inferred")
+    )]
+    #[error("latch is inferred for {identifier} because it is not assigned when {condition}")]
+    LatchInferred {
         identifier: String,
+        condition: String,
         #[source_code]
         input: NamedSource<String>,
         #[label("Error location")]
@@ -1061,10 +1416,141 @@ pub enum AnalyzerError {
         input: NamedSource<String>,
         #[label("Error location")]
         error_location: SourceSpan,
+        fixes: Vec<TextEdit>,
     },
 }
 
+/// Machine-applicable replacement of the source text
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct TextEdit {
+    /// Byte offset in the source text
+    pub offset: usize,
+    /// Byte length of the replaced text, 0 for insertion
+    pub length: usize,
+    pub text: String,
+}
+
+impl TextEdit {
+    pub fn insert(pos: u32, text: &str) -> Self {
+        Self {
+            offset: pos as usize,
+            length: 0,
+            text: text.to_string(),
+        }
+    }
+
+    pub fn replace(range: &TokenRange, text: &str) -> Self {
+        let span: SourceSpan = range.into();
+        Self {
+            offset: span.offset(),
+            length: span.len(),
+            text: text.to_string(),
+        }
+    }
+
+    fn end(&self) -> usize {
+        self.offset + self.length
+    }
+
+    fn overlaps(&self, other: &TextEdit) -> bool {
+        self.offset == other.offset || (self.offset < other.end() && other.offset < self.end())
+    }
+
+    /// Apply fixes of diagnostics to `text`.
+    /// A fix is skipped as a whole if any edit of it overlaps with an edit already accepted,
+    /// and identical edits are applied only once.
+    /// Returns the edited text and the number of applied fixes.
+    /// Fixes whose edits were all accepted already are not counted.
+    pub fn apply(text: &str, fixes: &[&[TextEdit]]) -> (String, usize) {
+        let mut accepted: Vec<&TextEdit> = Vec::new();
+        let mut count = 0;
+
+        for fix in fixes {
+            let edits: Vec<_> = fix.iter().filter(|x| !accepted.contains(x)).collect();
+            if edits.is_empty() {
+                continue;
+            }
+            let conflict = edits.iter().enumerate().any(|(i, x)| {
+                accepted.iter().chain(&edits[..i]).any(|y| x.overlaps(y))
+                    || text.get(x.offset..x.end()).is_none()
+            });
+            if !conflict {
+                accepted.extend(edits);
+                count += 1;
+            }
+        }
+
+        accepted.sort_by_key(|x| x.offset);
+        let mut ret = text.to_string();
+        for x in accepted.iter().rev() {
+            ret.replace_range(x.offset..x.end(), &x.text);
+        }
+        (ret, count)
+    }
+}
+
+/// Codes of diagnostics which are reported as warnings and can be suppressed by `#[allow(...)]`
+pub const WARNING_CODES: &[&str] = &[
+    "constant_condition",
+    "glitch_prone_crossing",
+    "implicit_extension",
+    "implicit_truncation",
+    "incomplete_enable_initialisation",
+    "invalid_identifier",
+    "latch_inferred",
+    "mismatch_port_width",
+    "mismatch_power_domain",
+    "mismatch_sign",
+    "missing_enable_statement",
+    "missing_port",
+    "non_exhaustive_case",
+    "unassign_variable",
+    "unreachable_case_arm",
+    "unreachable_generate_block",
+    "unsynchronized_crossing",
+    "unused_entity",
+    "unused_function",
+    "unused_package",
+    "unused_param",
+    "unused_port",
+    "unused_return",
+    "unused_variable",
+];
+
 impl AnalyzerError {
+    pub fn is_warning_code(code: &str) -> bool {
+        WARNING_CODES.contains(&code)
+    }
+
+    /// Text edits which fix this diagnostic
+    pub fn fixes(&self) -> &[TextEdit] {
+        match self {
+            AnalyzerError::MismatchPowerDomain { fixes, .. }
+            | AnalyzerError::MissingPort { fixes, .. }
+            | AnalyzerError::MissingTri { fixes, .. }
+            | AnalyzerError::UnusedFunction { fixes, .. }
+            | AnalyzerError::UnusedVariable { fixes, .. }
+            | AnalyzerError::WrongSeparator { fixes, .. } => fixes,
+            _ => &[],
+        }
+    }
+
+    pub fn code_name(&self) -> Option<String> {
+        self.code().map(|x| x.to_string())
+    }
+
+    /// Source name, line and column of the error location (1-origin)
+    pub fn location(&self) -> Option<(String, u32, u32)> {
+        let label = self.labels()?.next()?;
+        let contents = self.source_code()?.read_span(label.inner(), 0, 0).ok()?;
+        let name = contents.name()?.to_string();
+        Some((
+            name,
+            contents.line() as u32 + 1,
+            contents.column() as u32 + 1,
+        ))
+    }
+
     fn named_source(source: &str, token: &TokenRange) -> NamedSource<String> {
         NamedSource::new(token.beg.source.to_string(), source.to_string())
     }
@@ -1090,6 +1576,22 @@ impl AnalyzerError {
         }
     }
 
+    pub fn combinational_loop(chain: &str, source: &str, token: &TokenRange) -> Self {
+        AnalyzerError::CombinationalLoop {
+            chain: chain.to_string(),
+            input: AnalyzerError::named_source(source, token),
+            error_location: token.into(),
+        }
+    }
+
+    pub fn constant_condition(value: &str, source: &str, token: &TokenRange) -> Self {
+        AnalyzerError::ConstantCondition {
+            value: value.to_string(),
+            input: AnalyzerError::named_source(source, token),
+            error_location: token.into(),
+        }
+    }
+
     pub fn cyclic_type_dependency(
         source: &str,
         start: &str,
@@ -1112,6 +1614,34 @@ impl AnalyzerError {
         }
     }
 
+    pub fn elaboration_limit_exceeded(
+        identifier: &str,
+        limit: &str,
+        source: &str,
+        token: &TokenRange,
+    ) -> Self {
+        AnalyzerError::ElaborationLimitExceeded {
+            identifier: identifier.to_string(),
+            limit: limit.to_string(),
+            input: AnalyzerError::named_source(source, token),
+            error_location: token.into(),
+        }
+    }
+
+    pub fn evaluation_limit_exceeded(
+        function: &str,
+        limit: &str,
+        source: &str,
+        token: &TokenRange,
+    ) -> Self {
+        AnalyzerError::EvaluationLimitExceeded {
+            function: function.to_string(),
+            limit: limit.to_string(),
+            input: AnalyzerError::named_source(source, token),
+            error_location: token.into(),
+        }
+    }
+
     pub fn multiple_assignment(
         identifier: &str,
         source: &str,
@@ -1277,6 +1807,14 @@ impl AnalyzerError {
         }
     }
 
+    pub fn invalid_distribution(name: &str, source: &str, token: &TokenRange) -> Self {
+        AnalyzerError::InvalidDistribution {
+            name: name.into(),
+            input: AnalyzerError::named_source(source, token),
+            error_location: token.into(),
+        }
+    }
+
     pub fn invalid_test(cause: &str, source: &str, token: &TokenRange) -> Self {
         AnalyzerError::InvalidTest {
             cause: cause.into(),
@@ -1395,9 +1933,90 @@ impl AnalyzerError {
         }
     }
 
+    pub fn mismatch_port_width(
+        name: &str,
+        expected: usize,
+        actual: usize,
+        source: &str,
+        token: &TokenRange,
+    ) -> Self {
+        AnalyzerError::MismatchPortWidth {
+            name: name.to_string(),
+            expected,
+            actual,
+            input: AnalyzerError::named_source(source, token),
+            error_location: token.into(),
+        }
+    }
+
+    pub fn implicit_truncation(
+        name: &str,
+        expected: usize,
+        actual: usize,
+        source: &str,
+        token: &TokenRange,
+    ) -> Self {
+        AnalyzerError::ImplicitTruncation {
+            name: name.to_string(),
+            expected,
+            actual,
+            input: AnalyzerError::named_source(source, token),
+            error_location: token.into(),
+        }
+    }
+
+    pub fn implicit_extension(
+        name: &str,
+        expected: usize,
+        actual: usize,
+        source: &str,
+        token: &TokenRange,
+    ) -> Self {
+        AnalyzerError::ImplicitExtension {
+            name: name.to_string(),
+            expected,
+            actual,
+            input: AnalyzerError::named_source(source, token),
+            error_location: token.into(),
+        }
+    }
+
+    pub fn mismatch_sign(
+        name: &str,
+        expected: &str,
+        actual: &str,
+        source: &str,
+        token: &TokenRange,
+    ) -> Self {
+        AnalyzerError::MismatchSign {
+            name: name.to_string(),
+            expected: expected.to_string(),
+            actual: actual.to_string(),
+            input: AnalyzerError::named_source(source, token),
+            error_location: token.into(),
+        }
+    }
+
+    pub fn mismatch_port_dimension(
+        name: &str,
+        expected: &str,
+        actual: &str,
+        source: &str,
+        token: &TokenRange,
+    ) -> Self {
+        AnalyzerError::MismatchPortDimension {
+            name: name.to_string(),
+            expected: expected.to_string(),
+            actual: actual.to_string(),
+            input: AnalyzerError::named_source(source, token),
+            error_location: token.into(),
+        }
+    }
+
     pub fn mismatch_power_domain(
         power_domain: &str,
         other_domain: &str,
+        declaration: Option<&TokenRange>,
         source: &str,
         token: &TokenRange,
         other_token: &TokenRange,
@@ -1408,6 +2027,54 @@ impl AnalyzerError {
             input: AnalyzerError::named_source(source, token),
             error_location: token.into(),
             other_location: other_token.into(),
+            fixes: declaration
+                .map(|x| {
+                    vec![
+                        TextEdit::insert(x.beg.pos, "unsafe (pdc) {\n"),
+                        TextEdit::insert(x.end.pos + x.end.length, "\n}"),
+                    ]
+                })
+                .unwrap_or_default(),
+        }
+    }
+
+    pub fn unsynchronized_crossing(
+        power_domain: &str,
+        other_domain: &str,
+        source: &str,
+        token: &TokenRange,
+        other_token: &TokenRange,
+    ) -> Self {
+        AnalyzerError::UnsynchronizedCrossing {
+            power_domain: power_domain.to_string(),
+            other_domain: other_domain.to_string(),
+            input: AnalyzerError::named_source(source, token),
+            error_location: token.into(),
+            other_location: other_token.into(),
+        }
+    }
+
+    pub fn insufficient_synchronizer_stages(
+        identifier: &str,
+        required: usize,
+        actual: usize,
+        source: &str,
+        token: &TokenRange,
+    ) -> Self {
+        AnalyzerError::InsufficientSynchronizerStages {
+            identifier: identifier.to_string(),
+            required,
+            actual,
+            input: AnalyzerError::named_source(source, token),
+            error_location: token.into(),
+        }
+    }
+
+    pub fn glitch_prone_crossing(signals: &str, source: &str, token: &TokenRange) -> Self {
+        AnalyzerError::GlitchProneCrossing {
+            signals: signals.to_string(),
+            input: AnalyzerError::named_source(source, token),
+            error_location: token.into(),
         }
     }
 
@@ -1446,10 +2113,19 @@ impl AnalyzerError {
         }
     }
 
+    pub fn incomplete_enable_initialisation(names: &str, source: &str, token: &TokenRange) -> Self {
+        AnalyzerError::IncompleteEnableInitialisation {
+            names: names.to_string(),
+            input: AnalyzerError::named_source(source, token),
+            error_location: token.into(),
+        }
+    }
+
     pub fn missing_tri(source: &str, token: &TokenRange) -> Self {
         AnalyzerError::MissingTri {
             input: AnalyzerError::named_source(source, token),
             error_location: token.into(),
+            fixes: vec![TextEdit::insert(token.beg.pos, "tri ")],
         }
     }
 
@@ -1474,12 +2150,27 @@ impl AnalyzerError {
         }
     }
 
-    pub fn missing_port(name: &str, port: &str, source: &str, token: &TokenRange) -> Self {
+    pub fn missing_port(
+        name: &str,
+        port: &str,
+        fixes: &[TextEdit],
+        source: &str,
+        token: &TokenRange,
+    ) -> Self {
         AnalyzerError::MissingPort {
             name: name.to_string(),
             port: port.to_string(),
             input: AnalyzerError::named_source(source, token),
             error_location: token.into(),
+            fixes: fixes.to_vec(),
+        }
+    }
+
+    pub fn non_exhaustive_case(missing: &str, source: &str, token: &TokenRange) -> Self {
+        AnalyzerError::NonExhaustiveCase {
+            missing: missing.to_string(),
+            input: AnalyzerError::named_source(source, token),
+            error_location: token.into(),
         }
     }
 
@@ -1697,6 +2388,7 @@ impl AnalyzerError {
             identifier: identifier.to_string(),
             input: AnalyzerError::named_source(source, token),
             error_location: token.into(),
+            fixes: vec![TextEdit::insert(token.beg.pos, "_")],
         }
     }
 
@@ -1708,6 +2400,61 @@ impl AnalyzerError {
         }
     }
 
+    pub fn unused_port(identifier: &str, source: &str, token: &TokenRange) -> Self {
+        AnalyzerError::UnusedPort {
+            identifier: identifier.to_string(),
+            input: AnalyzerError::named_source(source, token),
+            error_location: token.into(),
+        }
+    }
+
+    pub fn unused_param(identifier: &str, source: &str, token: &TokenRange) -> Self {
+        AnalyzerError::UnusedParam {
+            identifier: identifier.to_string(),
+            input: AnalyzerError::named_source(source, token),
+            error_location: token.into(),
+        }
+    }
+
+    pub fn unused_function(identifier: &str, source: &str, token: &TokenRange) -> Self {
+        AnalyzerError::UnusedFunction {
+            identifier: identifier.to_string(),
+            input: AnalyzerError::named_source(source, token),
+            error_location: token.into(),
+            fixes: vec![TextEdit::insert(token.beg.pos, "_")],
+        }
+    }
+
+    pub fn unused_entity(identifier: &str, source: &str, token: &TokenRange) -> Self {
+        AnalyzerError::UnusedEntity {
+            identifier: identifier.to_string(),
+            input: AnalyzerError::named_source(source, token),
+            error_location: token.into(),
+        }
+    }
+
+    pub fn unused_package(identifier: &str, source: &str, token: &TokenRange) -> Self {
+        AnalyzerError::UnusedPackage {
+            identifier: identifier.to_string(),
+            input: AnalyzerError::named_source(source, token),
+            error_location: token.into(),
+        }
+    }
+
+    pub fn unreachable_case_arm(source: &str, token: &TokenRange) -> Self {
+        AnalyzerError::UnreachableCaseArm {
+            input: AnalyzerError::named_source(source, token),
+            error_location: token.into(),
+        }
+    }
+
+    pub fn unreachable_generate_block(source: &str, token: &TokenRange) -> Self {
+        AnalyzerError::UnreachableGenerateBlock {
+            input: AnalyzerError::named_source(source, token),
+            error_location: token.into(),
+        }
+    }
+
     pub fn unassign_variable(identifier: &str, source: &str, token: &TokenRange) -> Self {
         AnalyzerError::UnassignVariable {
             identifier: identifier.to_string(),
@@ -1716,14 +2463,16 @@ impl AnalyzerError {
         }
     }
 
-    pub fn uncovered_branch(
+    pub fn latch_inferred(
         identifier: &str,
+        condition: &str,
         source: &str,
         token: &TokenRange,
         uncovered: &TokenRange,
     ) -> Self {
-        AnalyzerError::UncoveredBranch {
+        AnalyzerError::LatchInferred {
             identifier: identifier.to_string(),
+            condition: condition.to_string(),
             input: AnalyzerError::named_source(source, token),
             error_location: token.into(),
             uncovered: uncovered.into(),
@@ -1754,6 +2503,7 @@ impl AnalyzerError {
             valid_separator: valid_separator.to_string(),
             input: AnalyzerError::named_source(source, token),
             error_location: token.into(),
+            fixes: vec![TextEdit::replace(token, valid_separator)],
         }
     }
 }
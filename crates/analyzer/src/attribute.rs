@@ -1,9 +1,10 @@
+use crate::analyzer_error::AnalyzerError;
 use std::cell::RefCell;
 use std::fmt;
 use veryla_parser::resource_table::{self, StrId};
-use veryla_parser::veryla_token::Token;
+use veryla_parser::veryla_token::{Token, TokenRange};
 
-#[derive(Copy, Clone, Debug, PartialEq, Eq)]
+#[derive(Clone, Debug, PartialEq, Eq)]
 pub enum Attribute {
     Ifdef(StrId),
     Ifndef(StrId),
@@ -11,8 +12,9 @@ pub enum Attribute {
     Allow(AllowItem),
     EnumEncoding(EnumEncodingItem),
     EnumMemberPrefix(StrId),
-    Test(Token, Option<StrId>),
+    Test(Token, Option<StrId>, Vec<SweepItem>),
     CondType(CondTypeItem),
+    Synchronizer(usize),
 }
 
 impl fmt::Display for Attribute {
@@ -24,8 +26,9 @@ impl fmt::Display for Attribute {
             Attribute::Allow(x) => format!("allow({})", x),
             Attribute::EnumEncoding(x) => format!("enum_encoding({})", x),
             Attribute::EnumMemberPrefix(x) => format!("enum_member_prefix({})", x),
-            Attribute::Test(x, _) => format!("test({})", x.text),
+            Attribute::Test(x, _, _) => format!("test({})", x.text),
             Attribute::CondType(x) => format!("cond_type({})", x),
+            Attribute::Synchronizer(x) => format!("synchronizer(stages = {})", x),
         };
         text.fmt(f)
     }
@@ -50,8 +53,12 @@ fn get_arg_ident(
         let args: Vec<g::AttributeItem> = x.attribute_list.as_ref().into();
         if args.len() <= pos {
             None
-        } else if let g::AttributeItem::Identifier(ref x) = args[pos] {
-            Some(x.identifier.identifier_token.token)
+        } else if let g::AttributeItem::IdentifierAttributeItemOpt(ref x) = args[pos] {
+            if x.attribute_item_opt.is_none() {
+                Some(x.identifier.identifier_token.token)
+            } else {
+                None
+            }
         } else {
             None
         }
@@ -80,6 +87,140 @@ fn get_arg_string(
     }
 }
 
+fn get_arg_call(
+    args: &Option<veryla_parser::veryla_grammar_trait::AttributeOpt>,
+    name: StrId,
+) -> Option<Vec<veryla_parser::veryla_grammar_trait::AttributeItem>> {
+    use veryla_parser::veryla_grammar_trait as g;
+
+    if let Some(ref x) = args {
+        let args: Vec<g::AttributeItem> = x.attribute_list.as_ref().into();
+        for arg in args {
+            if let g::AttributeItem::IdentifierAttributeItemOpt(ref x) = arg {
+                if x.identifier.identifier_token.token.text != name {
+                    continue;
+                }
+                if let Some(ref x) = x.attribute_item_opt {
+                    if let g::AttributeArgument::LParenAttributeListRParen(ref x) =
+                        *x.attribute_argument
+                    {
+                        return Some(x.attribute_list.as_ref().into());
+                    }
+                }
+            }
+        }
+    }
+    None
+}
+
+fn get_arg_number(
+    args: &Option<veryla_parser::veryla_grammar_trait::AttributeOpt>,
+    name: StrId,
+) -> Option<Token> {
+    use veryla_parser::veryla_grammar_trait as g;
+
+    if let Some(ref x) = args {
+        let args: Vec<g::AttributeItem> = x.attribute_list.as_ref().into();
+        for arg in args {
+            if let g::AttributeItem::IdentifierAttributeItemOpt(ref x) = arg {
+                if x.identifier.identifier_token.token.text != name {
+                    continue;
+                }
+                if let Some(ref x) = x.attribute_item_opt {
+                    if let g::AttributeArgument::EquAttributeItem(ref x) = *x.attribute_argument {
+                        if let g::AttributeItem::NumberAttributeItemOpt0(ref x) = *x.attribute_item
+                        {
+                            if x.attribute_item_opt0.is_none() {
+                                let number: TokenRange = x.number.as_ref().into();
+                                return Some(number.beg);
+                            }
+                        }
+                    }
+                }
+            }
+        }
+    }
+    None
+}
+
+fn get_sweep_items(
+    args: &[veryla_parser::veryla_grammar_trait::AttributeItem],
+) -> Option<Vec<SweepItem>> {
+    use veryla_parser::veryla_grammar_trait as g;
+
+    let mut ret = Vec::new();
+    for arg in args {
+        let g::AttributeItem::IdentifierAttributeItemOpt(ref x) = arg else {
+            return None;
+        };
+        let name = x.identifier.identifier_token.token.text;
+        let Some(g::AttributeArgument::EquAttributeItem(ref x)) = x
+            .attribute_item_opt
+            .as_ref()
+            .map(|x| &*x.attribute_argument)
+        else {
+            return None;
+        };
+        let g::AttributeItem::LBracketAttributeListRBracket(ref x) = *x.attribute_item else {
+            return None;
+        };
+
+        let mut values = Vec::new();
+        let items: Vec<g::AttributeItem> = x.attribute_list.as_ref().into();
+        for item in items {
+            let g::AttributeItem::NumberAttributeItemOpt0(ref x) = item else {
+                return None;
+            };
+            let number: TokenRange = x.number.as_ref().into();
+            let suffix = x
+                .attribute_item_opt0
+                .as_ref()
+                .map(|x| x.identifier.identifier_token.to_string());
+            values.push(parse_sweep_value(
+                &number.beg.to_string(),
+                suffix.as_deref(),
+            )?);
+        }
+
+        ret.push(SweepItem { name, values });
+    }
+
+    if ret.is_empty() {
+        None
+    } else {
+        Some(ret)
+    }
+}
+
+/// Converts a number with an optional SI suffix (e.g. `10k`, `1.5n`) into a plain number
+fn parse_sweep_value(number: &str, suffix: Option<&str>) -> Option<String> {
+    let number = number.replace('_', "");
+    let exponent: i32 = match suffix {
+        None => return Some(number),
+        Some("f") => -15,
+        Some("p") => -12,
+        Some("n") => -9,
+        Some("u") => -6,
+        Some("m") => -3,
+        Some("k") => 3,
+        Some("M") => 6,
+        Some("G") => 9,
+        Some("T") => 12,
+        _ => return None,
+    };
+    let value: f64 = number.parse().ok()?;
+    let value = if exponent < 0 {
+        value / 10f64.powi(-exponent)
+    } else {
+        value * 10f64.powi(exponent)
+    };
+    if value.fract() == 0.0 && value.abs() < i64::MAX as f64 {
+        Some(format!("{}", value as i64))
+    } else {
+        Some(format!("{}", value))
+    }
+}
+
 struct Pattern {
     pub ifdef: StrId,
     pub ifndef: StrId,
@@ -88,17 +229,21 @@ struct Pattern {
     pub missing_port: StrId,
     pub missing_enable_statement: StrId,
     pub unused_variable: StrId,
+    pub latch: StrId,
     pub enum_encoding: StrId,
     pub sequential: StrId,
     pub onehot: StrId,
     pub gray: StrId,
     pub enum_member_prefix: StrId,
     pub test: StrId,
+    pub sweep: StrId,
     pub cond_type: StrId,
     pub unique: StrId,
     pub unique0: StrId,
     pub priority: StrId,
     pub none: StrId,
+    pub synchronizer: StrId,
+    pub stages: StrId,
 }
 
 impl Pattern {
@@ -111,17 +256,21 @@ impl Pattern {
             missing_port: resource_table::insert_str("missing_port"),
             missing_enable_statement: resource_table::insert_str("missing_enable_statement"),
             unused_variable: resource_table::insert_str("unused_variable"),
+            latch: resource_table::insert_str("latch"),
             enum_encoding: resource_table::insert_str("enum_encoding"),
             sequential: resource_table::insert_str("sequential"),
             onehot: resource_table::insert_str("onehot"),
             gray: resource_table::insert_str("gray"),
             enum_member_prefix: resource_table::insert_str("enum_member_prefix"),
             test: resource_table::insert_str("test"),
+            sweep: resource_table::insert_str("sweep"),
             cond_type: resource_table::insert_str("cond_type"),
             unique: resource_table::insert_str("unique"),
             unique0: resource_table::insert_str("unique0"),
             priority: resource_table::insert_str("priority"),
             none: resource_table::insert_str("none"),
+            synchronizer: resource_table::insert_str("synchronizer"),
+            stages: resource_table::insert_str("stages"),
         }
     }
 }
@@ -167,6 +316,10 @@ impl TryFrom<&veryla_parser::veryla_grammar_trait::Attribute> for Attribute {
                         x if x == pat.unused_variable => {
                             Ok(Attribute::Allow(AllowItem::UnusedVariable))
                         }
+                        x if x == pat.latch => Ok(Attribute::Allow(AllowItem::Latch)),
+                        x if AnalyzerError::is_warning_code(&x.to_string()) => {
+                            Ok(Attribute::Allow(AllowItem::Rule(x)))
+                        }
                         _ => Err(AttributeError::InvalidAllow(arg.text)),
                     }
                 } else {
@@ -203,9 +356,20 @@ impl TryFrom<&veryla_parser::veryla_grammar_trait::Attribute> for Attribute {
             x if x == pat.test => {
                 let arg = get_arg_ident(&value.attribute_opt, 0);
                 let top = get_arg_ident(&value.attribute_opt, 1);
+                let sweep = if let Some(x) = get_arg_call(&value.attribute_opt, pat.sweep) {
+                    if let Some(x) = get_sweep_items(&x) {
+                        x
+                    } else {
+                        return Err(AttributeError::MismatchArgs(
+                            "sweep list like `sweep(R = [1k, 10k], C = [1n])`",
+                        ));
+                    }
+                } else {
+                    Vec::new()
+                };
 
                 if let Some(arg) = arg {
-                    Ok(Attribute::Test(arg, top.map(|x| x.text)))
+                    Ok(Attribute::Test(arg, top.map(|x| x.text), sweep))
                 } else {
                     Err(AttributeError::MismatchArgs("single identifier"))
                 }
@@ -225,24 +389,56 @@ impl TryFrom<&veryla_parser::veryla_grammar_trait::Attribute> for Attribute {
                     Err(AttributeError::MismatchArgs("condition type"))
                 }
             }
+            x if x == pat.synchronizer => {
+                let stages = get_arg_number(&value.attribute_opt, pat.stages)
+                    .and_then(|x| x.to_string().replace('_', "").parse::<usize>().ok());
+
+                match stages {
+                    Some(x) if x >= 2 => Ok(Attribute::Synchronizer(x)),
+                    _ => Err(AttributeError::MismatchArgs(
+                        "number of stages like `stages = 2`, at least 2",
+                    )),
+                }
+            }
             _ => Err(AttributeError::UnknownAttribute),
         })
     }
 }
 
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct SweepItem {
+    pub name: StrId,
+    pub values: Vec<String>,
+}
+
 #[derive(Copy, Clone, Debug, PartialEq, Eq)]
 pub enum AllowItem {
     MissingPort,
     MissingEnableStatement,
     UnusedVariable,
+    Latch,
+    /// Any other warning code
+    Rule(StrId),
+}
+
+impl AllowItem {
+    /// Whether diagnostics with `code` are suppressed by this item
+    pub fn allows(&self, code: &str) -> bool {
+        match self {
+            AllowItem::Latch => code == "latch_inferred",
+            _ => self.to_string() == code,
+        }
+    }
 }
 
 impl fmt::Display for AllowItem {
     fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
         let text = match self {
-            AllowItem::MissingPort => "missing_port",
-            AllowItem::MissingEnableStatement => "missing_enable_statement",
-            AllowItem::UnusedVariable => "unused_variable",
+            AllowItem::MissingPort => "missing_port".to_string(),
+            AllowItem::MissingEnableStatement => "missing_enable_statement".to_string(),
+            AllowItem::UnusedVariable => "unused_variable".to_string(),
+            AllowItem::Latch => "latch".to_string(),
+            AllowItem::Rule(x) => x.to_string(),
         };
         text.fmt(f)
     }
@@ -0,0 +1,290 @@
+//! Values covered by arms of `case` to check exhaustiveness and unreachable arms
+
+use crate::evaluator::{fixed, single_factor, Evaluated, Evaluator};
+use crate::symbol::{Symbol, SymbolKind, Type, TypeKind};
+use crate::symbol_table;
+use veryla_parser::veryla_grammar_trait::*;
+
+/// Cases wider than this are not analyzed
+const MAX_WIDTH: usize = 64;
+/// Wildcard patterns which are split into more intervals than this are not analyzed
+const MAX_INTERVALS: usize = 4096;
+/// Number of missing values listed in the diagnostic
+const MAX_MISSING: usize = 4;
+
+fn max_value(width: usize) -> u64 {
+    if width >= 64 {
+        u64::MAX
+    } else {
+        (1 << width) - 1
+    }
+}
+
+/// Set of values as sorted, disjoint and inclusive intervals
+#[derive(Default)]
+pub struct Coverage {
+    intervals: Vec<(u64, u64)>,
+}
+
+impl Coverage {
+    pub fn insert(&mut self, beg: u64, end: u64) {
+        self.intervals.push((beg, end));
+        self.intervals.sort_unstable();
+
+        let mut merged: Vec<(u64, u64)> = Vec::new();
+        for (beg, end) in self.intervals.drain(..) {
+            if let Some(last) = merged.last_mut() {
+                if beg <= last.1.saturating_add(1) {
+                    last.1 = last.1.max(end);
+                    continue;
+                }
+            }
+            merged.push((beg, end));
+        }
+        self.intervals = merged;
+    }
+
+    pub fn contains(&self, beg: u64, end: u64) -> bool {
+        self.intervals.iter().any(|x| x.0 <= beg && end <= x.1)
+    }
+
+    /// Values in `0..=max` which are not covered, up to `count`
+    pub fn missing(&self, max: u64, count: usize) -> Vec<u64> {
+        let mut ret = Vec::new();
+        let mut next = Some(0);
+        for (beg, end) in &self.intervals {
+            while let Some(x) = next.filter(|x| x < beg && ret.len() < count) {
+                ret.push(x);
+                next = x.checked_add(1);
+            }
+            next = next.map(|x| x.max(*end)).and_then(|x| x.checked_add(1));
+        }
+        while let Some(x) = next.filter(|x| *x <= max && ret.len() < count) {
+            ret.push(x);
+            next = x.checked_add(1);
+        }
+        ret
+    }
+}
+
+/// Values which `case` can take
+pub enum Domain {
+    Bits(usize),
+    /// Name and value of each enum member
+    Enum(usize, Vec<(String, u64)>),
+}
+
+impl Domain {
+    pub fn new(evaluator: &mut Evaluator, target: &Expression) -> Option<Self> {
+        if let Some(symbol) = target_symbol(target) {
+            if let Some(x) = enum_domain(&symbol) {
+                return Some(x);
+            }
+            if let Some(width) = evaluator.symbol_width(&symbol) {
+                return (width > 0 && width <= MAX_WIDTH).then_some(Domain::Bits(width));
+            }
+        }
+        match evaluator.expression(target) {
+            Evaluated::Fixed { width, .. } | Evaluated::Variable { width }
+                if width > 0 && width <= MAX_WIDTH =>
+            {
+                Some(Domain::Bits(width))
+            }
+            _ => None,
+        }
+    }
+
+    pub fn width(&self) -> usize {
+        match self {
+            Domain::Bits(x) => *x,
+            Domain::Enum(x, _) => *x,
+        }
+    }
+
+    /// Examples of values which are not covered
+    pub fn missing(&self, coverage: &Coverage) -> Vec<String> {
+        match self {
+            Domain::Bits(width) => coverage
+                .missing(max_value(*width), MAX_MISSING + 1)
+                .into_iter()
+                .map(|x| {
+                    if *width <= 8 {
+                        format!("{width}'b{x:0width$b}")
+                    } else {
+                        format!("{width}'h{x:x}")
+                    }
+                })
+                .collect(),
+            Domain::Enum(_, members) => members
+                .iter()
+                .filter(|(_, x)| !coverage.contains(*x, *x))
+                .take(MAX_MISSING + 1)
+                .map(|(name, _)| name.clone())
+                .collect(),
+        }
+    }
+}
+
+/// Format missing values with `...` if there are more than listed
+pub fn missing_text(mut missing: Vec<String>) -> String {
+    if missing.len() > MAX_MISSING {
+        missing.truncate(MAX_MISSING);
+        missing.push("...".to_string());
+    }
+    missing.join(", ")
+}
+
+fn enum_type(r#type: &Type, symbol: &Symbol) -> Option<Symbol> {
+    let TypeKind::UserDefined(ref path) = r#type.kind else {
+        return None;
+    };
+    if !r#type.width.is_empty() || !r#type.array.is_empty() {
+        return None;
+    }
+    let found = symbol_table::resolve((path, &symbol.namespace)).ok()?.found;
+    match found.kind {
+        SymbolKind::Enum(_) => Some(found),
+        SymbolKind::TypeDef(ref x) => enum_type(&x.r#type, &found),
+        _ => None,
+    }
+}
+
+/// Variable or port referred by `target` without select
+fn target_symbol(target: &Expression) -> Option<Symbol> {
+    let Factor::IdentifierFactor(x) = single_factor(target)? else {
+        return None;
+    };
+    let x = &x.identifier_factor;
+    let ident = &x.expression_identifier;
+    if x.identifier_factor_opt.is_some()
+        || !ident.expression_identifier_list.is_empty()
+        || !ident.expression_identifier_list0.is_empty()
+    {
+        return None;
+    }
+
+    let symbol = symbol_table::resolve(ident.as_ref()).ok()?.found;
+    match symbol.kind {
+        SymbolKind::Variable(_) | SymbolKind::Port(_) => Some(symbol),
+        _ => None,
+    }
+}
+
+fn enum_domain(symbol: &Symbol) -> Option<Domain> {
+    let r#enum = match symbol.kind {
+        SymbolKind::Variable(ref x) => enum_type(&x.r#type, symbol)?,
+        SymbolKind::Port(ref x) => enum_type(x.r#type.as_ref()?, symbol)?,
+        _ => return None,
+    };
+    let SymbolKind::Enum(ref property) = r#enum.kind else {
+        return None;
+    };
+    if property.width == 0 || property.width > MAX_WIDTH {
+        return None;
+    }
+
+    let mut members = Vec::new();
+    for id in &property.members {
+        let member = symbol_table::get(*id)?;
+        let value = fixed(member.evaluate())? as u64 & max_value(property.width);
+        members.push((format!("{}::{}", r#enum.token, member.token), value));
+    }
+    Some(Domain::Enum(property.width, members))
+}
+
+/// Value and don't-care bits of a number containing `x` or `z` digits
+fn wildcard(arg: &Expression) -> Option<(u64, u64)> {
+    let Factor::Number(x) = single_factor(arg)? else {
+        return None;
+    };
+    let Number::IntegralNumber(ref x) = *x.number else {
+        return None;
+    };
+    let IntegralNumber::Based(ref x) = *x.integral_number else {
+        return None;
+    };
+
+    let text = x.based.based_token.to_string().replace('_', "");
+    let (_, rest) = text.split_once('\'')?;
+    let rest = rest.strip_prefix('s').unwrap_or(rest);
+    let (base, digits) = rest.split_at(1);
+    let bits = match base {
+        "b" => 1,
+        "o" => 3,
+        "h" => 4,
+        _ => return None,
+    };
+    if !digits.contains(['x', 'X', 'z', 'Z']) {
+        return None;
+    }
+
+    let mut value: u64 = 0;
+    let mut mask: u64 = 0;
+    for c in digits.chars() {
+        value = value.checked_shl(bits)?;
+        mask = mask.checked_shl(bits)?;
+        match c {
+            'x' | 'X' | 'z' | 'Z' => mask |= (1 << bits) - 1,
+            _ => value |= c.to_digit(1 << bits)? as u64,
+        }
+    }
+    Some((value, mask))
+}
+
+/// Intervals of values matched by a case item, `None` if it is not known statically.
+/// Items which don't fit in `width` match no value.
+pub fn range_item(
+    evaluator: &mut Evaluator,
+    arg: &RangeItem,
+    width: usize,
+) -> Option<Vec<(u64, u64)>> {
+    let max = max_value(width);
+    let range = &arg.range;
+
+    if let Some(ref x) = range.range_opt {
+        let beg = u64::try_from(fixed(evaluator.expression(&range.expression))?).ok()?;
+        let end = u64::try_from(fixed(evaluator.expression(&x.expression))?).ok()?;
+        let end = match *x.range_operator {
+            RangeOperator::DotDot(_) => end.checked_sub(1),
+            RangeOperator::DotDotEqu(_) => Some(end),
+        };
+        return match end {
+            Some(end) if beg <= end && beg <= max => Some(vec![(beg, end.min(max))]),
+            _ => Some(Vec::new()),
+        };
+    }
+
+    if let Some((value, mask)) = wildcard(&range.expression) {
+        let value = value & !mask;
+        if value > max {
+            return Some(Vec::new());
+        }
+        let mask = mask & max;
+        let low = mask.trailing_ones();
+        let low_mask = max_value(low as usize);
+        let high = mask & !low_mask;
+        if 1usize.checked_shl(high.count_ones())? > MAX_INTERVALS {
+            return None;
+        }
+
+        // Enumerate every subset of don't-care bits above the lowest run
+        let mut ret = Vec::new();
+        let mut sub = high;
+        loop {
+            let beg = value | sub;
+            ret.push((beg, beg | low_mask));
+            if sub == 0 {
+                break;
+            }
+            sub = (sub - 1) & high;
+        }
+        return Some(ret);
+    }
+
+    // Negative items depend on the signedness of the selector which is not known here
+    let value = u64::try_from(fixed(evaluator.expression(&range.expression))?).ok()?;
+    if value > max {
+        return Some(Vec::new());
+    }
+    Some(vec![(value, value)])
+}
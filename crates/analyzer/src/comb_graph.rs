@@ -0,0 +1,290 @@
+use crate::symbol::{Direction, SymbolId, SymbolKind};
+use crate::symbol_table;
+use crate::var_ref::VarRefPath;
+use std::collections::{HashMap, HashSet, VecDeque};
+use veryla_parser::resource_table::{StrId, TokenId};
+use veryla_parser::veryla_token::Token;
+
+#[derive(Clone, Debug)]
+pub enum CombDependency {
+    /// `assign` / `let` declaration, or assignment statement in `always_comb`
+    Statement {
+        token: Token,
+        /// `always_comb` which contains the statement
+        block: Option<Token>,
+        sources: Vec<VarRefPath>,
+        targets: Vec<VarRefPath>,
+    },
+    /// Instance whose combinational paths are given by the instantiated entity
+    Instance { token: Token, symbol: SymbolId },
+}
+
+#[derive(Clone, Debug)]
+struct CombNode {
+    token: Token,
+    /// `always_comb` and the order of the statement in it
+    block: Option<(TokenId, usize)>,
+    sources: Vec<VarRefPath>,
+    targets: Vec<VarRefPath>,
+}
+
+#[derive(Clone, Debug)]
+pub struct CombLoop {
+    pub token: Token,
+    /// Signals which form the loop
+    pub signals: Vec<VarRefPath>,
+}
+
+#[derive(Default)]
+pub struct CombGraph {
+    dependencies: HashMap<SymbolId, Vec<CombDependency>>,
+    /// Pairs of input and output port which have combinational path
+    summaries: HashMap<SymbolId, Vec<(StrId, StrId)>>,
+    visiting: HashSet<SymbolId>,
+}
+
+fn overlapped(x: &VarRefPath, y: &VarRefPath) -> bool {
+    x.may_fully_included(y) || y.may_fully_included(x)
+}
+
+fn driven_signal(from: &CombNode, to: &CombNode) -> Option<VarRefPath> {
+    // Statements in an `always_comb` only see assignments which precede them
+    if let (Some((from_block, from_order)), Some((to_block, to_order))) = (from.block, to.block) {
+        if from_block == to_block && from_order >= to_order {
+            return None;
+        }
+    }
+
+    from.targets
+        .iter()
+        .find(|x| to.sources.iter().any(|y| overlapped(x, y)))
+        .cloned()
+}
+
+fn edges(nodes: &[CombNode]) -> Vec<Vec<usize>> {
+    nodes
+        .iter()
+        .map(|from| {
+            nodes
+                .iter()
+                .enumerate()
+                .filter(|(_, to)| driven_signal(from, to).is_some())
+                .map(|(i, _)| i)
+                .collect()
+        })
+        .collect()
+}
+
+fn reachable(edges: &[Vec<usize>], starts: &[usize]) -> Vec<bool> {
+    let mut ret = vec![false; edges.len()];
+    let mut queue: VecDeque<_> = starts.iter().copied().collect();
+    for x in starts {
+        ret[*x] = true;
+    }
+    while let Some(x) = queue.pop_front() {
+        for y in &edges[x] {
+            if !ret[*y] {
+                ret[*y] = true;
+                queue.push_back(*y);
+            }
+        }
+    }
+    ret
+}
+
+/// Shortest path of nodes which starts at `start` and returns to it
+fn shortest_cycle(edges: &[Vec<usize>], start: usize) -> Option<Vec<usize>> {
+    let mut parent = vec![None; edges.len()];
+    let mut queue = VecDeque::from([start]);
+    while let Some(x) = queue.pop_front() {
+        for y in &edges[x] {
+            if *y == start {
+                let mut ret = vec![x];
+                let mut x = x;
+                while let Some(p) = parent[x] {
+                    ret.push(p);
+                    x = p;
+                }
+                ret.reverse();
+                return Some(ret);
+            }
+            if parent[*y].is_none() {
+                parent[*y] = Some(x);
+                queue.push_back(*y);
+            }
+        }
+    }
+    None
+}
+
+impl CombGraph {
+    pub fn new() -> Self {
+        Self {
+            dependencies: symbol_table::get_comb_dependency_list(),
+            ..Default::default()
+        }
+    }
+
+    /// Combinational loops in `entity`, one for each set of strongly connected statements
+    pub fn loops(&mut self, entity: SymbolId) -> Vec<CombLoop> {
+        let nodes = self.nodes(entity);
+        let edges = edges(&nodes);
+        let reverse_edges = {
+            let mut ret = vec![Vec::new(); nodes.len()];
+            for (from, x) in edges.iter().enumerate() {
+                for to in x {
+                    ret[*to].push(from);
+                }
+            }
+            ret
+        };
+
+        let mut ret = Vec::new();
+        let mut covered = vec![false; nodes.len()];
+        for start in 0..nodes.len() {
+            if covered[start] {
+                continue;
+            }
+            let Some(cycle) = shortest_cycle(&edges, start) else {
+                continue;
+            };
+
+            let forward = reachable(&edges, &[start]);
+            let backward = reachable(&reverse_edges, &[start]);
+            for (i, x) in covered.iter_mut().enumerate() {
+                *x |= forward[i] && backward[i];
+            }
+
+            let signals = cycle
+                .iter()
+                .enumerate()
+                .filter_map(|(i, x)| {
+                    let next = cycle[(i + 1) % cycle.len()];
+                    driven_signal(&nodes[*x], &nodes[next])
+                })
+                .collect();
+            ret.push(CombLoop {
+                token: nodes[start].token,
+                signals,
+            });
+        }
+        ret
+    }
+
+    fn nodes(&mut self, entity: SymbolId) -> Vec<CombNode> {
+        let dependencies = self.dependencies.get(&entity).cloned().unwrap_or_default();
+
+        let mut ret = Vec::new();
+        for (i, x) in dependencies.into_iter().enumerate() {
+            match x {
+                CombDependency::Statement {
+                    token,
+                    block,
+                    sources,
+                    targets,
+                } => ret.push(CombNode {
+                    token,
+                    block: block.map(|x| (x.id, i)),
+                    sources,
+                    targets,
+                }),
+                CombDependency::Instance { token, symbol } => {
+                    ret.append(&mut self.instance_nodes(token, symbol));
+                }
+            }
+        }
+        ret
+    }
+
+    fn instance_nodes(&mut self, token: Token, symbol: SymbolId) -> Vec<CombNode> {
+        let Some(symbol) = symbol_table::get(symbol) else {
+            return Vec::new();
+        };
+        let SymbolKind::Instance(ref x) = symbol.kind else {
+            return Vec::new();
+        };
+        let Ok(found) = symbol_table::resolve((&x.type_name.mangled_path(), &symbol.namespace))
+        else {
+            return Vec::new();
+        };
+        let entity = match found.found.kind {
+            SymbolKind::Entity(_) => found.found.id,
+            SymbolKind::GenericInstance(ref x) => x.base,
+            _ => return Vec::new(),
+        };
+
+        let connected = |name: StrId| -> Vec<VarRefPath> {
+            x.connects
+                .iter()
+                .filter(|(port, _)| port.text == name)
+                .flat_map(|(_, targets)| targets.iter())
+                .filter_map(|target| VarRefPath::try_from((target, &symbol.namespace)).ok())
+                .collect()
+        };
+
+        self.summary(entity)
+            .into_iter()
+            .map(|(input, output)| CombNode {
+                token,
+                block: None,
+                sources: connected(input),
+                targets: connected(output),
+            })
+            .filter(|x| !x.sources.is_empty() && !x.targets.is_empty())
+            .collect()
+    }
+
+    fn summary(&mut self, entity: SymbolId) -> Vec<(StrId, StrId)> {
+        if let Some(x) = self.summaries.get(&entity) {
+            return x.clone();
+        }
+
+        // Recursive instantiation is reported by elaboration
+        if !self.visiting.insert(entity) {
+            return Vec::new();
+        }
+
+        let ports = match symbol_table::get(entity).map(|x| x.kind) {
+            Some(SymbolKind::Entity(x)) => x.ports,
+            _ => Vec::new(),
+        };
+        let nodes = self.nodes(entity);
+        let edges = edges(&nodes);
+
+        let port_path = |x: SymbolId| VarRefPath::new((&x).into());
+        let outputs: Vec<_> = ports
+            .iter()
+            .filter(|x| x.property().direction == Direction::Output)
+            .collect();
+
+        let mut ret = Vec::new();
+        for input in ports
+            .iter()
+            .filter(|x| x.property().direction == Direction::Input)
+        {
+            let path = port_path(input.symbol);
+            let starts: Vec<_> = nodes
+                .iter()
+                .enumerate()
+                .filter(|(_, x)| x.sources.iter().any(|x| overlapped(x, &path)))
+                .map(|(i, _)| i)
+                .collect();
+            let reached = reachable(&edges, &starts);
+
+            for output in &outputs {
+                let path = port_path(output.symbol);
+                let driven = nodes
+                    .iter()
+                    .enumerate()
+                    .any(|(i, x)| reached[i] && x.targets.iter().any(|x| overlapped(x, &path)));
+                if driven {
+                    ret.push((input.name(), output.name()));
+                }
+            }
+        }
+
+        self.visiting.remove(&entity);
+        self.summaries.insert(entity, ret.clone());
+        ret
+    }
+}
@@ -0,0 +1,168 @@
+use crate::evaluator::single_unary;
+use crate::symbol::{Symbol, SymbolKind};
+use std::path::Path;
+use veryla_parser::resource_table;
+use veryla_parser::veryla_grammar_trait::*;
+use veryla_parser::veryla_token::{Token, TokenSource};
+
+pub const DEFINED_DISTRIBUTIONS: [&str; 2] = ["gauss", "uniform"];
+
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum Distribution {
+    /// `gauss(mean, sigma)`
+    Gauss,
+    /// `uniform(min, max)`
+    Uniform,
+}
+
+impl Distribution {
+    pub fn from_name(name: &str) -> Option<Self> {
+        match name {
+            "gauss" => Some(Distribution::Gauss),
+            "uniform" => Some(Distribution::Uniform),
+            _ => None,
+        }
+    }
+
+    pub fn from_symbol(symbol: &Symbol) -> Option<Self> {
+        if matches!(symbol.kind, SymbolKind::SystemFunction)
+            && matches!(symbol.token.source, TokenSource::Builtin)
+        {
+            Self::from_name(&symbol.token.to_string())
+        } else {
+            None
+        }
+    }
+
+    pub fn name(&self) -> &'static str {
+        match self {
+            Distribution::Gauss => "gauss",
+            Distribution::Uniform => "uniform",
+        }
+    }
+
+    pub fn arity(&self) -> usize {
+        2
+    }
+
+    /// Value used by static checks and by builds without sampling
+    pub fn nominal(&self, args: &[f64]) -> f64 {
+        match self {
+            Distribution::Gauss => args[0],
+            Distribution::Uniform => (args[0] + args[1]) / 2.0,
+        }
+    }
+
+    /// Nominal value assigned to an integer-typed parameter
+    pub fn nominal_integer(&self, args: &[f64]) -> isize {
+        to_integer(self.nominal(args))
+    }
+
+    pub fn sample(&self, args: &[f64], sampler: &mut Sampler) -> f64 {
+        match self {
+            Distribution::Gauss => args[0] + args[1] * sampler.next_normal(),
+            Distribution::Uniform => args[0] + (args[1] - args[0]) * sampler.next_uniform(),
+        }
+    }
+}
+
+/// Convert a value for an integer-typed parameter
+///
+/// Ties round away from zero like the real to integer conversion of SystemVerilog.
+pub fn to_integer(value: f64) -> isize {
+    value.round() as isize
+}
+
+/// Get literal arguments of a distribution call
+pub fn arguments(arg: &FunctionCall) -> Option<Vec<f64>> {
+    let mut ret = Vec::new();
+    if let Some(ref x) = arg.function_call_opt {
+        ret.push(literal_value(&x.argument_list.argument_item.expression)?);
+        for x in &x.argument_list.argument_list_list {
+            ret.push(literal_value(&x.argument_item.expression)?);
+        }
+    }
+    Some(ret)
+}
+
+fn literal_value(arg: &Expression) -> Option<f64> {
+    let exp = single_unary(arg)?;
+    let mut sign = 1.0;
+    for x in &exp.expression13_list {
+        if let Expression13ListGroup::Operator10(x) = &*x.expression13_list_group {
+            if x.operator10.operator10_token.to_string() == "-" {
+                sign = -sign;
+            }
+        } else {
+            return None;
+        }
+    }
+
+    if let Factor::Number(x) = &*exp.factor {
+        let text = match &*x.number {
+            Number::IntegralNumber(x) => match &*x.integral_number {
+                IntegralNumber::BaseLess(x) => x.base_less.base_less_token.to_string(),
+                _ => return None,
+            },
+            Number::RealNumber(x) => match &*x.real_number {
+                RealNumber::FixedPoint(x) => x.fixed_point.fixed_point_token.to_string(),
+                RealNumber::Exponent(x) => x.exponent.exponent_token.to_string(),
+            },
+        };
+        text.replace('_', "").parse::<f64>().ok().map(|x| sign * x)
+    } else {
+        None
+    }
+}
+
+/// Deterministic random number generator for a distribution call site
+///
+/// The stream depends only on the seed and the location of the call relative to `base`,
+/// so a failing seed can be reproduced on any host.
+pub struct Sampler {
+    state: u64,
+}
+
+impl Sampler {
+    pub fn new(seed: u64, token: &Token, base: &Path) -> Self {
+        let mut hash: u64 = 0xcbf29ce484222325;
+        let mut feed = |bytes: &[u8]| {
+            for x in bytes {
+                hash ^= *x as u64;
+                hash = hash.wrapping_mul(0x100000001b3);
+            }
+        };
+        if let TokenSource::File(x) = token.source {
+            let path = resource_table::get_path_value(x).unwrap();
+            let path = path.strip_prefix(base).unwrap_or(&path);
+            for x in path.components() {
+                feed(x.as_os_str().to_string_lossy().as_bytes());
+            }
+        }
+        feed(&token.line.to_le_bytes());
+        feed(&token.column.to_le_bytes());
+
+        Self { state: seed ^ hash }
+    }
+
+    fn next_u64(&mut self) -> u64 {
+        // splitmix64
+        self.state = self.state.wrapping_add(0x9e3779b97f4a7c15);
+        let mut z = self.state;
+        z = (z ^ (z >> 30)).wrapping_mul(0xbf58476d1ce4e5b9);
+        z = (z ^ (z >> 27)).wrapping_mul(0x94d049bb133111eb);
+        z ^ (z >> 31)
+    }
+
+    /// Uniform value in (0, 1]
+    pub fn next_uniform(&mut self) -> f64 {
+        ((self.next_u64() >> 11) + 1) as f64 / (1u64 << 53) as f64
+    }
+
+    /// Standard normal value by Box-Muller transform
+    pub fn next_normal(&mut self) -> f64 {
+        let u1 = self.next_uniform();
+        let u2 = self.next_uniform();
+        (-2.0 * u1.ln()).sqrt() * (2.0 * std::f64::consts::PI * u2).cos()
+    }
+}
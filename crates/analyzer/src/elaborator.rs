@@ -0,0 +1,677 @@
+use crate::analyzer_error::AnalyzerError;
+use crate::evaluator::{Evaluated, EvaluationLimit, Evaluator};
+use crate::symbol::{GenericBoundKind, Symbol, SymbolId, SymbolKind};
+use crate::symbol_path::{GenericSymbolPath, SymbolPath};
+use crate::symbol_table;
+use std::collections::{HashMap, HashSet};
+use std::fmt;
+use veryla_parser::resource_table::{StrId, TokenId};
+use veryla_parser::veryla_grammar_trait::*;
+use veryla_parser::veryla_token::TokenRange;
+
+/// Maximum depth of instance hierarchy to stop recursive instantiation
+const MAX_DEPTH: usize = 64;
+
+/// Maximum iterations of a generate-for to stop unbounded expansion
+const MAX_ITERATIONS: usize = 65536;
+
+/// Maximum number of instances to stop exponential expansion of recursive instantiation
+const MAX_INSTANCES: usize = 65536;
+
+#[derive(Clone, Debug, Default)]
+pub struct InstanceTree {
+    pub tops: Vec<InstanceNode>,
+}
+
+impl InstanceTree {
+    pub fn find(&self, path: &str) -> Option<&InstanceNode> {
+        self.iter().into_iter().find(|x| x.path_string() == path)
+    }
+
+    /// All instances in depth-first order
+    pub fn iter(&self) -> Vec<&InstanceNode> {
+        let mut ret = Vec::new();
+        for top in &self.tops {
+            top.collect(&mut ret);
+        }
+        ret
+    }
+
+    pub fn dump(&self) -> String {
+        format!("{self}")
+    }
+}
+
+impl fmt::Display for InstanceTree {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        writeln!(f, "InstanceTree [")?;
+        for top in &self.tops {
+            top.fmt_node(f, 1, 0)?;
+        }
+        writeln!(f, "]")
+    }
+}
+
+#[derive(Clone, Debug)]
+pub struct InstanceNode {
+    /// Hierarchical path from the top entity including generate block labels
+    pub path: Vec<String>,
+    pub entity: SymbolId,
+    /// Instance symbol, `None` for top entities
+    pub instance: Option<SymbolId>,
+    pub array: Vec<usize>,
+    pub generic_arguments: Vec<String>,
+    pub parameters: Vec<(StrId, Evaluated)>,
+    pub ports: Vec<PortBinding>,
+    pub children: Vec<InstanceNode>,
+    /// Paths of generate blocks whose condition or range can't be evaluated
+    pub unresolved: Vec<String>,
+}
+
+impl InstanceNode {
+    pub fn name(&self) -> &str {
+        self.path.last().unwrap()
+    }
+
+    pub fn path_string(&self) -> String {
+        self.path.join(".")
+    }
+
+    pub fn parameter(&self, name: StrId) -> Option<Evaluated> {
+        self.parameters.iter().find(|x| x.0 == name).map(|x| x.1)
+    }
+
+    fn fmt_node(&self, f: &mut fmt::Formatter, depth: usize, prefix: usize) -> fmt::Result {
+        let indent = "    ".repeat(depth);
+        let entity = symbol_table::get(self.entity).unwrap();
+        let mut entity_name = entity.token.to_string();
+        if !self.generic_arguments.is_empty() {
+            entity_name.push_str(&format!("::<{}>", self.generic_arguments.join(", ")));
+        }
+        let name = self.path[prefix..].join(".");
+        let array: String = self.array.iter().map(|x| format!("[{}]", x)).collect();
+        writeln!(f, "{}{}{}: {}", indent, name, array, entity_name)?;
+        for (name, value) in &self.parameters {
+            writeln!(f, "{}    param {} = {}", indent, name, value)?;
+        }
+        for port in &self.ports {
+            let targets: Vec<_> = port.targets.iter().map(|x| path_string(x)).collect();
+            writeln!(
+                f,
+                "{}    port {} <- {}",
+                indent,
+                port.name,
+                targets.join(", ")
+            )?;
+        }
+        for unresolved in &self.unresolved {
+            writeln!(f, "{}    unresolved {}", indent, unresolved)?;
+        }
+        for child in &self.children {
+            child.fmt_node(f, depth + 1, self.path.len())?;
+        }
+        Ok(())
+    }
+
+    fn collect<'a>(&'a self, list: &mut Vec<&'a InstanceNode>) {
+        list.push(self);
+        for child in &self.children {
+            child.collect(list);
+        }
+    }
+}
+
+#[derive(Clone, Debug)]
+pub struct PortBinding {
+    pub name: StrId,
+    /// Paths of connected signals in the parent, empty if unconnected
+    pub targets: Vec<Vec<StrId>>,
+}
+
+fn path_string(path: &[StrId]) -> String {
+    let path: Vec<_> = path.iter().map(|x| x.to_string()).collect();
+    path.join(".")
+}
+
+/// Values and symbols given to the generic parameters of an entity
+#[derive(Default)]
+struct Generics {
+    values: HashMap<SymbolId, Evaluated>,
+    symbols: HashMap<SymbolId, Symbol>,
+}
+
+pub struct Elaborator<'a> {
+    /// Entity declarations with the source text of each file
+    entities: HashMap<TokenId, (&'a str, &'a EntityDeclaration)>,
+    instances: usize,
+    errors: Vec<AnalyzerError>,
+}
+
+impl<'a> Elaborator<'a> {
+    pub fn new(inputs: &[(&'a str, &'a Veryla)]) -> Self {
+        let mut entities = HashMap::new();
+        for (text, input) in inputs {
+            for x in &input.veryla_list {
+                collect_entities(text, &x.description_group, &mut entities);
+            }
+        }
+        Self {
+            entities,
+            instances: 0,
+            errors: Vec::new(),
+        }
+    }
+
+    /// Instance hierarchies or generate iterations which exceed the elaboration limits
+    pub fn errors(&mut self) -> Vec<AnalyzerError> {
+        std::mem::take(&mut self.errors)
+    }
+
+    /// Entities of the project which are not instantiated by other entities
+    pub fn tops(&self, project_name: StrId) -> Vec<SymbolId> {
+        let mut instantiated = HashSet::new();
+        let mut entities = Vec::new();
+        for symbol in symbol_table::get_all() {
+            match &symbol.kind {
+                SymbolKind::Instance(x) => {
+                    if let Some(entity) = resolve_entity(&x.type_name, &symbol) {
+                        instantiated.insert(entity.id);
+                    }
+                }
+                SymbolKind::Entity(x)
+                    if symbol.namespace.paths.len() == 1
+                        && symbol.namespace.paths[0] == project_name
+                        && x.generic_parameters.is_empty()
+                        && self.entities.contains_key(&symbol.token.id) =>
+                {
+                    entities.push(symbol);
+                }
+                _ => (),
+            }
+        }
+
+        let mut ret: Vec<_> = entities
+            .into_iter()
+            .filter(|x| !instantiated.contains(&x.id))
+            .collect();
+        ret.sort_by_key(|x| x.token.id);
+        ret.into_iter().map(|x| x.id).collect()
+    }
+
+    pub fn elaborate(&mut self, tops: &[SymbolId]) -> InstanceTree {
+        let mut ret = InstanceTree::default();
+        for top in tops {
+            let symbol = symbol_table::get(*top).unwrap();
+            let path = vec![symbol.token.to_string()];
+            let mut node = InstanceNode {
+                path,
+                entity: *top,
+                instance: None,
+                array: Vec::new(),
+                generic_arguments: Vec::new(),
+                parameters: Vec::new(),
+                ports: Vec::new(),
+                children: Vec::new(),
+                unresolved: Vec::new(),
+            };
+            self.entity(&mut node, &symbol, HashMap::new(), Generics::default());
+            ret.tops.push(node);
+        }
+        ret
+    }
+
+    fn entity(
+        &mut self,
+        node: &mut InstanceNode,
+        symbol: &Symbol,
+        overrides: HashMap<StrId, Evaluated>,
+        generics: Generics,
+    ) {
+        let SymbolKind::Entity(property) = &symbol.kind else {
+            return;
+        };
+
+        // Generic parameters are referred by parameters and port types
+        let mut evaluator = Evaluator::new();
+        evaluator.overrides.extend(generics.values);
+        node.parameters = evaluator.entity_parameters(property, &overrides);
+
+        // Elaboration is stopped after a limit is exceeded
+        if node.path.len() >= MAX_DEPTH || !self.errors.is_empty() {
+            return;
+        }
+
+        if let Some((text, decl)) = self.entities.get(&symbol.token.id).copied() {
+            let mut context = Context {
+                text,
+                evaluator,
+                generics: generics.symbols,
+                prefix: node.path.clone(),
+                children: Vec::new(),
+                unresolved: Vec::new(),
+            };
+            for x in &decl.entity_declaration_list {
+                let items: Vec<EntityItem> = x.entity_group.as_ref().into();
+                for item in items {
+                    self.generate_item(&mut context, &item.generate_item);
+                }
+            }
+            node.children = context.children;
+            node.unresolved = context.unresolved;
+        }
+    }
+
+    fn generate_item(&mut self, context: &mut Context, arg: &GenerateItem) {
+        match arg {
+            GenerateItem::InstDeclaration(x) => self.inst_declaration(context, &x.inst_declaration),
+            GenerateItem::GenerateIfDeclaration(x) => {
+                self.generate_if_declaration(context, &x.generate_if_declaration)
+            }
+            GenerateItem::GenerateForDeclaration(x) => {
+                self.generate_for_declaration(context, &x.generate_for_declaration)
+            }
+            GenerateItem::GenerateBlockDeclaration(x) => {
+                let block = &x.generate_block_declaration.generate_named_block;
+                let label = block.identifier.identifier_token.to_string();
+                self.generate_named_block(context, label, block);
+            }
+            _ => (),
+        }
+    }
+
+    fn generate_named_block(
+        &mut self,
+        context: &mut Context,
+        label: String,
+        arg: &GenerateNamedBlock,
+    ) {
+        context.prefix.push(label);
+        for x in &arg.generate_named_block_list {
+            let items: Vec<GenerateItem> = x.generate_group.as_ref().into();
+            for item in items {
+                self.generate_item(context, &item);
+            }
+        }
+        context.prefix.pop();
+    }
+
+    fn generate_optional_named_block(
+        &mut self,
+        context: &mut Context,
+        arg: &GenerateOptionalNamedBlock,
+    ) {
+        let label = arg
+            .generate_optional_named_block_opt
+            .as_ref()
+            .map(|x| x.identifier.identifier_token.to_string());
+        if let Some(ref label) = label {
+            context.prefix.push(label.clone());
+        }
+        for x in &arg.generate_optional_named_block_list {
+            let items: Vec<GenerateItem> = x.generate_group.as_ref().into();
+            for item in items {
+                self.generate_item(context, &item);
+            }
+        }
+        if label.is_some() {
+            context.prefix.pop();
+        }
+    }
+
+    /// Blocks are not elaborated from a condition which can't be evaluated
+    fn generate_if_declaration(&mut self, context: &mut Context, arg: &GenerateIfDeclaration) {
+        let label = arg
+            .generate_named_block
+            .identifier
+            .identifier_token
+            .to_string();
+        match condition(context.evaluator.expression(&arg.expression)) {
+            Some(true) => {
+                self.generate_named_block(context, label, &arg.generate_named_block);
+                return;
+            }
+            Some(false) => (),
+            None => {
+                context.push_unresolved(label);
+                return;
+            }
+        }
+        for x in &arg.generate_if_declaration_list {
+            match condition(context.evaluator.expression(&x.expression)) {
+                Some(true) => {
+                    self.generate_optional_named_block(context, &x.generate_optional_named_block);
+                    return;
+                }
+                Some(false) => (),
+                None => {
+                    context.push_unresolved(label);
+                    return;
+                }
+            }
+        }
+        if let Some(ref x) = arg.generate_if_declaration_opt {
+            self.generate_optional_named_block(context, &x.generate_optional_named_block);
+        }
+    }
+
+    fn generate_for_declaration(&mut self, context: &mut Context, arg: &GenerateForDeclaration) {
+        let range = &arg.range;
+        let start = context.evaluator.expression(&range.expression);
+        let (end, inclusive) = if let Some(ref x) = range.range_opt {
+            let inclusive = matches!(*x.range_operator, RangeOperator::DotDotEqu(_));
+            (context.evaluator.expression(&x.expression), inclusive)
+        } else {
+            (start, true)
+        };
+        let label = arg
+            .generate_named_block
+            .identifier
+            .identifier_token
+            .to_string();
+        let (Evaluated::Fixed { value: start, .. }, Evaluated::Fixed { value: end, .. }) =
+            (start, end)
+        else {
+            context.push_unresolved(label);
+            return;
+        };
+
+        let (operator, step) = if let Some(ref x) = arg.generate_for_declaration_opt {
+            let step = context.evaluator.expression(&x.expression);
+            let Evaluated::Fixed { value: step, .. } = step else {
+                context.push_unresolved(label);
+                return;
+            };
+            (
+                x.assignment_operator.assignment_operator_token.to_string(),
+                step,
+            )
+        } else {
+            ("+=".to_string(), 1)
+        };
+
+        // genvar is declared in the namespace of the named block
+        let genvar = symbol_table::resolve(arg.generate_named_block.identifier.as_ref())
+            .ok()
+            .and_then(|x| {
+                let path = SymbolPath::new(&[arg.identifier.identifier_token.token.text]);
+                symbol_table::resolve((&path, &x.found.inner_namespace())).ok()
+            })
+            .map(|x| x.found.id);
+
+        let mut value = start;
+        let mut iterations = 0;
+        loop {
+            let in_range = if inclusive { value <= end } else { value < end };
+            if !in_range {
+                break;
+            }
+            if iterations == MAX_ITERATIONS {
+                let token: TokenRange = arg.generate_named_block.identifier.as_ref().into();
+                self.errors.push(AnalyzerError::elaboration_limit_exceeded(
+                    &label,
+                    &EvaluationLimit::Iteration.to_string(),
+                    context.text,
+                    &token,
+                ));
+                break;
+            }
+            iterations += 1;
+
+            if let Some(genvar) = genvar {
+                context
+                    .evaluator
+                    .overrides
+                    .insert(genvar, Evaluated::Fixed { width: 32, value });
+                context.evaluator.consts.clear();
+            }
+            let label = format!("{}[{}]", label, value);
+            self.generate_named_block(context, label, &arg.generate_named_block);
+
+            let next = match operator.as_str() {
+                "+=" => value.checked_add(step),
+                "-=" => value.checked_sub(step),
+                "*=" => value.checked_mul(step),
+                "/=" if step != 0 => Some(value / step),
+                "<<=" => value.checked_shl(step as u32),
+                _ => None,
+            };
+            match next {
+                Some(x) if x != value => value = x,
+                _ => break,
+            }
+        }
+
+        if let Some(genvar) = genvar {
+            context.evaluator.overrides.remove(&genvar);
+            context.evaluator.consts.clear();
+        }
+    }
+
+    fn inst_declaration(&mut self, context: &mut Context, arg: &InstDeclaration) {
+        let Ok(instance) = symbol_table::resolve(arg.identifier.as_ref()) else {
+            return;
+        };
+        let instance = instance.found;
+        let SymbolKind::Instance(ref property) = instance.kind else {
+            return;
+        };
+
+        let Ok(type_symbol) = symbol_table::resolve(arg.scoped_identifier.as_ref()) else {
+            return;
+        };
+        let (entity, arguments) = match &type_symbol.found.kind {
+            SymbolKind::GenericInstance(x) => {
+                (symbol_table::get(x.base).unwrap(), x.arguments.clone())
+            }
+            // Generic parameter of the parent is replaced by the given entity
+            SymbolKind::GenericParameter(_) => {
+                let Some(x) = context.generics.get(&type_symbol.found.id) else {
+                    return;
+                };
+                match &x.kind {
+                    SymbolKind::GenericInstance(x) => {
+                        (symbol_table::get(x.base).unwrap(), x.arguments.clone())
+                    }
+                    _ => (x.clone(), Vec::new()),
+                }
+            }
+            // Arguments referring to generic parameters are not mangled
+            _ => {
+                let path: GenericSymbolPath = arg.scoped_identifier.as_ref().into();
+                let arguments = path.paths.last().unwrap().arguments.clone();
+                (type_symbol.found.clone(), arguments)
+            }
+        };
+        let (generics, generic_arguments) = context.generics(&entity, &arguments, &instance);
+
+        let mut array = Vec::new();
+        if let Some(ref x) = arg.inst_declaration_opt {
+            let mut exps = vec![x.array.expression.as_ref()];
+            exps.extend(x.array.array_list.iter().map(|x| x.expression.as_ref()));
+            for exp in exps {
+                if let Evaluated::Fixed { value, .. } = context.evaluator.expression(exp) {
+                    array.push(value.max(0) as usize);
+                }
+            }
+        }
+
+        let mut overrides = HashMap::new();
+        if let Some(ref x) = arg.inst_declaration_opt0 {
+            if let Some(ref x) = x.inst_parameter.inst_parameter_opt {
+                let items: Vec<InstParameterItem> = x.inst_parameter_list.as_ref().into();
+                for item in items {
+                    let name = item.identifier.identifier_token.token.text;
+                    let value = context.evaluator.inst_parameter_item(&item);
+                    overrides.insert(name, value);
+                }
+            }
+        }
+
+        let mut ports = Vec::new();
+        if let SymbolKind::Entity(ref x) = entity.kind {
+            for port in &x.ports {
+                let targets = property
+                    .connects
+                    .iter()
+                    .find(|(token, _)| token.text == port.name())
+                    .map(|(_, targets)| {
+                        targets
+                            .iter()
+                            .filter(|x| !x.is_empty())
+                            .map(|x| x.path())
+                            .collect()
+                    })
+                    .unwrap_or_default();
+                ports.push(PortBinding {
+                    name: port.name(),
+                    targets,
+                });
+            }
+        }
+
+        let mut path = context.prefix.clone();
+        path.push(instance.token.to_string());
+
+        self.instances += 1;
+        let exceeded = path.len() == MAX_DEPTH || self.instances == MAX_INSTANCES;
+        if exceeded && self.errors.is_empty() {
+            let limit = if path.len() == MAX_DEPTH {
+                EvaluationLimit::Recursion
+            } else {
+                EvaluationLimit::Iteration
+            };
+            let token: TokenRange = arg.identifier.as_ref().into();
+            self.errors.push(AnalyzerError::elaboration_limit_exceeded(
+                &path.join("."),
+                &limit.to_string(),
+                context.text,
+                &token,
+            ));
+        }
+
+        let mut node = InstanceNode {
+            path,
+            entity: entity.id,
+            instance: Some(instance.id),
+            array,
+            generic_arguments,
+            parameters: Vec::new(),
+            ports,
+            children: Vec::new(),
+            unresolved: Vec::new(),
+        };
+        self.entity(&mut node, &entity, overrides, generics);
+        context.children.push(node);
+    }
+}
+
+struct Context<'a> {
+    /// Source text of the entity
+    text: &'a str,
+    evaluator: Evaluator,
+    /// Symbols given to the type generic parameters of the entity
+    generics: HashMap<SymbolId, Symbol>,
+    prefix: Vec<String>,
+    children: Vec<InstanceNode>,
+    unresolved: Vec<String>,
+}
+
+impl Context<'_> {
+    fn push_unresolved(&mut self, label: String) {
+        let mut path = self.prefix.clone();
+        path.push(label);
+        self.unresolved.push(path.join("."));
+    }
+
+    /// Substitute generic arguments given by `instance` into the generic parameters of `entity`
+    fn generics(
+        &mut self,
+        entity: &Symbol,
+        arguments: &[GenericSymbolPath],
+        instance: &Symbol,
+    ) -> (Generics, Vec<String>) {
+        let SymbolKind::Entity(ref property) = entity.kind else {
+            return (Generics::default(), Vec::new());
+        };
+        // Missing arguments are reported by the generic instance check
+        let parameters = entity.generic_parameters();
+        let missing = parameters
+            .iter()
+            .skip(arguments.len())
+            .any(|x| x.1.default_value.is_none());
+        if property.generic_parameters.is_empty() || missing {
+            return (Generics::default(), Vec::new());
+        }
+
+        let mut generics = Generics::default();
+        let mut texts = Vec::new();
+        let table = entity.generic_table(arguments);
+        for id in &property.generic_parameters {
+            let param = symbol_table::get(*id).unwrap();
+            let SymbolKind::GenericParameter(ref x) = param.kind else {
+                continue;
+            };
+            let Some(arg) = table.get(&param.token.text) else {
+                continue;
+            };
+            if matches!(x.bound, GenericBoundKind::Const) {
+                let value = self.evaluator.generic_argument(arg, &instance.namespace);
+                texts.push(match value {
+                    Evaluated::Fixed { value, .. } => value.to_string(),
+                    _ => arg.to_string().trim().to_string(),
+                });
+                generics.values.insert(*id, value);
+            } else if arg.is_resolvable() {
+                let Ok(symbol) = symbol_table::resolve((&arg.mangled_path(), &instance.namespace))
+                else {
+                    continue;
+                };
+                let symbol = match symbol.found.kind {
+                    SymbolKind::GenericParameter(_) => self.generics.get(&symbol.found.id).cloned(),
+                    _ => Some(symbol.found),
+                };
+                if let Some(symbol) = symbol {
+                    texts.push(symbol.token.to_string());
+                    generics.symbols.insert(*id, symbol);
+                }
+            }
+        }
+        (generics, texts)
+    }
+}
+
+fn condition(x: Evaluated) -> Option<bool> {
+    match x {
+        Evaluated::Fixed { value, .. } => Some(value != 0),
+        _ => None,
+    }
+}
+
+fn collect_entities<'a>(
+    text: &'a str,
+    arg: &'a DescriptionGroup,
+    entities: &mut HashMap<TokenId, (&'a str, &'a EntityDeclaration)>,
+) {
+    match arg.description_group_group.as_ref() {
+        DescriptionGroupGroup::LBraceDescriptionGroupGroupListRBrace(x) => {
+            for x in &x.description_group_group_list {
+                collect_entities(text, &x.description_group, entities);
+            }
+        }
+        DescriptionGroupGroup::DescriptionItem(x) => {
+            if let DescriptionItem::EntityDeclaration(x) = x.description_item.as_ref() {
+                let decl = x.entity_declaration.as_ref();
+                entities.insert(decl.identifier.identifier_token.token.id, (text, decl));
+            }
+        }
+    }
+}
+
+fn resolve_entity(type_name: &GenericSymbolPath, instance: &Symbol) -> Option<Symbol> {
+    let symbol = symbol_table::resolve((&type_name.mangled_path(), &instance.namespace)).ok()?;
+    match symbol.found.kind {
+        SymbolKind::Entity(_) => Some(symbol.found),
+        SymbolKind::GenericInstance(x) => symbol_table::get(x.base),
+        _ => None,
+    }
+}
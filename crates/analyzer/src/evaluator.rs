@@ -1,5 +1,14 @@
-use crate::symbol::{Type, TypeKind};
+use crate::distribution::{self, Distribution};
+use crate::namespace::Namespace;
+use crate::symbol::{
+    Direction, EntityProperty, FunctionProperty, ParameterKind, ParameterProperty, Symbol,
+    SymbolId, SymbolKind, Type, TypeKind,
+};
+use crate::symbol_path::{GenericSymbolPath, GenericSymbolPathKind};
 use crate::symbol_table::{self, ResolveError, ResolveResult};
+use std::collections::HashMap;
+use std::fmt;
+use veryla_parser::resource_table::StrId;
 use veryla_parser::veryla_grammar_trait::*;
 
 #[derive(Clone, Copy, Debug)]
@@ -299,9 +308,135 @@ impl Evaluated {
     }
 }
 
+impl fmt::Display for Evaluated {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        match self {
+            Evaluated::Fixed { value, .. } => value.fmt(f),
+            Evaluated::Power => "power".fmt(f),
+            Evaluated::PowerPosedge => "power_posedge".fmt(f),
+            Evaluated::PowerNegedge => "power_negedge".fmt(f),
+            Evaluated::Enable => "enable".fmt(f),
+            Evaluated::EnableHigh => "enable_high".fmt(f),
+            Evaluated::EnableLow => "enable_low".fmt(f),
+            _ => "?".fmt(f),
+        }
+    }
+}
+
+/// Maximum depth of nested function calls in constant evaluation
+const MAX_CALL_DEPTH: usize = 64;
+/// Maximum number of statements executed in constant evaluation
+const MAX_STEPS: usize = 100_000;
+/// Maximum depth of nested user-defined types
+const MAX_TYPE_DEPTH: usize = 64;
+
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum EvaluationLimit {
+    Recursion,
+    Iteration,
+}
+
+impl fmt::Display for EvaluationLimit {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        match self {
+            EvaluationLimit::Recursion => "recursion".fmt(f),
+            EvaluationLimit::Iteration => "iteration".fmt(f),
+        }
+    }
+}
+
+/// Control flow of statements in constant functions
+enum Flow {
+    Next,
+    Break,
+    Return(Evaluated),
+}
+
 #[derive(Default)]
 pub struct Evaluator {
     pub context_width: Vec<usize>,
+    /// Values which replace the declared value of symbols, e.g. elaborated parameters
+    pub overrides: HashMap<SymbolId, Evaluated>,
+    /// Constants evaluated with `overrides`, which must be cleared when `overrides` changes
+    pub consts: HashMap<SymbolId, Evaluated>,
+    /// Regard `param` as unknown so that only values independent of overriding are fixed
+    pub unbound_params: bool,
+    /// Value is assigned to a real-typed parameter, so distributions have no integer value
+    pub real_context: bool,
+    /// Function whose evaluation was aborted because of the limits
+    pub limit_exceeded: Option<(StrId, EvaluationLimit)>,
+    /// Functions being evaluated
+    pub(crate) calls: Vec<StrId>,
+    pub(crate) steps: usize,
+    pub(crate) type_depth: usize,
+}
+
+/// Unary expression which an expression consists of only
+pub(crate) fn single_unary(arg: &Expression) -> Option<&Expression13> {
+    let x = arg
+        .expression_list
+        .is_empty()
+        .then_some(&arg.expression01)?;
+    let x = x.expression01_list.is_empty().then_some(&x.expression02)?;
+    let x = x.expression02_list.is_empty().then_some(&x.expression03)?;
+    let x = x.expression03_list.is_empty().then_some(&x.expression04)?;
+    let x = x.expression04_list.is_empty().then_some(&x.expression05)?;
+    let x = x.expression05_list.is_empty().then_some(&x.expression06)?;
+    let x = x.expression06_list.is_empty().then_some(&x.expression07)?;
+    let x = x.expression07_list.is_empty().then_some(&x.expression08)?;
+    let x = x.expression08_list.is_empty().then_some(&x.expression09)?;
+    let x = x.expression09_list.is_empty().then_some(&x.expression10)?;
+    let x = x.expression10_list.is_empty().then_some(&x.expression11)?;
+    let x = x.expression11_list.is_empty().then_some(&x.expression12)?;
+    x.expression12_opt.is_none().then_some(&x.expression13)
+}
+
+/// Factor of an expression which consists of it only
+pub(crate) fn single_factor(arg: &Expression) -> Option<&Factor> {
+    let x = single_unary(arg)?;
+    x.expression13_list.is_empty().then_some(&x.factor)
+}
+
+pub(crate) fn fixed(x: Evaluated) -> Option<isize> {
+    if let Evaluated::Fixed { value, .. } = x {
+        Some(value)
+    } else {
+        None
+    }
+}
+
+fn based_value(text: &str) -> Evaluated {
+    let text = text.replace('_', "");
+    if let Some((width, rest)) = text.split_once('\'') {
+        let signed = &rest[0..1] == "s";
+        let rest = if signed { &rest[1..] } else { rest };
+        let (base, value) = rest.split_at(1);
+        let radix = match base {
+            "b" => 2,
+            "o" => 8,
+            "d" => 10,
+            "h" => 16,
+            _ => unreachable!(),
+        };
+        let width = width.parse();
+        let value = isize::from_str_radix(value, radix);
+        if let (Ok(width), Ok(value)) = (width, value) {
+            Evaluated::Fixed { width, value }
+        } else {
+            Evaluated::Unknown
+        }
+    } else {
+        Evaluated::Unknown
+    }
+}
+
+fn base_less_value(text: &str) -> Evaluated {
+    let text = text.replace('_', "");
+    if let Ok(value) = text.parse() {
+        Evaluated::Fixed { width: 32, value }
+    } else {
+        Evaluated::Unknown
+    }
 }
 
 impl Evaluator {
@@ -323,8 +458,8 @@ impl Evaluator {
             ">>" => left.unsigned_shr(right),
             "<=" => left.le(right),
             ">=" => left.ge(right),
-            "<" => left.lt(right),
-            ">" => left.gt(right),
+            "<:" | "<" => left.lt(right),
+            ">:" | ">" => left.gt(right),
             "===" => left.eq(right),
             "==?" => left.eq(right),
             "!==" => left.ne(right),
@@ -397,6 +532,64 @@ impl Evaluator {
         }
     }
 
+    /// Width of a type including user-defined types which are resolved from `namespace`
+    pub fn packed_width(&mut self, x: &Type, namespace: &Namespace) -> Option<usize> {
+        let TypeKind::UserDefined(ref path) = x.kind else {
+            return self.type_width(x.clone());
+        };
+        let symbol = symbol_table::resolve((path, namespace)).ok()?;
+        let mut ret = self.symbol_width(&symbol.found)?;
+        for x in &x.width {
+            ret *= usize::try_from(fixed(self.expression(x))?).ok()?;
+        }
+        Some(ret)
+    }
+
+    /// Number of bits of a type or a value like `$bits`
+    pub fn symbol_width(&mut self, symbol: &Symbol) -> Option<usize> {
+        if self.type_depth >= MAX_TYPE_DEPTH {
+            return None;
+        }
+        self.type_depth += 1;
+        let ret = self.symbol_width_inner(symbol);
+        self.type_depth -= 1;
+        ret
+    }
+
+    fn symbol_width_inner(&mut self, symbol: &Symbol) -> Option<usize> {
+        let namespace = &symbol.namespace;
+        let r#type = match &symbol.kind {
+            SymbolKind::Struct(x) => {
+                let mut ret = 0;
+                for x in &x.members {
+                    ret += self.symbol_width(&symbol_table::get(*x)?)?;
+                }
+                return Some(ret);
+            }
+            SymbolKind::Union(x) => {
+                let mut ret = 0;
+                for x in &x.members {
+                    ret = ret.max(self.symbol_width(&symbol_table::get(*x)?)?);
+                }
+                return Some(ret);
+            }
+            SymbolKind::Enum(x) => return Some(x.width),
+            SymbolKind::TypeDef(x) => return self.packed_width(&x.r#type, namespace),
+            SymbolKind::StructMember(x) => &x.r#type,
+            SymbolKind::UnionMember(x) => &x.r#type,
+            SymbolKind::Parameter(x) => &x.r#type,
+            SymbolKind::Variable(x) => &x.r#type,
+            SymbolKind::Port(x) => x.r#type.as_ref()?,
+            _ => return None,
+        };
+
+        let mut ret = self.packed_width(r#type, namespace)?;
+        for x in &r#type.array {
+            ret *= usize::try_from(fixed(self.expression(x))?).ok()?;
+        }
+        Some(ret)
+    }
+
     fn exponent(&mut self, _arg: &Exponent) -> Evaluated {
         Evaluated::Unknown
     }
@@ -406,37 +599,11 @@ impl Evaluator {
     }
 
     fn based(&mut self, arg: &Based) -> Evaluated {
-        let text = arg.based_token.to_string().replace('_', "");
-        if let Some((width, rest)) = text.split_once('\'') {
-            let signed = &rest[0..1] == "s";
-            let rest = if signed { &rest[1..] } else { rest };
-            let (base, value) = rest.split_at(1);
-            let radix = match base {
-                "b" => 2,
-                "o" => 8,
-                "d" => 10,
-                "h" => 16,
-                _ => unreachable!(),
-            };
-            let width = width.parse();
-            let value = isize::from_str_radix(value, radix);
-            if let (Ok(width), Ok(value)) = (width, value) {
-                Evaluated::Fixed { width, value }
-            } else {
-                Evaluated::Unknown
-            }
-        } else {
-            Evaluated::Unknown
-        }
+        based_value(&arg.based_token.to_string())
     }
 
     fn base_less(&mut self, arg: &BaseLess) -> Evaluated {
-        let text = arg.base_less_token.to_string().replace('_', "");
-        if let Ok(value) = text.parse() {
-            Evaluated::Fixed { width: 32, value }
-        } else {
-            Evaluated::Unknown
-        }
+        base_less_value(&arg.base_less_token.to_string())
     }
 
     fn all_bit(&mut self, arg: &AllBit) -> Evaluated {
@@ -650,6 +817,37 @@ impl Evaluator {
         ret
     }
 
+    /// Evaluate parameters of an entity in declaration order
+    ///
+    /// Parameters given in `values` replace the default values, and all results are
+    /// registered to `overrides` so that later parameters and port types can refer to them.
+    pub fn entity_parameters(
+        &mut self,
+        property: &EntityProperty,
+        values: &HashMap<StrId, Evaluated>,
+    ) -> Vec<(StrId, Evaluated)> {
+        let mut ret = Vec::new();
+        for param in &property.parameters {
+            let value = if let Some(x) = values.get(&param.name) {
+                *x
+            } else {
+                let param_property = param.property();
+                self.context_width.clear();
+                if let Some(width) = self.type_width(param_property.r#type.clone()) {
+                    self.context_width.push(width);
+                }
+                self.real_context = param_property.r#type.kind.is_real();
+                let value = self.expression(&param_property.value);
+                self.real_context = false;
+                value
+            };
+            self.overrides.insert(param.symbol, value);
+            self.consts.clear();
+            ret.push((param.name, value));
+        }
+        ret
+    }
+
     pub fn inst_parameter_item(&mut self, arg: &InstParameterItem) -> Evaluated {
         if let Some(opt) = &arg.inst_parameter_item_opt {
             self.expression(opt.expression.as_ref())
@@ -658,14 +856,96 @@ impl Evaluator {
         }
     }
 
+    /// Value of a generic argument given to a const generic parameter
+    pub fn generic_argument(
+        &mut self,
+        arg: &GenericSymbolPath,
+        namespace: &Namespace,
+    ) -> Evaluated {
+        let text = arg.paths[0].base.to_string();
+        match arg.kind {
+            GenericSymbolPathKind::Identifier => {
+                let symbol = symbol_table::resolve((&arg.mangled_path(), namespace));
+                self.identifier_helper(symbol)
+            }
+            GenericSymbolPathKind::IntegerBased => based_value(&text),
+            GenericSymbolPathKind::IntegerBaseLess => base_less_value(&text),
+            _ => Evaluated::Unknown,
+        }
+    }
+
     fn identifier_helper(&mut self, symbol: Result<ResolveResult, ResolveError>) -> Evaluated {
         if let Ok(symbol) = symbol {
-            symbol.found.evaluate()
+            self.symbol_value(&symbol.found)
         } else {
             Evaluated::Unknown
         }
     }
 
+    fn symbol_value(&mut self, symbol: &Symbol) -> Evaluated {
+        if let Some(x) = self.overrides.get(&symbol.id) {
+            return *x;
+        }
+        if !self.overrides.is_empty() && !self.unbound_params {
+            if let SymbolKind::Parameter(ref x) = symbol.kind {
+                if matches!(x.kind, ParameterKind::Const) {
+                    return self.const_value(symbol.id, x, &symbol.namespace);
+                }
+            }
+        }
+        if self.unbound_params {
+            if let SymbolKind::Parameter(ref x) = symbol.kind {
+                return match x.kind {
+                    ParameterKind::Param => Evaluated::UnknownStatic,
+                    ParameterKind::Const => {
+                        let mut evaluator = Evaluator {
+                            unbound_params: true,
+                            ..Default::default()
+                        };
+                        if let Some(width) = evaluator.packed_width(&x.r#type, &symbol.namespace) {
+                            evaluator.context_width.push(width);
+                        }
+                        evaluator.real_context = x.r#type.kind.is_real();
+                        evaluator.expression(&x.value)
+                    }
+                };
+            }
+        }
+        symbol.evaluate()
+    }
+
+    /// Constant which may refer to overridden parameters
+    fn const_value(
+        &mut self,
+        id: SymbolId,
+        property: &ParameterProperty,
+        namespace: &Namespace,
+    ) -> Evaluated {
+        if let Some(x) = self.consts.get(&id) {
+            return *x;
+        }
+
+        // Placeholder to stop self reference
+        self.consts.insert(id, Evaluated::Unknown);
+        let context_width = std::mem::take(&mut self.context_width);
+        if let Some(width) = self.packed_width(&property.r#type, namespace) {
+            self.context_width.push(width);
+        }
+        let real_context =
+            std::mem::replace(&mut self.real_context, property.r#type.kind.is_real());
+        let value = self.expression(&property.value);
+        self.context_width = context_width;
+        self.real_context = real_context;
+
+        // Local values of constant functions are not the same for each call
+        if self.calls.is_empty() {
+            self.consts.insert(id, value);
+        } else {
+            self.consts.remove(&id);
+        }
+        value
+    }
+
     fn identifier(&mut self, arg: &Identifier) -> Evaluated {
         let symbol = symbol_table::resolve(arg);
         self.identifier_helper(symbol)
@@ -673,16 +953,150 @@ impl Evaluator {
 
     fn expression_identifier(&mut self, arg: &ExpressionIdentifier) -> Evaluated {
         let symbol = symbol_table::resolve(arg);
+        if let Ok(ref x) = symbol {
+            // Path after the constant, which may be prefixed by package
+            let base = x.full_path.iter().position(|x| {
+                symbol_table::get(*x).is_some_and(|x| matches!(x.kind, SymbolKind::Parameter(_)))
+            });
+            if let Some(base) = base {
+                if base + 1 < x.full_path.len() {
+                    if let Some(ret) = self.struct_member(arg, &x.full_path[base..]) {
+                        return ret;
+                    }
+                } else if let Some(select) = arg.expression_identifier_list.first() {
+                    if let Some(ret) = self.array_element(&x.found, &select.select) {
+                        return ret;
+                    }
+                }
+            }
+        }
         self.identifier_helper(symbol)
     }
 
+    /// Member of a struct constant, e.g. `C.a`
+    fn struct_member(
+        &mut self,
+        arg: &ExpressionIdentifier,
+        full_path: &[SymbolId],
+    ) -> Option<Evaluated> {
+        let selected = !arg.expression_identifier_list.is_empty()
+            || arg
+                .expression_identifier_list0
+                .iter()
+                .any(|x| !x.expression_identifier_list0_list.is_empty());
+        if selected {
+            return None;
+        }
+
+        let base = symbol_table::get(full_path[0])?;
+        if !matches!(base.kind, SymbolKind::Parameter(_)) {
+            return None;
+        }
+
+        let mut value = self.symbol_value(&base);
+        let mut width = 0;
+        for member in &full_path[1..] {
+            let member = symbol_table::get(*member)?;
+            let SymbolKind::StructMember(ref property) = member.kind else {
+                return None;
+            };
+            let SymbolKind::Struct(parent) = member.get_parent()?.kind else {
+                return None;
+            };
+
+            // The first member is placed at MSB
+            let mut offset = 0;
+            for x in parent.members.iter().rev() {
+                if *x == member.id {
+                    break;
+                }
+                offset += self.symbol_width(&symbol_table::get(*x)?)?;
+            }
+            width = self.packed_width(&property.r#type, &member.namespace)?;
+
+            value = match value {
+                Evaluated::Fixed { value, .. } => {
+                    let shifted = value.checked_shr(offset.try_into().ok()?).unwrap_or(0);
+                    let mask = 1isize.checked_shl(width.try_into().ok()?).map(|x| x - 1);
+                    Evaluated::Fixed {
+                        width,
+                        value: mask.map(|x| shifted & x).unwrap_or(shifted),
+                    }
+                }
+                x if x.is_known_static() => Evaluated::UnknownStatic,
+                _ => return None,
+            };
+        }
+
+        Some(Self::fit(value, Some(width)))
+    }
+
+    /// Element of an array constant, e.g. `C[1]` for `const C: u32 [2] = '{1, 2};`
+    fn array_element(&mut self, symbol: &Symbol, select: &Select) -> Option<Evaluated> {
+        let SymbolKind::Parameter(ref x) = symbol.kind else {
+            return None;
+        };
+        if x.r#type.array.len() != 1 || select.select_opt.is_some() {
+            return None;
+        }
+        if self.overrides.contains_key(&symbol.id)
+            || (self.unbound_params && matches!(x.kind, ParameterKind::Param))
+        {
+            return Some(Evaluated::UnknownStatic);
+        }
+
+        let index = self.expression(&select.expression);
+        let Evaluated::Fixed { value: index, .. } = index else {
+            return index.is_known_static().then_some(Evaluated::UnknownStatic);
+        };
+        let Factor::QuoteLBraceArrayLiteralListRBrace(literal) = single_factor(&x.value)? else {
+            return None;
+        };
+
+        let literal = &literal.array_literal_list;
+        let items = std::iter::once(&literal.array_literal_item).chain(
+            literal
+                .array_literal_list_list
+                .iter()
+                .map(|x| &x.array_literal_item),
+        );
+
+        let mut position = 0;
+        let mut default = None;
+        let mut element = None;
+        for item in items {
+            match item.array_literal_item_group.as_ref() {
+                ArrayLiteralItemGroup::ExpressionArrayLiteralItemOpt(x) => {
+                    let repeat = if let Some(ref x) = x.array_literal_item_opt {
+                        fixed(self.expression(&x.expression))?
+                    } else {
+                        1
+                    };
+                    if position <= index && index < position + repeat {
+                        element = Some(x.expression.as_ref());
+                    }
+                    position += repeat;
+                }
+                ArrayLiteralItemGroup::DefaulColonExpression(x) => {
+                    default = Some(x.expression.as_ref());
+                }
+            }
+        }
+
+        let width = self.packed_width(&x.r#type, &symbol.namespace);
+        let value = self.expression_with_width(element.or(default)?, width);
+        Some(Self::fit(value, width))
+    }
     fn factor(&mut self, arg: &Factor) -> Evaluated {
         match arg {
             Factor::Number(x) => self.number(&x.number),
             Factor::IdentifierFactor(x) => {
-                if x.identifier_factor.identifier_factor_opt.is_some() {
+                if let Some(ref call) = x.identifier_factor.identifier_factor_opt {
                     // Function call
-                    Evaluated::Unknown
+                    self.function_call(
+                        x.identifier_factor.expression_identifier.as_ref(),
+                        &call.function_call,
+                    )
                 } else {
                     // Identifier
                     self.expression_identifier(x.identifier_factor.expression_identifier.as_ref())
@@ -707,23 +1121,83 @@ impl Evaluator {
         }
     }
 
+    fn function_call(
+        &mut self,
+        identifier: &ExpressionIdentifier,
+        function_call: &FunctionCall,
+    ) -> Evaluated {
+        let Ok(symbol) = symbol_table::resolve(identifier) else {
+            return Evaluated::Unknown;
+        };
+        if let Some(distribution) = Distribution::from_symbol(&symbol.found) {
+            self.distribution(distribution, function_call)
+        } else if let SymbolKind::Function(ref x) = symbol.found.kind {
+            self.constant_function(symbol.found.token.text, x, function_call)
+        } else if symbol.found.token.to_string() == "$bits" {
+            self.bits(function_call)
+        } else {
+            Evaluated::Unknown
+        }
+    }
+
+    fn bits(&mut self, function_call: &FunctionCall) -> Evaluated {
+        let Some(ref args) = function_call.function_call_opt else {
+            return Evaluated::Unknown;
+        };
+        let args = &args.argument_list;
+        if !args.argument_list_list.is_empty() {
+            return Evaluated::Unknown;
+        }
+        let Some(Factor::IdentifierFactor(x)) = single_factor(&args.argument_item.expression)
+        else {
+            return Evaluated::Unknown;
+        };
+        if x.identifier_factor.identifier_factor_opt.is_some() {
+            return Evaluated::Unknown;
+        }
+
+        let width = symbol_table::resolve(x.identifier_factor.expression_identifier.as_ref())
+            .ok()
+            .and_then(|x| self.symbol_width(&x.found));
+        match width.and_then(|x| isize::try_from(x).ok()) {
+            Some(value) => Evaluated::Fixed { width: 32, value },
+            None => Evaluated::Unknown,
+        }
+    }
+
+    /// Distribution built-ins are evaluated as their nominal value
+    fn distribution(
+        &mut self,
+        distribution: Distribution,
+        function_call: &FunctionCall,
+    ) -> Evaluated {
+        let Some(args) = distribution::arguments(function_call) else {
+            return Evaluated::Unknown;
+        };
+        if args.len() != distribution.arity() {
+            return Evaluated::Unknown;
+        }
+
+        // Real values are not representable like real literals
+        if self.real_context {
+            return Evaluated::UnknownStatic;
+        }
+
+        Evaluated::Fixed {
+            width: 32,
+            value: distribution.nominal_integer(&args),
+        }
+    }
+
     fn do_concatenation(&mut self, exp: Evaluated, rep: Evaluated) -> Evaluated {
         match exp {
-            Evaluated::Fixed {
-                width: ewidth,
-                value: eval,
-            } => match rep {
-                Evaluated::Fixed {
-                    width: rwidth,
-                    value: rval,
-                } => {
-                    let width = ewidth * rwidth;
-                    let mut value = eval;
+            Evaluated::Fixed { width: ewidth, .. } => match rep {
+                Evaluated::Fixed { value: rval, .. } => {
+                    let mut ret = Evaluated::Fixed { width: 0, value: 0 };
                     for _ in 0..rval {
-                        value <<= ewidth;
-                        value |= eval;
+                        ret = self.concat(ret, exp);
                     }
-                    Evaluated::Fixed { width, value }
+                    ret
                 }
                 Evaluated::Variable { width: rwidth } => Evaluated::Variable {
                     width: ewidth * rwidth,
@@ -745,6 +1219,40 @@ impl Evaluator {
         }
     }
 
+    /// Place `lsb` at the right side of `msb`
+    fn concat(&mut self, msb: Evaluated, lsb: Evaluated) -> Evaluated {
+        match (msb, lsb) {
+            (
+                Evaluated::Fixed {
+                    width: mwidth,
+                    value: mval,
+                },
+                Evaluated::Fixed {
+                    width: lwidth,
+                    value: lval,
+                },
+            ) => {
+                let width = mwidth + lwidth;
+                let unit = (lwidth < isize::BITS as usize - 1).then(|| 1isize << lwidth);
+                match unit.and_then(|x| mval.checked_mul(x).map(|y| (x, y))) {
+                    Some((unit, value)) => Evaluated::Fixed {
+                        width,
+                        value: value | (lval & (unit - 1)),
+                    },
+                    None => Evaluated::UnknownStatic,
+                }
+            }
+            (
+                Evaluated::Fixed { width: mwidth, .. } | Evaluated::Variable { width: mwidth },
+                Evaluated::Fixed { width: lwidth, .. } | Evaluated::Variable { width: lwidth },
+            ) => Evaluated::Variable {
+                width: mwidth + lwidth,
+            },
+            (x, y) if x.is_known_static() && y.is_known_static() => Evaluated::UnknownStatic,
+            _ => Evaluated::Unknown,
+        }
+    }
+
     fn concatenation_item(&mut self, arg: &ConcatenationItem) -> Evaluated {
         let e = self.expression(arg.expression.as_ref());
         if let Some(cio) = &arg.concatenation_item_opt {
@@ -760,16 +1268,12 @@ impl Evaluator {
     }
 
     fn concatenation_list(&mut self, arg: &ConcatenationList) -> Evaluated {
-        let mut eval_vec = vec![];
-        eval_vec.push(self.concatenation_item(arg.concatenation_item.as_ref()));
+        let mut ret = self.concatenation_item(arg.concatenation_item.as_ref());
         for cll in arg.concatenation_list_list.iter() {
-            eval_vec.push(self.concatenation_list_list(cll));
+            let x = self.concatenation_list_list(cll);
+            ret = self.concat(ret, x);
         }
-        eval_vec.reverse();
-        let default_value = Evaluated::Fixed { width: 1, value: 0 };
-        eval_vec
-            .iter()
-            .fold(default_value, |acc, x| self.do_concatenation(acc, *x))
+        ret
     }
 
     fn array_literal_item_group_default_colon_expression(
@@ -841,4 +1345,769 @@ impl Evaluator {
     fn switch_expression(&mut self, _arg: &SwitchExpression) -> Evaluated {
         Evaluated::Unknown
     }
+
+    /// Execute a function body if all arguments are fixed
+    fn constant_function(
+        &mut self,
+        name: StrId,
+        property: &FunctionProperty,
+        function_call: &FunctionCall,
+    ) -> Evaluated {
+        let mut args = Vec::new();
+        if let Some(ref x) = function_call.function_call_opt {
+            args.push(x.argument_list.argument_item.expression.as_ref());
+            for x in &x.argument_list.argument_list_list {
+                args.push(x.argument_item.expression.as_ref());
+            }
+        }
+        if args.len() != property.ports.len() {
+            return Evaluated::Unknown;
+        }
+
+        let mut values = Vec::new();
+        for (port, arg) in property.ports.iter().zip(args) {
+            let port_property = port.property();
+            if port_property.direction != Direction::Input {
+                return Evaluated::Unknown;
+            }
+            let width = port_property.r#type.and_then(|x| self.type_width(x));
+            let value = self.expression_with_width(arg, width);
+            values.push((port.symbol, Self::fit(value, width)));
+        }
+        if values.iter().any(|(_, x)| !x.is_known_static()) {
+            return Evaluated::Unknown;
+        }
+        if values.iter().any(|(_, x)| fixed(*x).is_none()) {
+            return Evaluated::UnknownStatic;
+        }
+
+        if self.limit_exceeded.is_some() {
+            return Evaluated::Unknown;
+        }
+        if self.calls.len() >= MAX_CALL_DEPTH {
+            self.limit_exceeded = Some((name, EvaluationLimit::Recursion));
+            return Evaluated::Unknown;
+        }
+        if self.calls.is_empty() {
+            self.steps = 0;
+        }
+
+        let overrides = self.overrides.clone();
+        self.overrides.extend(values);
+        self.calls.push(name);
+        let flow = self.statement_block(&property.body);
+        self.calls.pop();
+        self.overrides = overrides;
+
+        let width = property.ret.clone().and_then(|x| self.type_width(x));
+        match flow {
+            Some(Flow::Return(x)) if fixed(x).is_some() => Self::fit(x, width),
+            _ => Evaluated::Unknown,
+        }
+    }
+
+    fn expression_with_width(&mut self, arg: &Expression, width: Option<usize>) -> Evaluated {
+        let context_width = std::mem::take(&mut self.context_width);
+        if let Some(width) = width {
+            self.context_width.push(width);
+        }
+        let ret = self.expression(arg);
+        self.context_width = context_width;
+        ret
+    }
+
+    /// Give the declared width to a value
+    fn fit(x: Evaluated, width: Option<usize>) -> Evaluated {
+        match (x, width) {
+            (Evaluated::Fixed { value, .. }, Some(width)) => Evaluated::Fixed { width, value },
+            _ => x,
+        }
+    }
+
+    fn declared_width(id: SymbolId) -> Option<usize> {
+        match symbol_table::get(id)?.evaluate() {
+            Evaluated::Variable { width } | Evaluated::Fixed { width, .. } => Some(width),
+            _ => None,
+        }
+    }
+
+    fn step(&mut self) -> Option<()> {
+        self.steps += 1;
+        if self.steps > MAX_STEPS {
+            if self.limit_exceeded.is_none() {
+                let name = *self.calls.first().unwrap();
+                self.limit_exceeded = Some((name, EvaluationLimit::Iteration));
+            }
+            None
+        } else {
+            Some(())
+        }
+    }
+
+    fn statement_block(&mut self, arg: &StatementBlock) -> Option<Flow> {
+        for x in &arg.statement_block_list {
+            match self.statement_block_group(&x.statement_block_group)? {
+                Flow::Next => (),
+                x => return Some(x),
+            }
+        }
+        Some(Flow::Next)
+    }
+
+    fn statement_block_group(&mut self, arg: &StatementBlockGroup) -> Option<Flow> {
+        match arg.statement_block_group_group.as_ref() {
+            StatementBlockGroupGroup::LBraceStatementBlockGroupGroupListRBrace(x) => {
+                for x in &x.statement_block_group_group_list {
+                    match self.statement_block_group(&x.statement_block_group)? {
+                        Flow::Next => (),
+                        x => return Some(x),
+                    }
+                }
+                Some(Flow::Next)
+            }
+            StatementBlockGroupGroup::StatementBlockItem(x) => {
+                match x.statement_block_item.as_ref() {
+                    StatementBlockItem::VarDeclaration(x) => {
+                        // Forget the value of the previous iteration
+                        let symbol =
+                            symbol_table::resolve(x.var_declaration.identifier.as_ref()).ok()?;
+                        self.overrides.remove(&symbol.found.id);
+                        Some(Flow::Next)
+                    }
+                    StatementBlockItem::LetStatement(x) => {
+                        self.step()?;
+                        let x = &x.let_statement;
+                        let symbol = symbol_table::resolve(x.identifier.as_ref()).ok()?;
+                        let width = Self::declared_width(symbol.found.id);
+                        let value = self.expression_with_width(&x.expression, width);
+                        self.overrides
+                            .insert(symbol.found.id, Self::fit(value, width));
+                        Some(Flow::Next)
+                    }
+                    StatementBlockItem::Statement(x) => self.statement(&x.statement),
+                }
+            }
+        }
+    }
+
+    fn statement(&mut self, arg: &Statement) -> Option<Flow> {
+        self.step()?;
+        match arg {
+            Statement::IdentifierStatement(x) => {
+                let x = &x.identifier_statement;
+                match x.identifier_statement_group.as_ref() {
+                    IdentifierStatementGroup::Assignment(y) => {
+                        self.assignment(&x.expression_identifier, &y.assignment)
+                    }
+                    IdentifierStatementGroup::FunctionCall(_) => None,
+                }
+            }
+            Statement::IfStatement(x) => self.if_statement(&x.if_statement),
+            Statement::ReturnStatement(x) => {
+                let value = self.expression(&x.return_statement.expression);
+                Some(Flow::Return(value))
+            }
+            Statement::BreakStatement(_) => Some(Flow::Break),
+            Statement::ForStatement(x) => self.for_statement(&x.for_statement),
+            Statement::CaseStatement(x) => self.case_statement(&x.case_statement),
+            Statement::SwitchStatement(x) => self.switch_statement(&x.switch_statement),
+            Statement::IfEnableStatement(_) => None,
+        }
+    }
+
+    fn assignment(&mut self, identifier: &ExpressionIdentifier, arg: &Assignment) -> Option<Flow> {
+        // Assignment to a part of variables is not supported
+        if !identifier.expression_identifier_list.is_empty()
+            || !identifier.expression_identifier_list0.is_empty()
+        {
+            return None;
+        }
+        let symbol = symbol_table::resolve(identifier).ok()?;
+        if !matches!(
+            symbol.found.kind,
+            SymbolKind::Variable(_) | SymbolKind::Port(_)
+        ) {
+            return None;
+        }
+
+        let width = Self::declared_width(symbol.found.id);
+        let value = self.expression_with_width(&arg.expression, width);
+        let value = match arg.assignment_group.as_ref() {
+            AssignmentGroup::Equ(_) => value,
+            AssignmentGroup::AssignmentOperator(x) => {
+                let operator = x.assignment_operator.assignment_operator_token.to_string();
+                let current = self.expression_identifier(identifier);
+                self.binary_operator(operator.trim_end_matches('='), current, value)
+            }
+        };
+        self.overrides
+            .insert(symbol.found.id, Self::fit(value, width));
+        Some(Flow::Next)
+    }
+
+    fn if_statement(&mut self, arg: &IfStatement) -> Option<Flow> {
+        if fixed(self.expression(&arg.expression))? != 0 {
+            return self.statement_block(&arg.statement_block);
+        }
+        for x in &arg.if_statement_list {
+            if fixed(self.expression(&x.expression))? != 0 {
+                return self.statement_block(&x.statement_block);
+            }
+        }
+        if let Some(ref x) = arg.if_statement_opt {
+            self.statement_block(&x.statement_block)
+        } else {
+            Some(Flow::Next)
+        }
+    }
+
+    fn for_statement(&mut self, arg: &ForStatement) -> Option<Flow> {
+        let symbol = symbol_table::resolve(arg.identifier.as_ref()).ok()?;
+        let width = self.type_width(arg.scalar_type.as_ref().into());
+        let start = fixed(self.expression(&arg.range.expression))?;
+        let range_opt = arg.range.range_opt.as_ref()?;
+        let end = fixed(self.expression(&range_opt.expression))?;
+        let inclusive = matches!(
+            range_opt.range_operator.as_ref(),
+            RangeOperator::DotDotEqu(_)
+        );
+        let (operator, step) = if let Some(ref x) = arg.for_statement_opt {
+            let operator = x.assignment_operator.assignment_operator_token.to_string();
+            (operator, self.expression(&x.expression))
+        } else {
+            (
+                "+=".to_string(),
+                Evaluated::Fixed {
+                    width: 32,
+                    value: 1,
+                },
+            )
+        };
+
+        let mut i = start;
+        while if inclusive { i <= end } else { i < end } {
+            self.step()?;
+            let value = Evaluated::Fixed {
+                width: width.unwrap_or(32),
+                value: i,
+            };
+            self.overrides.insert(symbol.found.id, value);
+            match self.statement_block(&arg.statement_block)? {
+                Flow::Next => (),
+                Flow::Break => break,
+                x => return Some(x),
+            }
+            let next = self.binary_operator(operator.trim_end_matches('='), value, step);
+            i = fixed(next)?;
+        }
+        Some(Flow::Next)
+    }
+
+    fn case_statement(&mut self, arg: &CaseStatement) -> Option<Flow> {
+        let value = fixed(self.expression(&arg.expression))?;
+        for x in &arg.case_statement_list {
+            let item = &x.case_item;
+            let matched = match item.case_item_group.as_ref() {
+                CaseItemGroup::CaseCondition(x) => {
+                    let x = &x.case_condition;
+                    let mut ret = self.range_contains(&x.range_item.range, value)?;
+                    for x in &x.case_condition_list {
+                        ret |= self.range_contains(&x.range_item.range, value)?;
+                    }
+                    ret
+                }
+                CaseItemGroup::Defaul(_) => true,
+            };
+            if matched {
+                return match item.case_item_group0.as_ref() {
+                    CaseItemGroup0::Statement(x) => self.statement(&x.statement),
+                    CaseItemGroup0::StatementBlock(x) => self.statement_block(&x.statement_block),
+                };
+            }
+        }
+        Some(Flow::Next)
+    }
+
+    fn range_contains(&mut self, arg: &Range, value: isize) -> Option<bool> {
+        let start = fixed(self.expression(&arg.expression))?;
+        if let Some(ref x) = arg.range_opt {
+            let end = fixed(self.expression(&x.expression))?;
+            let inclusive = matches!(x.range_operator.as_ref(), RangeOperator::DotDotEqu(_));
+            Some(start <= value && if inclusive { value <= end } else { value < end })
+        } else {
+            Some(start == value)
+        }
+    }
+
+    fn switch_statement(&mut self, arg: &SwitchStatement) -> Option<Flow> {
+        for x in &arg.switch_statement_list {
+            let item = &x.switch_item;
+            let matched = match item.switch_item_group.as_ref() {
+                SwitchItemGroup::SwitchCondition(x) => {
+                    let x = &x.switch_condition;
+                    let mut ret = fixed(self.expression(&x.expression))? != 0;
+                    for x in &x.switch_condition_list {
+                        ret |= fixed(self.expression(&x.expression))? != 0;
+                    }
+                    ret
+                }
+                SwitchItemGroup::Defaul(_) => true,
+            };
+            if matched {
+                return match item.switch_item_group0.as_ref() {
+                    SwitchItemGroup0::Statement(x) => self.statement(&x.statement),
+                    SwitchItemGroup0::StatementBlock(x) => self.statement_block(&x.statement_block),
+                };
+            }
+        }
+        Some(Flow::Next)
+    }
+}
+
+/// Width and signedness of an expression determined by itself,
+/// which is extended or truncated to the context by SystemVerilog rules
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub struct ExpressionWidth {
+    pub width: usize,
+    pub signed: bool,
+    /// `false` for unsized literals like `1` and `'0` which follow the width of context
+    pub sized: bool,
+}
+
+impl ExpressionWidth {
+    fn sized(width: usize, signed: bool) -> Self {
+        Self {
+            width,
+            signed,
+            sized: true,
+        }
+    }
+
+    /// Operands of arithmetic and bitwise operators are extended to the wider one,
+    /// and the result is signed only if both are signed.
+    /// Unsized literals don't widen the result.
+    fn merge(self, other: Self) -> Self {
+        match (self.sized, other.sized) {
+            (true, false) => self,
+            (false, true) => other,
+            _ => Self {
+                width: self.width.max(other.width),
+                signed: self.signed && other.signed,
+                sized: self.sized,
+            },
+        }
+    }
+
+    fn binary(operator: &str, left: Self, right: Self) -> Option<Self> {
+        match operator {
+            "**" | "<<<" | ">>>" | "<<" | ">>" => Some(left),
+            "<=" | ">=" | "<:" | "<" | ">:" | ">" | "===" | "==?" | "!==" | "!=?" | "==" | "!="
+            | "&&" | "||" => Some(Self::sized(1, false)),
+            "/" | "*" | "%" | "+" | "-" | "&" | "^~" | "^" | "~^" | "|" => Some(left.merge(right)),
+            _ => None,
+        }
+    }
+
+    fn unary(operator: &str, operand: Self) -> Option<Self> {
+        match operator {
+            "+" | "-" | "~" => Some(operand),
+            "!" | "~&" | "~|" | "&" | "|" | "^" | "~^" | "^~" => Some(Self::sized(1, false)),
+            _ => None,
+        }
+    }
+}
+
+/// Shape of a type to apply selects
+struct TypeShape {
+    /// Array dimensions followed by packed dimensions
+    dims: Vec<usize>,
+    array_dims: usize,
+    /// Width of the element of the innermost dimension
+    base: usize,
+    signed: bool,
+}
+
+impl Evaluator {
+    /// Width of `arg` determined by itself, `None` if it is not known statically
+    pub fn expression_width(&mut self, arg: &Expression) -> Option<ExpressionWidth> {
+        let mut ret = self.expression01_width(&arg.expression01)?;
+        for x in &arg.expression_list {
+            let operator = x.operator01.operator01_token.to_string();
+            let operand = self.expression01_width(&x.expression01)?;
+            ret = ExpressionWidth::binary(&operator, ret, operand)?;
+        }
+        Some(ret)
+    }
+
+    fn expression01_width(&mut self, arg: &Expression01) -> Option<ExpressionWidth> {
+        let mut ret = self.expression02_width(&arg.expression02)?;
+        for x in &arg.expression01_list {
+            let operator = x.operator02.operator02_token.to_string();
+            let operand = self.expression02_width(&x.expression02)?;
+            ret = ExpressionWidth::binary(&operator, ret, operand)?;
+        }
+        Some(ret)
+    }
+
+    fn expression02_width(&mut self, arg: &Expression02) -> Option<ExpressionWidth> {
+        let mut ret = self.expression03_width(&arg.expression03)?;
+        for x in &arg.expression02_list {
+            let operator = x.operator03.operator03_token.to_string();
+            let operand = self.expression03_width(&x.expression03)?;
+            ret = ExpressionWidth::binary(&operator, ret, operand)?;
+        }
+        Some(ret)
+    }
+
+    fn expression03_width(&mut self, arg: &Expression03) -> Option<ExpressionWidth> {
+        let mut ret = self.expression04_width(&arg.expression04)?;
+        for x in &arg.expression03_list {
+            let operator = x.operator04.operator04_token.to_string();
+            let operand = self.expression04_width(&x.expression04)?;
+            ret = ExpressionWidth::binary(&operator, ret, operand)?;
+        }
+        Some(ret)
+    }
+
+    fn expression04_width(&mut self, arg: &Expression04) -> Option<ExpressionWidth> {
+        let mut ret = self.expression05_width(&arg.expression05)?;
+        for x in &arg.expression04_list {
+            let operator = x.operator05.operator05_token.to_string();
+            let operand = self.expression05_width(&x.expression05)?;
+            ret = ExpressionWidth::binary(&operator, ret, operand)?;
+        }
+        Some(ret)
+    }
+
+    fn expression05_width(&mut self, arg: &Expression05) -> Option<ExpressionWidth> {
+        let mut ret = self.expression06_width(&arg.expression06)?;
+        for x in &arg.expression05_list {
+            let operator = x.operator06.operator06_token.to_string();
+            let operand = self.expression06_width(&x.expression06)?;
+            ret = ExpressionWidth::binary(&operator, ret, operand)?;
+        }
+        Some(ret)
+    }
+
+    fn expression06_width(&mut self, arg: &Expression06) -> Option<ExpressionWidth> {
+        let mut ret = self.expression07_width(&arg.expression07)?;
+        for x in &arg.expression06_list {
+            let operator = x.operator07.operator07_token.to_string();
+            let operand = self.expression07_width(&x.expression07)?;
+            ret = ExpressionWidth::binary(&operator, ret, operand)?;
+        }
+        Some(ret)
+    }
+
+    fn expression07_width(&mut self, arg: &Expression07) -> Option<ExpressionWidth> {
+        let mut ret = self.expression08_width(&arg.expression08)?;
+        for x in &arg.expression07_list {
+            let operator = x.operator08.operator08_token.to_string();
+            let operand = self.expression08_width(&x.expression08)?;
+            ret = ExpressionWidth::binary(&operator, ret, operand)?;
+        }
+        Some(ret)
+    }
+
+    fn expression08_width(&mut self, arg: &Expression08) -> Option<ExpressionWidth> {
+        let mut ret = self.expression09_width(&arg.expression09)?;
+        for x in &arg.expression08_list {
+            let operator = x.operator09.operator09_token.to_string();
+            let operand = self.expression09_width(&x.expression09)?;
+            ret = ExpressionWidth::binary(&operator, ret, operand)?;
+        }
+        Some(ret)
+    }
+
+    fn expression09_width(&mut self, arg: &Expression09) -> Option<ExpressionWidth> {
+        let mut ret = self.expression10_width(&arg.expression10)?;
+        for x in &arg.expression09_list {
+            let operator = x.operator10.operator10_token.to_string();
+            let operand = self.expression10_width(&x.expression10)?;
+            ret = ExpressionWidth::binary(&operator, ret, operand)?;
+        }
+        Some(ret)
+    }
+
+    fn expression10_width(&mut self, arg: &Expression10) -> Option<ExpressionWidth> {
+        let mut ret = self.expression11_width(&arg.expression11)?;
+        for x in &arg.expression10_list {
+            let operator = match &*x.expression10_list_group {
+                Expression10ListGroup::Operator11(x) => x.operator11.operator11_token.to_string(),
+                Expression10ListGroup::Star(x) => x.star.star_token.to_string(),
+            };
+            let operand = self.expression11_width(&x.expression11)?;
+            ret = ExpressionWidth::binary(&operator, ret, operand)?;
+        }
+        Some(ret)
+    }
+
+    fn expression11_width(&mut self, arg: &Expression11) -> Option<ExpressionWidth> {
+        let mut ret = self.expression12_width(&arg.expression12)?;
+        for x in &arg.expression11_list {
+            let operator = x.operator12.operator12_token.to_string();
+            let operand = self.expression12_width(&x.expression12)?;
+            ret = ExpressionWidth::binary(&operator, ret, operand)?;
+        }
+        Some(ret)
+    }
+
+    fn expression12_width(&mut self, arg: &Expression12) -> Option<ExpressionWidth> {
+        let ret = self.expression13_width(&arg.expression13)?;
+        let Some(ref x) = arg.expression12_opt else {
+            return Some(ret);
+        };
+        match x.casting_type.as_ref() {
+            CastingType::U32(_) => Some(ExpressionWidth::sized(32, false)),
+            CastingType::U64(_) => Some(ExpressionWidth::sized(64, false)),
+            CastingType::I32(_) => Some(ExpressionWidth::sized(32, true)),
+            CastingType::I64(_) => Some(ExpressionWidth::sized(64, true)),
+            CastingType::UserDefinedType(x) => {
+                let symbol =
+                    symbol_table::resolve(x.user_defined_type.scoped_identifier.as_ref()).ok()?;
+                let width = self.symbol_width(&symbol.found)?;
+                Some(ExpressionWidth::sized(width, false))
+            }
+            CastingType::Based(x) => {
+                let width = usize::try_from(fixed(self.based(&x.based))?).ok()?;
+                Some(ExpressionWidth::sized(width, ret.signed))
+            }
+            CastingType::BaseLess(x) => {
+                let width = usize::try_from(fixed(self.base_less(&x.base_less))?).ok()?;
+                Some(ExpressionWidth::sized(width, ret.signed))
+            }
+            _ => None,
+        }
+    }
+
+    fn expression13_width(&mut self, arg: &Expression13) -> Option<ExpressionWidth> {
+        let mut ret = self.factor_width(&arg.factor)?;
+        for x in arg.expression13_list.iter().rev() {
+            let operator = match &*x.expression13_list_group {
+                Expression13ListGroup::UnaryOperator(x) => {
+                    x.unary_operator.unary_operator_token.to_string()
+                }
+                Expression13ListGroup::Operator03(x) => x.operator03.operator03_token.to_string(),
+                Expression13ListGroup::Operator04(x) => x.operator04.operator04_token.to_string(),
+                Expression13ListGroup::Operator05(x) => x.operator05.operator05_token.to_string(),
+                Expression13ListGroup::Operator10(x) => x.operator10.operator10_token.to_string(),
+            };
+            ret = ExpressionWidth::unary(&operator, ret)?;
+        }
+        Some(ret)
+    }
+
+    fn factor_width(&mut self, arg: &Factor) -> Option<ExpressionWidth> {
+        match arg {
+            Factor::Number(x) => self.number_width(&x.number),
+            Factor::IdentifierFactor(x) => {
+                let x = &x.identifier_factor;
+                let symbol = symbol_table::resolve(x.expression_identifier.as_ref()).ok()?;
+                if x.identifier_factor_opt.is_some() {
+                    let SymbolKind::Function(ref x) = symbol.found.kind else {
+                        return None;
+                    };
+                    self.declared_type_width(x.ret.as_ref()?, &symbol.found.namespace)
+                } else {
+                    self.expression_identifier_width(&symbol.found, &x.expression_identifier)
+                }
+            }
+            Factor::LParenExpressionRParen(x) => self.expression_width(&x.expression),
+            Factor::LBraceConcatenationListRBrace(x) => {
+                let list = &x.concatenation_list;
+                let mut width = 0;
+                let items = std::iter::once(&list.concatenation_item).chain(
+                    list.concatenation_list_list
+                        .iter()
+                        .map(|x| &x.concatenation_item),
+                );
+                for item in items {
+                    let item_width = self.expression_width(&item.expression)?;
+                    if !item_width.sized {
+                        return None;
+                    }
+                    let repeat = if let Some(ref x) = item.concatenation_item_opt {
+                        usize::try_from(fixed(self.expression(&x.expression))?).ok()?
+                    } else {
+                        1
+                    };
+                    width += item_width.width * repeat;
+                }
+                Some(ExpressionWidth::sized(width, false))
+            }
+            Factor::IfExpression(x) => {
+                let x = &x.if_expression;
+                let mut ret = self.expression_width(&x.expression0)?;
+                for x in &x.if_expression_list {
+                    ret = ret.merge(self.expression_width(&x.expression0)?);
+                }
+                Some(ret.merge(self.expression_width(&x.expression1)?))
+            }
+            Factor::CaseExpression(x) => {
+                let x = &x.case_expression;
+                let mut ret = self.expression_width(&x.expression0)?;
+                for x in &x.case_expression_list {
+                    ret = ret.merge(self.expression_width(&x.expression)?);
+                }
+                Some(ret.merge(self.expression_width(&x.expression1)?))
+            }
+            Factor::SwitchExpression(x) => {
+                let x = &x.switch_expression;
+                let mut ret = self.expression_width(&x.expression)?;
+                for x in &x.switch_expression_list {
+                    ret = ret.merge(self.expression_width(&x.expression)?);
+                }
+                Some(ret.merge(self.expression_width(&x.expression0)?))
+            }
+            Factor::InsideExpression(_) | Factor::OutsideExpression(_) => {
+                Some(ExpressionWidth::sized(1, false))
+            }
+            _ => None,
+        }
+    }
+
+    fn number_width(&mut self, arg: &Number) -> Option<ExpressionWidth> {
+        let Number::IntegralNumber(x) = arg else {
+            return None;
+        };
+        match x.integral_number.as_ref() {
+            IntegralNumber::Based(x) => {
+                let text = x.based.based_token.to_string();
+                let (width, rest) = text.split_once('\'')?;
+                let width = width.replace('_', "").parse().ok()?;
+                Some(ExpressionWidth::sized(width, rest.starts_with('s')))
+            }
+            IntegralNumber::BaseLess(_) => Some(ExpressionWidth {
+                width: 32,
+                signed: true,
+                sized: false,
+            }),
+            IntegralNumber::AllBit(_) => Some(ExpressionWidth {
+                width: 1,
+                signed: false,
+                sized: false,
+            }),
+        }
+    }
+
+    /// Width of `arg` which refers `symbol`
+    pub fn expression_identifier_width(
+        &mut self,
+        symbol: &Symbol,
+        arg: &ExpressionIdentifier,
+    ) -> Option<ExpressionWidth> {
+        if arg.expression_identifier_opt.is_some() {
+            return None;
+        }
+        // Selects are applied to the last member
+        let selects: Vec<_> = if let Some(x) = arg.expression_identifier_list0.last() {
+            x.expression_identifier_list0_list
+                .iter()
+                .map(|x| x.select.as_ref())
+                .collect()
+        } else {
+            arg.expression_identifier_list
+                .iter()
+                .map(|x| x.select.as_ref())
+                .collect()
+        };
+        self.identifier_width(symbol, &selects)
+    }
+
+    /// Width of `symbol` referred with `selects`
+    pub fn identifier_width(
+        &mut self,
+        symbol: &Symbol,
+        selects: &[&Select],
+    ) -> Option<ExpressionWidth> {
+        let r#type = match &symbol.kind {
+            SymbolKind::Variable(x) => &x.r#type,
+            SymbolKind::Port(x) => x.r#type.as_ref()?,
+            SymbolKind::Parameter(x) => &x.r#type,
+            SymbolKind::StructMember(x) => &x.r#type,
+            SymbolKind::UnionMember(x) => &x.r#type,
+            SymbolKind::EnumMember(_) if selects.is_empty() => {
+                let SymbolKind::Enum(x) = symbol.get_parent()?.kind else {
+                    return None;
+                };
+                return Some(ExpressionWidth::sized(x.width, false));
+            }
+            _ => return None,
+        };
+        let mut shape = self.type_shape(r#type, &symbol.namespace)?;
+
+        for (i, select) in selects.iter().enumerate() {
+            // Bits of a scalar are selected
+            if shape.dims.is_empty() {
+                shape.dims.push(shape.base);
+                shape.base = 1;
+            }
+            if i >= shape.array_dims {
+                shape.signed = false;
+            }
+            let Some(ref x) = select.select_opt else {
+                shape.dims.remove(0);
+                continue;
+            };
+
+            // Part select must be the last
+            if i + 1 != selects.len() {
+                return None;
+            }
+            let count = match x.select_operator.as_ref() {
+                SelectOperator::Colon(_) => {
+                    let msb = fixed(self.expression(&select.expression))?;
+                    let lsb = fixed(self.expression(&x.expression))?;
+                    msb.abs_diff(lsb) + 1
+                }
+                _ => usize::try_from(fixed(self.expression(&x.expression))?).ok()?,
+            };
+            shape.dims[0] = count;
+        }
+
+        let width = shape.dims.iter().product::<usize>() * shape.base;
+        Some(ExpressionWidth::sized(width, shape.signed))
+    }
+
+    /// Width of `r#type` referred as a whole, `None` if it is not a bit vector
+    pub fn declared_type_width(
+        &mut self,
+        r#type: &Type,
+        namespace: &Namespace,
+    ) -> Option<ExpressionWidth> {
+        let shape = self.type_shape(r#type, namespace)?;
+        let width = shape.dims.iter().product::<usize>() * shape.base;
+        Some(ExpressionWidth::sized(width, shape.signed))
+    }
+
+    fn type_shape(&mut self, r#type: &Type, namespace: &Namespace) -> Option<TypeShape> {
+        let mut dims = Vec::new();
+        for x in r#type.array.iter().chain(r#type.width.iter()) {
+            dims.push(usize::try_from(fixed(self.expression(x))?).ok()?);
+        }
+        let (base, signed) = match r#type.kind {
+            TypeKind::Bit | TypeKind::Logic => (1, false),
+            TypeKind::U32 => (32, false),
+            TypeKind::U64 => (64, false),
+            TypeKind::I32 => (32, true),
+            TypeKind::I64 => (64, true),
+            TypeKind::UserDefined(ref path) => {
+                let symbol = symbol_table::resolve((path, namespace)).ok()?.found;
+                let signed = match symbol.kind {
+                    SymbolKind::TypeDef(ref x) => {
+                        matches!(x.r#type.kind, TypeKind::I32 | TypeKind::I64)
+                    }
+                    _ => false,
+                };
+                (self.symbol_width(&symbol)?, signed)
+            }
+            _ => return None,
+        };
+        Some(TypeShape {
+            dims,
+            array_dims: r#type.array.len(),
+            base,
+            signed,
+        })
+    }
 }
@@ -1,4 +1,5 @@
 pub mod check_attribute;
+pub mod check_constant_condition;
 pub mod check_power_domain;
 pub mod check_power_enable;
 pub mod check_embed_include;
@@ -10,15 +11,18 @@ pub mod check_modport;
 pub mod check_msb_lsb;
 pub mod check_number;
 pub mod check_port;
+pub mod check_port_connection;
 pub mod check_proto;
 pub mod check_separator;
 pub mod check_statement;
 pub mod check_type;
 pub mod check_unsafe;
 pub mod check_var_ref;
+pub mod create_comb_dependency;
 pub mod create_reference;
 pub mod create_symbol_table;
 use check_attribute::*;
+use check_constant_condition::*;
 use check_power_domain::*;
 use check_power_enable::*;
 use check_embed_include::*;
@@ -30,12 +34,14 @@ use check_modport::*;
 use check_msb_lsb::*;
 use check_number::*;
 use check_port::*;
+use check_port_connection::*;
 use check_proto::*;
 use check_separator::*;
 use check_statement::*;
 use check_type::*;
 use check_unsafe::*;
 use check_var_ref::*;
+use create_comb_dependency::*;
 use create_reference::*;
 use create_symbol_table::*;
 
@@ -108,6 +114,9 @@ pub struct Pass2Handlers<'a> {
     check_power_domain: CheckPowerDomain<'a>,
     check_proto: CheckProto<'a>,
     check_type: CheckType<'a>,
+    check_port_connection: CheckPortConnection<'a>,
+    check_constant_condition: CheckConstantCondition<'a>,
+    create_comb_dependency: CreateCombDependency,
 }
 
 impl<'a> Pass2Handlers<'a> {
@@ -125,6 +134,9 @@ impl<'a> Pass2Handlers<'a> {
             check_power_domain: CheckPowerDomain::new(text),
             check_proto: CheckProto::new(text),
             check_type: CheckType::new(text),
+            check_port_connection: CheckPortConnection::new(text),
+            check_constant_condition: CheckConstantCondition::new(text),
+            create_comb_dependency: CreateCombDependency::default(),
         }
     }
 
@@ -142,6 +154,9 @@ impl<'a> Pass2Handlers<'a> {
             &mut self.check_power_domain as &mut dyn Handler,
             &mut self.check_proto as &mut dyn Handler,
             &mut self.check_type as &mut dyn Handler,
+            &mut self.check_port_connection as &mut dyn Handler,
+            &mut self.check_constant_condition as &mut dyn Handler,
+            &mut self.create_comb_dependency as &mut dyn Handler,
         ]
     }
 
@@ -159,6 +174,8 @@ impl<'a> Pass2Handlers<'a> {
         ret.append(&mut self.check_power_domain.errors);
         ret.append(&mut self.check_proto.errors);
         ret.append(&mut self.check_type.errors);
+        ret.append(&mut self.check_port_connection.errors);
+        ret.append(&mut self.check_constant_condition.errors);
         ret
     }
 }
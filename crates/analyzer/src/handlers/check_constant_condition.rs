@@ -0,0 +1,153 @@
+use crate::analyzer_error::AnalyzerError;
+use crate::evaluator::{Evaluated, Evaluator};
+use std::collections::HashSet;
+use veryla_parser::resource_table::TokenId;
+use veryla_parser::veryla_grammar_trait::*;
+use veryla_parser::veryla_token::{Token, TokenRange};
+use veryla_parser::veryla_walker::{Handler, HandlerPoint};
+use veryla_parser::ParolError;
+
+#[derive(Default)]
+pub struct CheckConstantCondition<'a> {
+    pub errors: Vec<AnalyzerError>,
+    text: &'a str,
+    point: HandlerPoint,
+    /// `{` of generate blocks which are never elaborated
+    unreachable_blocks: HashSet<TokenId>,
+    unreachable_depth: usize,
+}
+
+impl<'a> CheckConstantCondition<'a> {
+    pub fn new(text: &'a str) -> Self {
+        Self {
+            text,
+            ..Default::default()
+        }
+    }
+
+    fn enter_block(&mut self, l_brace: &Token) {
+        if self.unreachable_blocks.contains(&l_brace.id) {
+            self.unreachable_depth += 1;
+        }
+    }
+
+    fn leave_block(&mut self, l_brace: &Token) {
+        if self.unreachable_blocks.contains(&l_brace.id) {
+            self.unreachable_depth -= 1;
+        }
+    }
+}
+
+impl Handler for CheckConstantCondition<'_> {
+    fn set_point(&mut self, p: HandlerPoint) {
+        self.point = p;
+    }
+}
+
+/// Value of the condition if it is fixed regardless of parameter overriding
+fn constant(arg: &Expression) -> Option<bool> {
+    let mut evaluator = Evaluator {
+        unbound_params: true,
+        ..Default::default()
+    };
+    if let Evaluated::Fixed { value, .. } = evaluator.expression(arg) {
+        Some(value != 0)
+    } else {
+        None
+    }
+}
+
+impl VerylaGrammarTrait for CheckConstantCondition<'_> {
+    fn if_statement(&mut self, arg: &IfStatement) -> Result<(), ParolError> {
+        if let HandlerPoint::Before = self.point {
+            if self.unreachable_depth != 0 {
+                return Ok(());
+            }
+
+            let conditions = std::iter::once(arg.expression.as_ref())
+                .chain(arg.if_statement_list.iter().map(|x| x.expression.as_ref()));
+            for x in conditions {
+                if let Some(value) = constant(x) {
+                    self.errors.push(AnalyzerError::constant_condition(
+                        &value.to_string(),
+                        self.text,
+                        &x.into(),
+                    ));
+                }
+            }
+        }
+        Ok(())
+    }
+
+    fn generate_if_declaration(&mut self, arg: &GenerateIfDeclaration) -> Result<(), ParolError> {
+        if let HandlerPoint::Before = self.point {
+            // keyword, condition and `{` of each branch
+            let mut branches = vec![(
+                arg.r#if.if_token.token,
+                Some(arg.expression.as_ref()),
+                arg.generate_named_block.l_brace.l_brace_token.token,
+            )];
+            for x in &arg.generate_if_declaration_list {
+                branches.push((
+                    x.r#else.else_token.token,
+                    Some(x.expression.as_ref()),
+                    x.generate_optional_named_block.l_brace.l_brace_token.token,
+                ));
+            }
+            if let Some(ref x) = arg.generate_if_declaration_opt {
+                branches.push((
+                    x.r#else.else_token.token,
+                    None,
+                    x.generate_optional_named_block.l_brace.l_brace_token.token,
+                ));
+            }
+
+            let last = branches.len() - 1;
+            let mut taken = false;
+            for (i, (keyword, condition, l_brace)) in branches.into_iter().enumerate() {
+                let value = condition.and_then(constant);
+                if taken || value == Some(false) {
+                    self.unreachable_blocks.insert(l_brace.id);
+                    if self.unreachable_depth == 0 {
+                        self.errors.push(AnalyzerError::unreachable_generate_block(
+                            self.text,
+                            &TokenRange {
+                                beg: keyword,
+                                end: l_brace,
+                            },
+                        ));
+                    }
+                } else if value == Some(true) {
+                    taken = true;
+                    if i == last && self.unreachable_depth == 0 {
+                        self.errors.push(AnalyzerError::constant_condition(
+                            "true",
+                            self.text,
+                            &condition.unwrap().into(),
+                        ));
+                    }
+                }
+            }
+        }
+        Ok(())
+    }
+
+    fn generate_named_block(&mut self, arg: &GenerateNamedBlock) -> Result<(), ParolError> {
+        match self.point {
+            HandlerPoint::Before => self.enter_block(&arg.l_brace.l_brace_token.token),
+            HandlerPoint::After => self.leave_block(&arg.l_brace.l_brace_token.token),
+        }
+        Ok(())
+    }
+
+    fn generate_optional_named_block(
+        &mut self,
+        arg: &GenerateOptionalNamedBlock,
+    ) -> Result<(), ParolError> {
+        match self.point {
+            HandlerPoint::Before => self.enter_block(&arg.l_brace.l_brace_token.token),
+            HandlerPoint::After => self.leave_block(&arg.l_brace.l_brace_token.token),
+        }
+        Ok(())
+    }
+}
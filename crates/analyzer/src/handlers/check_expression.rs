@@ -1,6 +1,7 @@
 use crate::analyzer_error::AnalyzerError;
-use crate::evaluator::{Evaluated, Evaluator};
-use crate::symbol::{Direction, GenericBoundKind, SymbolId, SymbolKind};
+use crate::case_coverage::{self, Coverage, Domain};
+use crate::evaluator::{Evaluated, Evaluator, ExpressionWidth};
+use crate::symbol::{Direction, GenericBoundKind, Symbol, SymbolId, SymbolKind};
 use crate::symbol_table;
 use veryla_parser::veryla_grammar_trait::*;
 use veryla_parser::veryla_token::TokenRange;
@@ -28,6 +29,209 @@ impl<'a> CheckExpression<'a> {
     }
 }
 
+impl CheckExpression<'_> {
+    fn check_evaluation_limit(&mut self, arg: &Expression) {
+        let mut evaluator = Evaluator::new();
+        evaluator.expression(arg);
+        if let Some((function, limit)) = evaluator.limit_exceeded {
+            self.errors.push(AnalyzerError::evaluation_limit_exceeded(
+                &function.to_string(),
+                &limit.to_string(),
+                self.text,
+                &arg.into(),
+            ));
+        }
+    }
+}
+
+impl CheckExpression<'_> {
+    /// Check arms of `case` whose conditions are given in order.
+    /// Exhaustiveness is checked only if there is no default arm.
+    fn check_case_arms(&mut self, target: &Expression, arms: &[&CaseCondition], has_default: bool) {
+        let mut evaluator = Evaluator::new();
+        let Some(domain) = Domain::new(&mut evaluator, target) else {
+            return;
+        };
+
+        let mut coverage = Coverage::default();
+        let mut known = true;
+        for arm in arms {
+            let mut items = vec![arm.range_item.as_ref()];
+            items.extend(
+                arm.case_condition_list
+                    .iter()
+                    .map(|x| x.range_item.as_ref()),
+            );
+
+            let mut intervals = Vec::new();
+            let mut arm_known = true;
+            for item in &items {
+                match case_coverage::range_item(&mut evaluator, item, domain.width()) {
+                    Some(mut x) => intervals.append(&mut x),
+                    None => arm_known = false,
+                }
+            }
+
+            if arm_known && intervals.iter().all(|x| coverage.contains(x.0, x.1)) {
+                let beg: TokenRange = items[0].range.expression.as_ref().into();
+                let last = &items[items.len() - 1].range;
+                let end: TokenRange = match last.range_opt {
+                    Some(ref x) => x.expression.as_ref().into(),
+                    None => last.expression.as_ref().into(),
+                };
+                let range = TokenRange {
+                    beg: beg.beg,
+                    end: end.end,
+                };
+                self.errors
+                    .push(AnalyzerError::unreachable_case_arm(self.text, &range));
+            }
+
+            known &= arm_known;
+            for (beg, end) in intervals {
+                coverage.insert(beg, end);
+            }
+        }
+
+        if !has_default && known {
+            let missing = domain.missing(&coverage);
+            if !missing.is_empty() {
+                self.errors.push(AnalyzerError::non_exhaustive_case(
+                    &case_coverage::missing_text(missing),
+                    self.text,
+                    &target.into(),
+                ));
+            }
+        }
+    }
+
+    /// Arms of `switch` after an arm whose condition is always true are never matched
+    fn check_switch_arms(&mut self, arms: &[&SwitchCondition]) {
+        let mut evaluator = Evaluator::new();
+        let mut matched = false;
+        for arm in arms {
+            if matched {
+                self.errors.push(AnalyzerError::unreachable_case_arm(
+                    self.text,
+                    &arm.expression.as_ref().into(),
+                ));
+                continue;
+            }
+
+            let mut conditions = vec![arm.expression.as_ref()];
+            conditions.extend(
+                arm.switch_condition_list
+                    .iter()
+                    .map(|x| x.expression.as_ref()),
+            );
+            matched = conditions.iter().any(
+                |x| matches!(evaluator.expression(x), Evaluated::Fixed { value, .. } if value != 0),
+            );
+        }
+    }
+}
+
+/// Number of bits required to represent `value` in two's complement
+fn required_width(value: isize) -> usize {
+    let bits = if value < 0 {
+        isize::BITS - value.leading_ones() + 1
+    } else {
+        isize::BITS - value.leading_zeros()
+    };
+    bits.max(1) as usize
+}
+
+impl CheckExpression<'_> {
+    /// Check that `value` assigned to `name` fits its width and signedness
+    fn check_assignment_width(
+        &mut self,
+        name: &str,
+        target: Option<ExpressionWidth>,
+        value: &Expression,
+    ) {
+        let Some(target) = target else {
+            return;
+        };
+        let mut evaluator = Evaluator::new();
+        let Some(actual) = evaluator.expression_width(value) else {
+            return;
+        };
+        let range: TokenRange = value.into();
+
+        // Constants are checked whether the value fits
+        evaluator.context_width.push(target.width);
+        if let Evaluated::Fixed { value, .. } = evaluator.expression(value) {
+            let actual = required_width(value);
+            if actual > target.width {
+                self.errors.push(AnalyzerError::implicit_truncation(
+                    name,
+                    target.width,
+                    actual,
+                    self.text,
+                    &range,
+                ));
+            }
+            return;
+        }
+        if !actual.sized {
+            return;
+        }
+
+        if actual.width > target.width {
+            self.errors.push(AnalyzerError::implicit_truncation(
+                name,
+                target.width,
+                actual.width,
+                self.text,
+                &range,
+            ));
+        } else if actual.width < target.width {
+            self.errors.push(AnalyzerError::implicit_extension(
+                name,
+                target.width,
+                actual.width,
+                self.text,
+                &range,
+            ));
+        }
+
+        if actual.signed != target.signed {
+            let sign = |x: bool| if x { "signed" } else { "unsigned" };
+            self.errors.push(AnalyzerError::mismatch_sign(
+                name,
+                sign(target.signed),
+                sign(actual.signed),
+                self.text,
+                &range,
+            ));
+        }
+    }
+
+    /// Check arguments of a call to `function` against its input ports
+    fn check_argument_width(&mut self, function: &Symbol, arg: &FunctionCall) {
+        let SymbolKind::Function(ref property) = function.kind else {
+            return;
+        };
+        let Some(ref list) = arg.function_call_opt else {
+            return;
+        };
+        let list = &list.argument_list;
+        let args = std::iter::once(&list.argument_item)
+            .chain(list.argument_list_list.iter().map(|x| &x.argument_item));
+
+        for (port, arg) in property.ports.iter().zip(args) {
+            if port.property().direction != Direction::Input {
+                continue;
+            }
+            let Some(symbol) = symbol_table::get(port.symbol) else {
+                continue;
+            };
+            let target = Evaluator::new().identifier_width(&symbol, &[]);
+            self.check_assignment_width(&port.name().to_string(), target, &arg.expression);
+        }
+    }
+}
+
 impl Handler for CheckExpression<'_> {
     fn set_point(&mut self, p: HandlerPoint) {
         self.point = p;
@@ -55,6 +259,62 @@ fn is_defined_in_package(full_path: &[SymbolId]) -> bool {
 }
 
 impl VerylaGrammarTrait for CheckExpression<'_> {
+    fn case_statement(&mut self, arg: &CaseStatement) -> Result<(), ParolError> {
+        if let HandlerPoint::Before = self.point {
+            let mut arms = Vec::new();
+            let mut has_default = false;
+            for x in &arg.case_statement_list {
+                match x.case_item.case_item_group.as_ref() {
+                    CaseItemGroup::CaseCondition(x) => arms.push(x.case_condition.as_ref()),
+                    CaseItemGroup::Defaul(_) => has_default = true,
+                }
+            }
+            self.check_case_arms(&arg.expression, &arms, has_default);
+        }
+        Ok(())
+    }
+
+    fn case_expression(&mut self, arg: &CaseExpression) -> Result<(), ParolError> {
+        if let HandlerPoint::Before = self.point {
+            let mut arms = vec![arg.case_condition.as_ref()];
+            arms.extend(
+                arg.case_expression_list
+                    .iter()
+                    .map(|x| x.case_condition.as_ref()),
+            );
+            self.check_case_arms(&arg.expression, &arms, true);
+        }
+        Ok(())
+    }
+
+    fn switch_statement(&mut self, arg: &SwitchStatement) -> Result<(), ParolError> {
+        if let HandlerPoint::Before = self.point {
+            let arms: Vec<_> = arg
+                .switch_statement_list
+                .iter()
+                .filter_map(|x| match x.switch_item.switch_item_group.as_ref() {
+                    SwitchItemGroup::SwitchCondition(x) => Some(x.switch_condition.as_ref()),
+                    SwitchItemGroup::Defaul(_) => None,
+                })
+                .collect();
+            self.check_switch_arms(&arms);
+        }
+        Ok(())
+    }
+
+    fn switch_expression(&mut self, arg: &SwitchExpression) -> Result<(), ParolError> {
+        if let HandlerPoint::Before = self.point {
+            let mut arms = vec![arg.switch_condition.as_ref()];
+            arms.extend(
+                arg.switch_expression_list
+                    .iter()
+                    .map(|x| x.switch_condition.as_ref()),
+            );
+            self.check_switch_arms(&arms);
+        }
+        Ok(())
+    }
+
     fn case_condition(&mut self, _arg: &CaseCondition) -> Result<(), ParolError> {
         match self.point {
             HandlerPoint::Before => self.case_condition_depth += 1,
@@ -107,9 +367,11 @@ impl VerylaGrammarTrait for CheckExpression<'_> {
                     SymbolKind::Entity(_)
                     | SymbolKind::ProtoEntity(_)
                     | SymbolKind::Interface(_)
+                    | SymbolKind::ProtoInterface(_)
                     | SymbolKind::Instance(_)
                     | SymbolKind::Block
                     | SymbolKind::Package(_)
+                    | SymbolKind::ProtoPackage(_)
                     | SymbolKind::Modport(_)
                     | SymbolKind::Namespace
                     | SymbolKind::PowerDomain
@@ -152,10 +414,12 @@ impl VerylaGrammarTrait for CheckExpression<'_> {
                 }
             }
 
-            if arg.identifier_factor_opt.is_some() {
+            if let Some(ref call) = arg.identifier_factor_opt {
                 // Must be a function call
                 let expid = arg.expression_identifier.as_ref();
                 if let Ok(rr) = symbol_table::resolve(expid) {
+                    self.check_argument_width(&rr.found, &call.function_call);
+
                     let is_function = match &rr.found.kind {
                         SymbolKind::Function(_)
                         | SymbolKind::SystemVerilog
@@ -199,6 +463,99 @@ impl VerylaGrammarTrait for CheckExpression<'_> {
         Ok(())
     }
 
+    fn let_statement(&mut self, arg: &LetStatement) -> Result<(), ParolError> {
+        if let HandlerPoint::Before = self.point {
+            if let Ok(symbol) = symbol_table::resolve(arg.identifier.as_ref()) {
+                let target = Evaluator::new().identifier_width(&symbol.found, &[]);
+                let name = symbol.found.token.to_string();
+                self.check_assignment_width(&name, target, &arg.expression);
+            }
+        }
+        Ok(())
+    }
+
+    fn identifier_statement(&mut self, arg: &IdentifierStatement) -> Result<(), ParolError> {
+        if let HandlerPoint::Before = self.point {
+            let ident = arg.expression_identifier.as_ref();
+            let Ok(symbol) = symbol_table::resolve(ident) else {
+                return Ok(());
+            };
+            match arg.identifier_statement_group.as_ref() {
+                IdentifierStatementGroup::FunctionCall(x) => {
+                    self.check_argument_width(&symbol.found, &x.function_call);
+                }
+                IdentifierStatementGroup::Assignment(x) => {
+                    // The right-hand side of shift assignment is the amount of shift
+                    let is_shift = match x.assignment.assignment_group.as_ref() {
+                        AssignmentGroup::Equ(_) => false,
+                        AssignmentGroup::AssignmentOperator(x) => {
+                            let operator =
+                                x.assignment_operator.assignment_operator_token.to_string();
+                            operator.contains("<<") || operator.contains(">>")
+                        }
+                    };
+                    if !is_shift {
+                        let target =
+                            Evaluator::new().expression_identifier_width(&symbol.found, ident);
+                        let name = symbol.found.token.to_string();
+                        self.check_assignment_width(&name, target, &x.assignment.expression);
+                    }
+                }
+            }
+        }
+        Ok(())
+    }
+
+    fn let_declaration(&mut self, arg: &LetDeclaration) -> Result<(), ParolError> {
+        if let HandlerPoint::Before = self.point {
+            if let Ok(symbol) = symbol_table::resolve(arg.identifier.as_ref()) {
+                let target = Evaluator::new().identifier_width(&symbol.found, &[]);
+                let name = symbol.found.token.to_string();
+                self.check_assignment_width(&name, target, &arg.expression);
+            }
+        }
+        Ok(())
+    }
+
+    fn assign_declaration(&mut self, arg: &AssignDeclaration) -> Result<(), ParolError> {
+        if let HandlerPoint::Before = self.point {
+            let ident = arg.hierarchical_identifier.as_ref();
+            if let Ok(symbol) = symbol_table::resolve(ident) {
+                // Selects are applied to the last member
+                let selects: Vec<_> = if let Some(x) = ident.hierarchical_identifier_list0.last() {
+                    x.hierarchical_identifier_list0_list
+                        .iter()
+                        .map(|x| x.select.as_ref())
+                        .collect()
+                } else {
+                    ident
+                        .hierarchical_identifier_list
+                        .iter()
+                        .map(|x| x.select.as_ref())
+                        .collect()
+                };
+                let target = Evaluator::new().identifier_width(&symbol.found, &selects);
+                let name = symbol.found.token.to_string();
+                self.check_assignment_width(&name, target, &arg.expression);
+            }
+        }
+        Ok(())
+    }
+
+    fn const_declaration(&mut self, arg: &ConstDeclaration) -> Result<(), ParolError> {
+        if let HandlerPoint::Before = self.point {
+            self.check_evaluation_limit(&arg.expression);
+        }
+        Ok(())
+    }
+
+    fn with_parameter_item(&mut self, arg: &WithParameterItem) -> Result<(), ParolError> {
+        if let HandlerPoint::Before = self.point {
+            self.check_evaluation_limit(&arg.expression);
+        }
+        Ok(())
+    }
+
     fn inst_declaration(&mut self, _arg: &InstDeclaration) -> Result<(), ParolError> {
         match self.point {
             HandlerPoint::Before => self.in_inst_declaration = true,
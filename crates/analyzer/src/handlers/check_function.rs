@@ -1,4 +1,5 @@
 use crate::analyzer_error::AnalyzerError;
+use crate::distribution::{self, Distribution};
 use crate::symbol::SymbolKind;
 use crate::symbol_path::SymbolPath;
 use crate::symbol_table;
@@ -92,6 +93,7 @@ impl VerylaGrammarTrait for CheckFunction<'_> {
             }
 
             if let Ok(symbol) = symbol_table::resolve(arg.expression_identifier.as_ref()) {
+                let distribution = Distribution::from_symbol(&symbol.found);
                 let arity = match symbol.found.kind {
                     SymbolKind::Function(x) => Some(x.ports.len()),
                     SymbolKind::ModportFunctionMember(x) => {
@@ -102,7 +104,7 @@ impl VerylaGrammarTrait for CheckFunction<'_> {
                             unreachable!();
                         }
                     }
-                    _ => None,
+                    _ => distribution.map(|x| x.arity()),
                 };
 
                 let mut args = 0;
@@ -131,6 +133,17 @@ impl VerylaGrammarTrait for CheckFunction<'_> {
                         ));
                     }
                 }
+
+                if let Some(distribution) = distribution {
+                    let function_call = &arg.identifier_factor_opt.as_ref().unwrap().function_call;
+                    if distribution::arguments(function_call).is_none() {
+                        self.errors.push(AnalyzerError::invalid_distribution(
+                            distribution.name(),
+                            self.text,
+                            &arg.expression_identifier.as_ref().into(),
+                        ));
+                    }
+                }
             }
         }
 
@@ -0,0 +1,296 @@
+use crate::analyzer_error::AnalyzerError;
+use crate::evaluator::{single_factor, Evaluated, Evaluator};
+use crate::symbol::{EntityProperty, Symbol, SymbolKind, Type, TypeKind};
+use crate::symbol_table;
+use std::collections::HashMap;
+use veryla_parser::veryla_grammar_trait::*;
+use veryla_parser::veryla_token::TokenRange;
+use veryla_parser::veryla_walker::{Handler, HandlerPoint};
+use veryla_parser::ParolError;
+
+#[derive(Default)]
+pub struct CheckPortConnection<'a> {
+    pub errors: Vec<AnalyzerError>,
+    text: &'a str,
+    point: HandlerPoint,
+}
+
+impl<'a> CheckPortConnection<'a> {
+    pub fn new(text: &'a str) -> Self {
+        Self {
+            text,
+            ..Default::default()
+        }
+    }
+}
+
+impl Handler for CheckPortConnection<'_> {
+    fn set_point(&mut self, p: HandlerPoint) {
+        self.point = p;
+    }
+}
+
+fn entity_property(symbol: &Symbol) -> Option<EntityProperty> {
+    match &symbol.kind {
+        SymbolKind::Entity(x) => Some(x.clone()),
+        SymbolKind::GenericInstance(x) => {
+            let base = symbol_table::get(x.base)?;
+            entity_property(&base)
+        }
+        _ => None,
+    }
+}
+
+fn symbol_type(symbol: &Symbol) -> Option<Type> {
+    match &symbol.kind {
+        SymbolKind::Variable(x) => Some(x.r#type.clone()),
+        SymbolKind::Port(x) => x.r#type.clone(),
+        _ => None,
+    }
+}
+
+/// Get the identifier if the expression refers a whole variable or port
+pub fn whole_identifier(arg: &Expression) -> Option<&ExpressionIdentifier> {
+    let Factor::IdentifierFactor(x) = single_factor(arg)? else {
+        return None;
+    };
+    let x = &x.identifier_factor;
+    let identifier = x.expression_identifier.as_ref();
+    if x.identifier_factor_opt.is_none()
+        && identifier.expression_identifier_opt.is_none()
+        && identifier.expression_identifier_list.is_empty()
+        && identifier.expression_identifier_list0.is_empty()
+    {
+        Some(identifier)
+    } else {
+        None
+    }
+}
+
+fn dimension(evaluator: &mut Evaluator, r#type: &Type) -> Option<Vec<usize>> {
+    let mut ret = Vec::new();
+    for x in &r#type.array {
+        evaluator.context_width.clear();
+        if let Evaluated::Fixed { value, .. } = evaluator.expression(x) {
+            ret.push(value.try_into().ok()?);
+        } else {
+            return None;
+        }
+    }
+    Some(ret)
+}
+
+fn dimension_string(x: &[usize]) -> String {
+    if x.is_empty() {
+        "none".to_string()
+    } else {
+        format!(
+            "[{}]",
+            x.iter()
+                .map(|x| x.to_string())
+                .collect::<Vec<_>>()
+                .join(", ")
+        )
+    }
+}
+
+fn kind_name(kind: &TypeKind) -> &'static str {
+    match kind {
+        TypeKind::Analog => "analog type",
+        _ if kind.is_power() => "power type",
+        _ if kind.is_enable() => "enable type",
+        _ => "logic type",
+    }
+}
+
+impl CheckPortConnection<'_> {
+    fn check_expression(
+        &mut self,
+        evaluator: &mut Evaluator,
+        name: &str,
+        port_type: &Type,
+        arg: &Expression,
+        range: &TokenRange,
+    ) {
+        // Only a variable or port can have array dimensions
+        let is_identifier = matches!(single_factor(arg), Some(Factor::IdentifierFactor(_)));
+        if !port_type.array.is_empty() && !is_identifier {
+            let expected = dimension(evaluator, port_type).map(|x| dimension_string(&x));
+            let expected = expected.unwrap_or(format!("{}D", port_type.array.len()));
+            self.errors.push(AnalyzerError::mismatch_port_dimension(
+                name,
+                &expected,
+                &dimension_string(&[]),
+                self.text,
+                range,
+            ));
+            return;
+        }
+        if !port_type.array.is_empty() {
+            return;
+        }
+
+        let port_width = evaluator.type_width(port_type.clone());
+        let connected_width = Evaluator::new().expression_width(arg);
+        if let (Some(expected), Some(actual)) = (port_width, connected_width) {
+            // Unsized literals follow the width of the port
+            if actual.sized && expected != actual.width {
+                self.errors.push(AnalyzerError::mismatch_port_width(
+                    name,
+                    expected,
+                    actual.width,
+                    self.text,
+                    range,
+                ));
+            }
+        }
+    }
+}
+
+impl VerylaGrammarTrait for CheckPortConnection<'_> {
+    fn inst_declaration(&mut self, arg: &InstDeclaration) -> Result<(), ParolError> {
+        if let HandlerPoint::Before = self.point {
+            let Ok(symbol) = symbol_table::resolve(arg.scoped_identifier.as_ref()) else {
+                return Ok(());
+            };
+            let Some(property) = entity_property(&symbol.found) else {
+                return Ok(());
+            };
+            let Some(ref x) = arg.inst_declaration_opt1 else {
+                return Ok(());
+            };
+            let Some(ref x) = x.inst_declaration_opt2 else {
+                return Ok(());
+            };
+
+            // Parameter values given at the instance are evaluated at the caller side
+            let mut values = HashMap::new();
+            if let Some(ref x) = arg.inst_declaration_opt0 {
+                if let Some(ref x) = x.inst_parameter.inst_parameter_opt {
+                    let items: Vec<InstParameterItem> = x.inst_parameter_list.as_ref().into();
+                    for item in items {
+                        let value = Evaluator::new().inst_parameter_item(&item);
+                        values.insert(item.identifier.identifier_token.token.text, value);
+                    }
+                }
+            }
+
+            let mut evaluator = Evaluator::new();
+            evaluator.entity_parameters(&property, &values);
+
+            let items: Vec<InstPortItem> = x.inst_port_list.as_ref().into();
+            for item in items {
+                let name = item.identifier.identifier_token.token.text;
+                let Some(port) = property.ports.iter().find(|x| x.name() == name) else {
+                    continue;
+                };
+                let Some(port_type) = port.property().r#type else {
+                    continue;
+                };
+
+                let (connected, evaluated) = if let Some(ref x) = item.inst_port_item_opt {
+                    let connected = whole_identifier(&x.expression)
+                        .and_then(|x| symbol_table::resolve(x).ok())
+                        .and_then(|x| symbol_type(&x.found));
+                    (connected, Evaluator::new().expression(&x.expression))
+                } else {
+                    match symbol_table::resolve(item.identifier.as_ref()) {
+                        Ok(x) => (symbol_type(&x.found), x.found.evaluate()),
+                        Err(_) => (None, Evaluated::Unknown),
+                    }
+                };
+
+                let name = name.to_string();
+                let range = (&item).into();
+
+                // Connections to power and enable ports are checked by CheckVarRef
+                if port_type.kind.is_power() || port_type.kind.is_enable() {
+                    continue;
+                }
+
+                let connected_kind = connected.as_ref().map(|x| x.kind.clone());
+                let is_power = connected_kind.as_ref().map(|x| x.is_power());
+                let is_power = is_power.unwrap_or(evaluated.is_power());
+                let is_enable = connected_kind.as_ref().map(|x| x.is_enable());
+                let is_enable = is_enable.unwrap_or(evaluated.is_enable());
+                if is_power || is_enable {
+                    let actual = if is_power {
+                        "power type"
+                    } else {
+                        "enable type"
+                    };
+                    self.errors.push(AnalyzerError::mismatch_type(
+                        &name,
+                        kind_name(&port_type.kind),
+                        actual,
+                        self.text,
+                        &range,
+                    ));
+                    continue;
+                }
+
+                let is_signal =
+                    |x: &TypeKind| matches!(x, TypeKind::Analog | TypeKind::Logic | TypeKind::Bit);
+
+                // Selects, concatenations and operators are checked by the width of the expression
+                let Some(connected) = connected else {
+                    if let Some(ref x) = item.inst_port_item_opt {
+                        if is_signal(&port_type.kind) && port_type.kind != TypeKind::Analog {
+                            self.check_expression(
+                                &mut evaluator,
+                                &name,
+                                &port_type,
+                                &x.expression,
+                                &range,
+                            );
+                        }
+                    }
+                    continue;
+                };
+
+                if !is_signal(&port_type.kind) || !is_signal(&connected.kind) {
+                    continue;
+                }
+
+                if (port_type.kind == TypeKind::Analog) != (connected.kind == TypeKind::Analog) {
+                    self.errors.push(AnalyzerError::mismatch_type(
+                        &name,
+                        kind_name(&port_type.kind),
+                        kind_name(&connected.kind),
+                        self.text,
+                        &range,
+                    ));
+                    continue;
+                }
+
+                let port_width = evaluator.type_width(port_type.clone());
+                let connected_width = Evaluator::new().type_width(connected.clone());
+                if let (Some(expected), Some(actual)) = (port_width, connected_width) {
+                    if expected != actual {
+                        self.errors.push(AnalyzerError::mismatch_port_width(
+                            &name, expected, actual, self.text, &range,
+                        ));
+                        continue;
+                    }
+                }
+
+                let port_dimension = dimension(&mut evaluator, &port_type);
+                let connected_dimension = dimension(&mut Evaluator::new(), &connected);
+                let dimension_mismatch = match (&port_dimension, &connected_dimension) {
+                    (Some(x), Some(y)) => x != y,
+                    _ => port_type.array.len() != connected.array.len(),
+                };
+                if dimension_mismatch {
+                    let expected = port_dimension.map(|x| dimension_string(&x));
+                    let expected = expected.unwrap_or(format!("{}D", port_type.array.len()));
+                    let actual = connected_dimension.map(|x| dimension_string(&x));
+                    let actual = actual.unwrap_or(format!("{}D", connected.array.len()));
+                    self.errors.push(AnalyzerError::mismatch_port_dimension(
+                        &name, &expected, &actual, self.text, &range,
+                    ));
+                }
+            }
+        }
+        Ok(())
+    }
+}
@@ -1,24 +1,123 @@
 use crate::analyzer_error::AnalyzerError;
+use crate::attribute::Attribute as Attr;
+use crate::attribute_table;
+use crate::handlers::check_port_connection::whole_identifier;
+use crate::power_domain_table;
 use crate::r#unsafe::Unsafe;
-use crate::symbol::{PowerDomain, SymbolId, SymbolKind};
+use crate::symbol::{Direction, Port, PowerDomain, Symbol, SymbolId, SymbolKind};
 use crate::symbol_table;
 use crate::unsafe_table;
-use std::collections::HashMap;
+use std::collections::{HashMap, HashSet};
 use veryla_parser::resource_table::StrId;
 use veryla_parser::veryla_grammar_trait::*;
 use veryla_parser::veryla_token::{Token, TokenRange};
 use veryla_parser::veryla_walker::{Handler, HandlerPoint};
 use veryla_parser::ParolError;
 
+/// Symbol referred in a statement, and its explicit power domain
+#[derive(Clone, Copy, Debug)]
+struct Operand {
+    id: SymbolId,
+    power_domain: PowerDomain,
+}
+
+/// Operands of a statement which should share a power domain
+#[derive(Clone, Debug)]
+struct ExprCheck {
+    sequence: Option<(PowerDomain, TokenRange)>,
+    operands: Vec<(Operand, TokenRange)>,
+    /// Symbol driven by the other operands
+    target: Option<SymbolId>,
+    token: Token,
+    declaration: Option<TokenRange>,
+    sequential: bool,
+    synchronized: bool,
+    in_synchronizer: bool,
+    /// Target is read by its own assignment, e.g. `r[1] = r[0]`
+    feedback: bool,
+}
+
+#[derive(Clone, Debug)]
+enum InstTarget {
+    Entity {
+        ports: Vec<Port>,
+        synchronizer: bool,
+    },
+    SystemVerilog,
+}
+
+/// Connections of an instance, which refer `ExprCheck` of each port
+#[derive(Clone, Debug)]
+struct InstCheck {
+    target: InstTarget,
+    connections: HashMap<StrId, (usize, TokenRange)>,
+    token: Token,
+    declaration: Option<TokenRange>,
+}
+
+#[derive(Clone, Debug)]
+enum Check {
+    Expr(ExprCheck),
+    Inst(InstCheck),
+}
+
 #[derive(Default)]
 pub struct CheckPowerDomain<'a> {
     pub errors: Vec<AnalyzerError>,
     text: &'a str,
     point: HandlerPoint,
-    expr_power_domains: Vec<(PowerDomain, TokenRange)>,
-    inst_power_domains: HashMap<StrId, (PowerDomain, TokenRange)>,
+    expr_power_domains: Vec<(Operand, TokenRange)>,
+    inst_power_domains: HashMap<StrId, (usize, TokenRange)>,
     sequence_power_domain: Option<(PowerDomain, TokenRange)>,
     default_power: Option<SymbolId>,
+    declaration: Option<TokenRange>,
+    function: Option<SymbolId>,
+    in_sequence: bool,
+    in_synchronizer: bool,
+    plain_copy: bool,
+    checks: Vec<Check>,
+    /// Index of the first check in the current entity
+    entity_checks: usize,
+    /// Index of the first operand of the right-hand side in `expr_power_domains`
+    source_begin: usize,
+    unannotated: HashSet<SymbolId>,
+    inferred: HashMap<SymbolId, PowerDomain>,
+}
+
+/// Number of stages of entity marked by `#[synchronizer]`, or implementing such proto
+fn synchronizer_stages(symbol: &Symbol) -> Option<usize> {
+    let stages = attribute_table::get(&symbol.token)
+        .iter()
+        .find_map(|x| match x {
+            Attr::Synchronizer(x) => Some(*x),
+            _ => None,
+        });
+    if stages.is_some() {
+        return stages;
+    }
+
+    if let SymbolKind::Entity(ref x) = symbol.kind {
+        if let Some(ref proto) = x.proto {
+            if let Ok(proto) = symbol_table::resolve((proto, &symbol.namespace)) {
+                return synchronizer_stages(&proto.found);
+            }
+        }
+    }
+    None
+}
+
+fn is_synchronizer(symbol: &Symbol) -> bool {
+    synchronizer_stages(symbol).is_some()
+}
+
+/// Data input of synchronizer which may belong to a foreign power domain
+fn is_synchronized_input(port: &Port) -> bool {
+    let property = port.property();
+    let is_data = property
+        .r#type
+        .map(|x| !x.kind.is_power() && !x.kind.is_enable())
+        .unwrap_or(true);
+    property.direction == Direction::Input && is_data
 }
 
 impl<'a> CheckPowerDomain<'a> {
@@ -29,36 +128,434 @@ impl<'a> CheckPowerDomain<'a> {
         }
     }
 
-    fn push_expr_power_domain(&mut self, kind: &SymbolKind, range: TokenRange) {
-        match kind {
+    fn push_expr_power_domain(&mut self, symbol: &Symbol, range: TokenRange) {
+        match &symbol.kind {
             SymbolKind::Variable(x) => {
-                self.expr_power_domains.push((x.power_domain, range));
+                let operand = Operand {
+                    id: symbol.id,
+                    power_domain: x.power_domain,
+                };
+                self.expr_power_domains.push((operand, range));
             }
             SymbolKind::Port(x) => {
-                self.expr_power_domains.push((x.power_domain, range));
+                let operand = Operand {
+                    id: symbol.id,
+                    power_domain: x.power_domain,
+                };
+                self.expr_power_domains.push((operand, range));
+            }
+            SymbolKind::Function(_) => {
+                let operand = Operand {
+                    id: symbol.id,
+                    power_domain: PowerDomain::None,
+                };
+                self.expr_power_domains.push((operand, range));
             }
             _ => (),
         }
     }
 
-    fn check_expr_power_domains(&mut self, token: &Token) -> PowerDomain {
-        let mut prev: Option<(PowerDomain, TokenRange)> = self.sequence_power_domain;
-        for curr in &self.expr_power_domains {
+    /// Record operands of the current statement, and return the index of the check
+    fn push_expr_check(&mut self, target: Option<SymbolId>, token: &Token) -> usize {
+        // Target is pushed as the last operand
+        let sources = self
+            .expr_power_domains
+            .get(self.source_begin..self.expr_power_domains.len().saturating_sub(1))
+            .unwrap_or_default();
+        let feedback = target.is_some_and(|x| sources.iter().any(|(y, _)| y.id == x));
+        self.source_begin = 0;
+        self.checks.push(Check::Expr(ExprCheck {
+            sequence: self.sequence_power_domain,
+            operands: std::mem::take(&mut self.expr_power_domains),
+            target,
+            token: *token,
+            declaration: self.declaration,
+            sequential: self.in_sequence,
+            synchronized: self.in_sequence && self.plain_copy,
+            in_synchronizer: self.in_synchronizer,
+            feedback,
+        }));
+        self.checks.len() - 1
+    }
+
+    fn insert_unannotated(&mut self, identifier: &Identifier) {
+        if let Ok(symbol) = symbol_table::resolve(identifier) {
+            self.unannotated.insert(symbol.found.id);
+        }
+    }
+
+    /// Power domain of the operand, and whether it is inferred
+    fn resolve_operand(&self, operand: &Operand) -> (PowerDomain, bool) {
+        match self.inferred.get(&operand.id) {
+            Some(x) => (*x, true),
+            None => (operand.power_domain, false),
+        }
+    }
+
+    fn domain_string(domain: &PowerDomain, inferred: bool) -> String {
+        if inferred {
+            format!("{domain} (inferred)")
+        } else {
+            domain.to_string()
+        }
+    }
+
+    /// Infer power domains from sequence blocks and drivers until no more change
+    fn infer_power_domains(&mut self) {
+        loop {
+            let mut changed = false;
+            for check in &self.checks {
+                let Check::Expr(check) = check else {
+                    continue;
+                };
+                let Some(id) = check.target else {
+                    continue;
+                };
+                if !self.unannotated.contains(&id) || self.inferred.contains_key(&id) {
+                    continue;
+                }
+
+                let sequence = check
+                    .sequence
+                    .map(|(x, _)| x)
+                    .filter(|x| *x != PowerDomain::None);
+                let driver = check
+                    .operands
+                    .iter()
+                    .filter(|(x, _)| x.id != id)
+                    .filter(|(x, _)| {
+                        !self.unannotated.contains(&x.id) || self.inferred.contains_key(&x.id)
+                    })
+                    .map(|(x, _)| self.resolve_operand(x).0)
+                    .find(|x| *x != PowerDomain::None);
+                if let Some(domain) = sequence.or(driver) {
+                    self.inferred.insert(id, domain);
+                    changed = true;
+                }
+            }
+            if !changed {
+                break;
+            }
+        }
+    }
+
+    /// Check operands of the statement, and return its power domain and whether it is inferred
+    fn check_expr_power_domains(&mut self, check: &ExprCheck) -> (PowerDomain, bool) {
+        let mut prev: Option<(PowerDomain, bool, TokenRange)> =
+            check.sequence.map(|(x, range)| (x, false, range));
+        for (operand, range) in &check.operands {
+            let (domain, inferred) = self.resolve_operand(operand);
+            let curr = (domain, inferred, *range);
             if let Some(prev) = prev {
-                if !curr.0.compatible(&prev.0) && !unsafe_table::contains(token, Unsafe::Pdc) {
-                    self.errors.push(AnalyzerError::mismatch_power_domain(
-                        &curr.0.to_string(),
-                        &prev.0.to_string(),
+                // Register which directly captures the foreign signal is the first synchronizer stage
+                let accepted =
+                    curr.0.compatible(&prev.0) || (check.synchronized && check.in_synchronizer);
+                if !accepted {
+                    if !unsafe_table::contains(&check.token, Unsafe::Pdc) {
+                        self.errors.push(AnalyzerError::mismatch_power_domain(
+                            &Self::domain_string(&curr.0, curr.1),
+                            &Self::domain_string(&prev.0, prev.1),
+                            check.declaration.as_ref(),
+                            self.text,
+                            &curr.2,
+                            &prev.2,
+                        ));
+                    } else if !check.synchronized {
+                        self.errors.push(AnalyzerError::unsynchronized_crossing(
+                            &Self::domain_string(&curr.0, curr.1),
+                            &Self::domain_string(&prev.0, prev.1),
+                            self.text,
+                            &curr.2,
+                            &prev.2,
+                        ));
+                    }
+                }
+            }
+
+            prev = Some(curr);
+        }
+        prev.map(|(x, inferred, _)| (x, inferred))
+            .unwrap_or((PowerDomain::None, false))
+    }
+
+    fn check_inst_power_domains(&mut self, check: &InstCheck, domains: &[(PowerDomain, bool)]) {
+        let connection = |x: &(usize, TokenRange)| (domains[x.0].0, domains[x.0].1, x.1);
+        match &check.target {
+            InstTarget::Entity {
+                ports,
+                synchronizer,
+            } => {
+                let mut connection_table =
+                    HashMap::<PowerDomain, (PowerDomain, bool, TokenRange)>::new();
+                for x in ports {
+                    if *synchronizer && is_synchronized_input(x) {
+                        continue;
+                    }
+                    if let Some(connected) = check.connections.get(&x.name()).map(connection) {
+                        let port_domain = x.property().power_domain;
+                        if let Some(assigned) = connection_table.get(&port_domain) {
+                            if !assigned.0.compatible(&connected.0)
+                                && !unsafe_table::contains(&check.token, Unsafe::Pdc)
+                            {
+                                self.errors.push(AnalyzerError::mismatch_power_domain(
+                                    &Self::domain_string(&connected.0, connected.1),
+                                    &Self::domain_string(&assigned.0, assigned.1),
+                                    check.declaration.as_ref(),
+                                    self.text,
+                                    &connected.2,
+                                    &assigned.2,
+                                ));
+                            }
+                        } else {
+                            connection_table.insert(port_domain, connected);
+                        }
+                    }
+                }
+            }
+            InstTarget::SystemVerilog => {
+                let mut prev: Option<(PowerDomain, bool, TokenRange)> = None;
+                for curr in check.connections.values().map(connection) {
+                    if let Some(prev) = prev {
+                        if !prev.0.compatible(&curr.0)
+                            && !unsafe_table::contains(&check.token, Unsafe::Pdc)
+                        {
+                            self.errors.push(AnalyzerError::mismatch_power_domain(
+                                &Self::domain_string(&curr.0, curr.1),
+                                &Self::domain_string(&prev.0, prev.1),
+                                check.declaration.as_ref(),
+                                self.text,
+                                &curr.2,
+                                &prev.2,
+                            ));
+                        }
+                    }
+                    prev = Some(curr);
+                }
+            }
+        }
+    }
+
+    /// Leaf signals of the combinational fan-in cone of `roots`, and whether each is a register
+    fn fan_in_sources(&self, roots: &[Operand]) -> Vec<(Operand, bool)> {
+        let mut drivers: HashMap<SymbolId, Vec<&ExprCheck>> = HashMap::new();
+        for check in &self.checks {
+            if let Check::Expr(x) = check {
+                if let Some(target) = x.target {
+                    drivers.entry(target).or_default().push(x);
+                }
+            }
+        }
+
+        let mut ret = Vec::new();
+        let mut visited = HashSet::new();
+        let mut stack: Vec<Operand> = roots.iter().rev().copied().collect();
+        while let Some(operand) = stack.pop() {
+            if !visited.insert(operand.id) {
+                continue;
+            }
+
+            let drivers = drivers.get(&operand.id).cloned().unwrap_or_default();
+            let is_register = drivers.iter().any(|x| x.sequential);
+            if is_register || drivers.is_empty() {
+                ret.push((operand, is_register));
+                continue;
+            }
+            for driver in drivers.iter().rev() {
+                for (x, _) in driver.operands.iter().rev() {
+                    if x.id != operand.id {
+                        stack.push(*x);
+                    }
+                }
+            }
+        }
+        ret
+    }
+
+    /// Warn crossing points whose fan-in cone merges several registers or power domains
+    fn check_glitch_prone_crossings(&mut self) {
+        let mut crossings: Vec<(Vec<Operand>, TokenRange)> = Vec::new();
+        for check in &self.checks {
+            match check {
+                // First synchronizer stage, or register capturing foreign signal in unsafe (pdc)
+                Check::Expr(x) => {
+                    let capture = x.synchronized
+                        && (x.in_synchronizer || unsafe_table::contains(&x.token, Unsafe::Pdc));
+                    let Some((domain, _)) = x.sequence.filter(|_| capture) else {
+                        continue;
+                    };
+                    let sources: Vec<_> = x
+                        .operands
+                        .iter()
+                        .filter(|(y, _)| Some(y.id) != x.target)
+                        .collect();
+                    let crossing = sources
+                        .iter()
+                        .any(|(y, _)| !self.resolve_operand(y).0.compatible(&domain));
+                    if crossing {
+                        let roots = sources.iter().map(|(y, _)| *y).collect();
+                        crossings.push((roots, sources[0].1));
+                    }
+                }
+                // Data input of synchronizer instance
+                Check::Inst(x) => {
+                    if let InstTarget::Entity {
+                        ports,
+                        synchronizer: true,
+                    } = &x.target
+                    {
+                        for port in ports.iter().filter(|y| is_synchronized_input(y)) {
+                            if let Some((index, range)) = x.connections.get(&port.name()) {
+                                if let Check::Expr(y) = &self.checks[*index] {
+                                    let roots = y.operands.iter().map(|(z, _)| *z).collect();
+                                    crossings.push((roots, *range));
+                                }
+                            }
+                        }
+                    }
+                }
+            }
+        }
+
+        for (roots, range) in crossings {
+            let sources = self.fan_in_sources(&roots);
+            let registers = sources.iter().filter(|(_, x)| *x).count();
+            let mut domains = Vec::new();
+            for (x, _) in &sources {
+                let domain = self.resolve_operand(x).0;
+                if domain != PowerDomain::None && !domains.contains(&domain) {
+                    domains.push(domain);
+                }
+            }
+
+            if registers > 1 || domains.len() > 1 {
+                let signals: Vec<_> = sources
+                    .iter()
+                    .filter_map(|(x, _)| symbol_table::get(x.id))
+                    .map(|x| x.token.to_string())
+                    .collect();
+                self.errors.push(AnalyzerError::glitch_prone_crossing(
+                    &signals.join(", "),
+                    self.text,
+                    &range,
+                ));
+            }
+        }
+    }
+
+    /// Check all recorded statements after inferring power domains of the whole file
+    /// Check that every output of synchronizer `entity` is driven through `stages` registers
+    /// in its power domain from the synchronized inputs.
+    /// A register which feeds itself, e.g. a shift register in a vector,
+    /// adds a stage for each such assignment.
+    fn check_synchronizer_stages(&mut self, entity: &Symbol, stages: usize) {
+        let SymbolKind::Entity(ref property) = entity.kind else {
+            return;
+        };
+        let checks: Vec<_> = self.checks[self.entity_checks..]
+            .iter()
+            .filter_map(|x| match x {
+                Check::Expr(x) if x.target.is_some() => Some(x),
+                _ => None,
+            })
+            .collect();
+
+        let mut errors = Vec::new();
+        for port in &property.ports {
+            let port_property = port.property();
+            let is_data = port_property
+                .r#type
+                .as_ref()
+                .map(|x| !x.kind.is_power() && !x.kind.is_enable())
+                .unwrap_or(true);
+            if port_property.direction != Direction::Output || !is_data {
+                continue;
+            }
+            let domain = port_property.power_domain;
+            let is_register = |x: &ExprCheck| {
+                x.sequential
+                    && x.sequence
+                        .as_ref()
+                        .is_some_and(|(y, _)| y.compatible(&domain))
+            };
+
+            let mut shifts: HashMap<SymbolId, usize> = HashMap::new();
+            for x in &checks {
+                if is_register(x) && x.feedback {
+                    *shifts.entry(x.target.unwrap()).or_default() += 1;
+                }
+            }
+
+            // Minimum number of registers from the synchronized inputs
+            let mut depth: HashMap<SymbolId, usize> = property
+                .ports
+                .iter()
+                .filter(|x| is_synchronized_input(x))
+                .map(|x| (x.symbol, 0))
+                .collect();
+            loop {
+                let mut changed = false;
+                for x in &checks {
+                    let target = x.target.unwrap();
+                    let base = x
+                        .operands
+                        .iter()
+                        .filter(|(y, _)| y.id != target)
+                        .filter_map(|(y, _)| depth.get(&y.id))
+                        .min();
+                    let Some(base) = base else {
+                        continue;
+                    };
+                    let value = base
+                        + usize::from(is_register(x))
+                        + shifts.get(&target).copied().unwrap_or(0);
+                    if depth.get(&target).is_none_or(|y| value < *y) {
+                        depth.insert(target, value);
+                        changed = true;
+                    }
+                }
+                if !changed {
+                    break;
+                }
+            }
+
+            if let Some(actual) = depth.get(&port.symbol) {
+                if *actual < stages {
+                    errors.push(AnalyzerError::insufficient_synchronizer_stages(
+                        &port.name().to_string(),
+                        stages,
+                        *actual,
                         self.text,
-                        &curr.1,
-                        &prev.1,
+                        &port.token.token.into(),
                     ));
                 }
             }
+        }
+        self.errors.append(&mut errors);
+    }
 
-            prev = Some(*curr);
+    fn check_power_domains(&mut self) {
+        self.infer_power_domains();
+        for (id, domain) in &self.inferred {
+            power_domain_table::insert(*id, *domain);
         }
-        prev.map(|(x, _)| x).unwrap_or(PowerDomain::None)
+
+        let checks = std::mem::take(&mut self.checks);
+        let mut domains = Vec::new();
+        for check in &checks {
+            match check {
+                Check::Expr(x) => {
+                    let domain = self.check_expr_power_domains(x);
+                    domains.push(domain);
+                }
+                Check::Inst(x) => {
+                    self.check_inst_power_domains(x, &domains);
+                    domains.push((PowerDomain::None, false));
+                }
+            }
+        }
+        self.checks = checks;
+
+        self.check_glitch_prone_crossings();
     }
 }
 
@@ -72,7 +569,7 @@ impl VerylaGrammarTrait for CheckPowerDomain<'_> {
     fn scoped_identifier(&mut self, arg: &ScopedIdentifier) -> Result<(), ParolError> {
         if let HandlerPoint::Before = self.point {
             if let Ok(symbol) = symbol_table::resolve(arg) {
-                self.push_expr_power_domain(&symbol.found.kind, arg.into());
+                self.push_expr_power_domain(&symbol.found, arg.into());
             }
         }
         Ok(())
@@ -80,12 +577,19 @@ impl VerylaGrammarTrait for CheckPowerDomain<'_> {
 
     fn let_statement(&mut self, arg: &LetStatement) -> Result<(), ParolError> {
         match self.point {
-            HandlerPoint::Before => self.expr_power_domains.clear(),
+            HandlerPoint::Before => {
+                if arg.let_statement_opt.is_none() {
+                    self.insert_unannotated(&arg.identifier);
+                }
+                self.expr_power_domains.clear();
+            }
             HandlerPoint::After => {
+                let mut target = None;
                 if let Ok(symbol) = symbol_table::resolve(arg.identifier.as_ref()) {
-                    self.push_expr_power_domain(&symbol.found.kind, arg.identifier.as_ref().into());
+                    self.push_expr_power_domain(&symbol.found, arg.identifier.as_ref().into());
+                    target = Some(symbol.found.id);
                 }
-                self.check_expr_power_domains(&arg.semicolon.semicolon_token.token);
+                self.push_expr_check(target, &arg.semicolon.semicolon_token.token);
             }
         }
         Ok(())
@@ -93,31 +597,65 @@ impl VerylaGrammarTrait for CheckPowerDomain<'_> {
 
     fn identifier_statement(&mut self, arg: &IdentifierStatement) -> Result<(), ParolError> {
         match self.point {
-            HandlerPoint::Before => self.expr_power_domains.clear(),
+            HandlerPoint::Before => {
+                self.expr_power_domains.clear();
+                self.plain_copy = match arg.identifier_statement_group.as_ref() {
+                    IdentifierStatementGroup::Assignment(x) => {
+                        whole_identifier(&x.assignment.expression).is_some()
+                    }
+                    IdentifierStatementGroup::FunctionCall(_) => false,
+                };
+            }
             HandlerPoint::After => {
                 // power domain is assigned to base identifier
                 let ident = arg.expression_identifier.scoped_identifier.as_ref();
+                let mut target = None;
                 if let Ok(symbol) = symbol_table::resolve(ident) {
                     self.push_expr_power_domain(
-                        &symbol.found.kind,
+                        &symbol.found,
                         arg.expression_identifier.as_ref().into(),
                     );
+                    if let IdentifierStatementGroup::Assignment(_) =
+                        arg.identifier_statement_group.as_ref()
+                    {
+                        target = Some(symbol.found.id);
+                    }
                 }
 
-                self.check_expr_power_domains(&arg.semicolon.semicolon_token.token);
+                self.push_expr_check(target, &arg.semicolon.semicolon_token.token);
+                self.plain_copy = false;
             }
         }
         Ok(())
     }
 
+    fn assignment(&mut self, _arg: &Assignment) -> Result<(), ParolError> {
+        if let HandlerPoint::Before = self.point {
+            self.source_begin = self.expr_power_domains.len();
+        }
+        Ok(())
+    }
+
     fn let_declaration(&mut self, arg: &LetDeclaration) -> Result<(), ParolError> {
         match self.point {
-            HandlerPoint::Before => self.expr_power_domains.clear(),
+            HandlerPoint::Before => {
+                if arg.let_declaration_opt.is_none() {
+                    self.insert_unannotated(&arg.identifier);
+                }
+                self.expr_power_domains.clear();
+                self.declaration = Some(TokenRange {
+                    beg: arg.r#let.let_token.token,
+                    end: arg.semicolon.semicolon_token.token,
+                });
+            }
             HandlerPoint::After => {
+                let mut target = None;
                 if let Ok(symbol) = symbol_table::resolve(arg.identifier.as_ref()) {
-                    self.push_expr_power_domain(&symbol.found.kind, arg.identifier.as_ref().into());
+                    self.push_expr_power_domain(&symbol.found, arg.identifier.as_ref().into());
+                    target = Some(symbol.found.id);
                 }
-                self.check_expr_power_domains(&arg.semicolon.semicolon_token.token);
+                self.push_expr_check(target, &arg.semicolon.semicolon_token.token);
+                self.declaration = None;
             }
         }
         Ok(())
@@ -126,6 +664,8 @@ impl VerylaGrammarTrait for CheckPowerDomain<'_> {
     fn sequence_declaration(&mut self, arg: &SequenceDeclaration) -> Result<(), ParolError> {
         match self.point {
             HandlerPoint::Before => {
+                self.in_sequence = true;
+                self.declaration = Some(arg.into());
                 let range: TokenRange = arg.sequence.sequence_token.token.into();
                 if let Some(ref x) = arg.sequence_declaration_opt {
                     // power domain is assigned to base identifier
@@ -160,24 +700,50 @@ impl VerylaGrammarTrait for CheckPowerDomain<'_> {
                     }
                 }
             }
-            HandlerPoint::After => self.sequence_power_domain = None,
+            HandlerPoint::After => {
+                self.sequence_power_domain = None;
+                self.in_sequence = false;
+                self.declaration = None;
+            }
+        }
+        Ok(())
+    }
+
+    fn always_comb_declaration(&mut self, arg: &AlwaysCombDeclaration) -> Result<(), ParolError> {
+        match self.point {
+            HandlerPoint::Before => {
+                self.declaration = Some(TokenRange {
+                    beg: arg.always_comb.always_comb_token.token,
+                    end: arg.statement_block.r_brace.r_brace_token.token,
+                });
+            }
+            HandlerPoint::After => self.declaration = None,
         }
         Ok(())
     }
 
     fn assign_declaration(&mut self, arg: &AssignDeclaration) -> Result<(), ParolError> {
         match self.point {
-            HandlerPoint::Before => self.expr_power_domains.clear(),
+            HandlerPoint::Before => {
+                self.expr_power_domains.clear();
+                self.declaration = Some(TokenRange {
+                    beg: arg.assign.assign_token.token,
+                    end: arg.semicolon.semicolon_token.token,
+                });
+            }
             HandlerPoint::After => {
                 // power domain is assigned to base identifier
                 let ident = arg.hierarchical_identifier.identifier.as_ref();
+                let mut target = None;
                 if let Ok(symbol) = symbol_table::resolve(ident) {
                     self.push_expr_power_domain(
-                        &symbol.found.kind,
+                        &symbol.found,
                         arg.hierarchical_identifier.as_ref().into(),
                     );
+                    target = Some(symbol.found.id);
                 }
-                self.check_expr_power_domains(&arg.semicolon.semicolon_token.token);
+                self.push_expr_check(target, &arg.semicolon.semicolon_token.token);
+                self.declaration = None;
             }
         }
         Ok(())
@@ -189,16 +755,13 @@ impl VerylaGrammarTrait for CheckPowerDomain<'_> {
             HandlerPoint::After => {
                 if arg.inst_port_item_opt.is_none() {
                     if let Ok(symbol) = symbol_table::resolve(arg.identifier.as_ref()) {
-                        self.push_expr_power_domain(
-                            &symbol.found.kind,
-                            arg.identifier.as_ref().into(),
-                        );
+                        self.push_expr_power_domain(&symbol.found, arg.identifier.as_ref().into());
                     }
                 }
-                let domain = self.check_expr_power_domains(&arg.identifier.identifier_token.token);
+                let index = self.push_expr_check(None, &arg.identifier.identifier_token.token);
                 let range: TokenRange = arg.identifier.as_ref().into();
                 self.inst_power_domains
-                    .insert(arg.identifier.identifier_token.token.text, (domain, range));
+                    .insert(arg.identifier.identifier_token.token.text, (index, range));
             }
         }
         Ok(())
@@ -206,67 +769,92 @@ impl VerylaGrammarTrait for CheckPowerDomain<'_> {
 
     fn inst_declaration(&mut self, arg: &InstDeclaration) -> Result<(), ParolError> {
         match self.point {
-            HandlerPoint::Before => self.inst_power_domains.clear(),
+            HandlerPoint::Before => {
+                self.inst_power_domains.clear();
+                self.declaration = Some(TokenRange {
+                    beg: arg.inst.inst_token.token,
+                    end: arg.semicolon.semicolon_token.token,
+                });
+            }
             HandlerPoint::After => {
-                let token = &arg.semicolon.semicolon_token.token;
                 if let Ok(symbol) = symbol_table::resolve(arg.scoped_identifier.as_ref()) {
-                    match &symbol.found.kind {
-                        SymbolKind::Entity(x) => {
-                            let mut connection_table =
-                                HashMap::<PowerDomain, (PowerDomain, TokenRange)>::new();
-                            for x in &x.ports {
-                                if let Some(connected) = self.inst_power_domains.get(&x.name()) {
-                                    let port_domain = x.property().power_domain;
-                                    if let Some(assigned) = connection_table.get(&port_domain) {
-                                        if !assigned.0.compatible(&connected.0)
-                                            && !unsafe_table::contains(token, Unsafe::Pdc)
-                                        {
-                                            self.errors.push(AnalyzerError::mismatch_power_domain(
-                                                &connected.0.to_string(),
-                                                &assigned.0.to_string(),
-                                                self.text,
-                                                &connected.1,
-                                                &assigned.1,
-                                            ));
-                                        }
-                                    } else {
-                                        connection_table.insert(port_domain, *connected);
-                                    }
-                                }
-                            }
-                        }
-                        SymbolKind::SystemVerilog => {
-                            let mut prev: Option<(PowerDomain, TokenRange)> = None;
-                            for curr in self.inst_power_domains.values() {
-                                if let Some(prev) = prev {
-                                    if !prev.0.compatible(&curr.0)
-                                        && !unsafe_table::contains(token, Unsafe::Pdc)
-                                    {
-                                        self.errors.push(AnalyzerError::mismatch_power_domain(
-                                            &curr.0.to_string(),
-                                            &prev.0.to_string(),
-                                            self.text,
-                                            &curr.1,
-                                            &prev.1,
-                                        ));
-                                    }
-                                }
-                                prev = Some(*curr);
-                            }
-                        }
-                        _ => (),
+                    let target = match &symbol.found.kind {
+                        SymbolKind::Entity(x) => Some(InstTarget::Entity {
+                            ports: x.ports.clone(),
+                            synchronizer: is_synchronizer(&symbol.found),
+                        }),
+                        SymbolKind::SystemVerilog => Some(InstTarget::SystemVerilog),
+                        _ => None,
+                    };
+                    if let Some(target) = target {
+                        self.checks.push(Check::Inst(InstCheck {
+                            target,
+                            connections: std::mem::take(&mut self.inst_power_domains),
+                            token: arg.semicolon.semicolon_token.token,
+                            declaration: self.declaration,
+                        }));
                     }
                 }
+                self.declaration = None;
             }
         }
         Ok(())
     }
 
-    fn entity_declaration(&mut self, arg: &EntityDeclaration) -> Result<(), ParolError> {
+    fn var_declaration(&mut self, arg: &VarDeclaration) -> Result<(), ParolError> {
         if let HandlerPoint::Before = self.point {
-            let symbol = symbol_table::resolve(arg.identifier.as_ref()).unwrap();
-            if let SymbolKind::Entity(ref x) = symbol.found.kind {
-                self.default_power = x.default_power;
+            if arg.var_declaration_opt.is_none() {
+                self.insert_unannotated(&arg.identifier);
+            }
+        }
+        Ok(())
+    }
+
+    fn function_declaration(&mut self, arg: &FunctionDeclaration) -> Result<(), ParolError> {
+        match self.point {
+            HandlerPoint::Before => {
+                self.insert_unannotated(&arg.identifier);
+                self.function = symbol_table::resolve(arg.identifier.as_ref())
+                    .ok()
+                    .map(|x| x.found.id);
+            }
+            HandlerPoint::After => self.function = None,
+        }
+        Ok(())
+    }
+
+    fn return_statement(&mut self, arg: &ReturnStatement) -> Result<(), ParolError> {
+        match self.point {
+            HandlerPoint::Before => self.expr_power_domains.clear(),
+            HandlerPoint::After => {
+                // power domain of function is inferred from returned value
+                self.push_expr_check(self.function, &arg.semicolon.semicolon_token.token);
+            }
+        }
+        Ok(())
+    }
+
+    fn veryla(&mut self, _arg: &Veryla) -> Result<(), ParolError> {
+        if let HandlerPoint::After = self.point {
+            self.check_power_domains();
+        }
+        Ok(())
+    }
+
+    fn entity_declaration(&mut self, arg: &EntityDeclaration) -> Result<(), ParolError> {
+        let symbol = symbol_table::resolve(arg.identifier.as_ref()).unwrap();
+        match self.point {
+            HandlerPoint::Before => {
+                if let SymbolKind::Entity(ref x) = symbol.found.kind {
+                    self.default_power = x.default_power;
+                }
+                self.in_synchronizer = is_synchronizer(&symbol.found);
+                self.entity_checks = self.checks.len();
+            }
+            HandlerPoint::After => {
+                if let Some(stages) = synchronizer_stages(&symbol.found) {
+                    self.check_synchronizer_stages(&symbol.found, stages);
+                }
             }
         }
         Ok(())
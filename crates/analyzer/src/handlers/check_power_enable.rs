@@ -1,8 +1,12 @@
 use crate::analyzer_error::AnalyzerError;
+use crate::attribute::AllowItem;
+use crate::attribute::Attribute as Attr;
+use crate::attribute_table;
 use crate::evaluator::{Evaluated, Evaluator};
-use crate::symbol::{SymbolKind, TypeKind};
+use crate::symbol::{Direction, Symbol, SymbolKind, TypeKind, VariableAffiliation};
 use crate::symbol_table;
 use veryla_parser::veryla_grammar_trait::*;
+use veryla_parser::veryla_token::Token;
 use veryla_parser::veryla_walker::{Handler, HandlerPoint};
 use veryla_parser::ParolError;
 
@@ -15,6 +19,9 @@ pub struct CheckPowerEnable<'a> {
     in_if_enable: bool,
     if_enable_brace: usize,
     if_enable_exist: bool,
+    if_enable_token: Option<Token>,
+    sequence_assigned: Vec<Symbol>,
+    enable_assigned: Vec<Symbol>,
     n_of_select: usize,
     default_power_exists: bool,
     default_enable_exists: bool,
@@ -28,6 +35,32 @@ impl<'a> CheckPowerEnable<'a> {
             ..Default::default()
         }
     }
+
+    fn check_enable_initialisation(&mut self) {
+        let Some(token) = self.if_enable_token else {
+            return;
+        };
+        if attribute_table::contains(&token, Attr::Allow(AllowItem::MissingEnableStatement)) {
+            return;
+        }
+
+        let mut names: Vec<String> = Vec::new();
+        for x in &self.sequence_assigned {
+            let name = x.token.to_string();
+            if !self.enable_assigned.iter().any(|y| y.id == x.id) && !names.contains(&name) {
+                names.push(name);
+            }
+        }
+
+        if !names.is_empty() {
+            self.errors
+                .push(AnalyzerError::incomplete_enable_initialisation(
+                    &names.join(", "),
+                    self.text,
+                    &token.into(),
+                ));
+        }
+    }
 }
 
 impl Handler for CheckPowerEnable<'_> {
@@ -76,10 +109,49 @@ impl VerylaGrammarTrait for CheckPowerEnable<'_> {
         Ok(())
     }
 
-    fn if_enable(&mut self, _arg: &IfEnable) -> Result<(), ParolError> {
+    fn if_enable(&mut self, arg: &IfEnable) -> Result<(), ParolError> {
         if let HandlerPoint::Before = self.point {
             self.if_enable_exist = true;
             self.in_if_enable = true;
+            if self.if_enable_token.is_none() {
+                self.if_enable_token = Some(arg.if_enable_token.token);
+            }
+        }
+        Ok(())
+    }
+
+    fn identifier_statement(&mut self, arg: &IdentifierStatement) -> Result<(), ParolError> {
+        if let HandlerPoint::Before = self.point {
+            if !self.in_sequence
+                || !matches!(
+                    arg.identifier_statement_group.as_ref(),
+                    IdentifierStatementGroup::Assignment(_)
+                )
+            {
+                return Ok(());
+            }
+
+            // Registers are variables and output ports of the enclosing entity
+            let Ok(symbol) = symbol_table::resolve(arg.expression_identifier.as_ref()) else {
+                return Ok(());
+            };
+            let Some(symbol) = symbol_table::get(symbol.full_path[0]) else {
+                return Ok(());
+            };
+            let is_register = match &symbol.kind {
+                SymbolKind::Variable(x) => matches!(
+                    x.affiliation,
+                    VariableAffiliation::Entity | VariableAffiliation::Interface
+                ),
+                SymbolKind::Port(x) => x.direction == Direction::Output,
+                _ => false,
+            };
+            if is_register {
+                if self.in_if_enable {
+                    self.enable_assigned.push(symbol.clone());
+                }
+                self.sequence_assigned.push(symbol);
+            }
         }
         Ok(())
     }
@@ -134,8 +206,13 @@ impl VerylaGrammarTrait for CheckPowerEnable<'_> {
                     }
                 }
 
+                self.check_enable_initialisation();
+
                 self.in_sequence = false;
                 self.if_enable_exist = false;
+                self.if_enable_token = None;
+                self.sequence_assigned.clear();
+                self.enable_assigned.clear();
             }
         }
         Ok(())
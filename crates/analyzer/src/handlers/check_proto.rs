@@ -19,6 +19,49 @@ impl<'a> CheckProto<'a> {
             ..Default::default()
         }
     }
+
+    fn push_incompat_errors(
+        &mut self,
+        errors: Vec<ProtoIncompatible>,
+        identifier: &Identifier,
+        proto: &str,
+    ) {
+        for error in errors {
+            let cause = match error {
+                ProtoIncompatible::MissingParam(x) => {
+                    format!("parameter {x} is missing")
+                }
+                ProtoIncompatible::MissingPort(x) => {
+                    format!("port {x} is missing")
+                }
+                ProtoIncompatible::MissingMember(x) => {
+                    format!("member {x} is missing")
+                }
+                ProtoIncompatible::UnnecessaryParam(x) => {
+                    format!("parameter {x} is unnecessary")
+                }
+                ProtoIncompatible::UnnecessaryPort(x) => {
+                    format!("port {x} is unnecessary")
+                }
+                ProtoIncompatible::IncompatibleParam(x) => {
+                    format!("parameter {x} has incompatible type")
+                }
+                ProtoIncompatible::IncompatiblePort(x) => {
+                    format!("port {x} has incompatible type")
+                }
+                ProtoIncompatible::IncompatibleMember(x) => {
+                    format!("member {x} has incompatible definition")
+                }
+            };
+            self.errors.push(AnalyzerError::incompat_proto(
+                &identifier.identifier_token.to_string(),
+                proto,
+                &cause,
+                self.text,
+                &identifier.identifier_token.token.into(),
+            ));
+        }
+    }
 }
 
 impl Handler for CheckProto<'_> {
@@ -36,35 +79,11 @@ impl VerylaGrammarTrait for CheckProto<'_> {
                         if let Ok(entity) = symbol_table::resolve(arg.identifier.as_ref()) {
                             if let SymbolKind::Entity(entity) = entity.found.kind {
                                 let errors = proto.check_compat(&entity);
-                                for error in errors {
-                                    let cause = match error {
-                                        ProtoIncompatible::MissingParam(x) => {
-                                            format!("parameter {x} is missing")
-                                        }
-                                        ProtoIncompatible::MissingPort(x) => {
-                                            format!("port {x} is missing")
-                                        }
-                                        ProtoIncompatible::UnnecessaryParam(x) => {
-                                            format!("parameter {x} is unnecessary")
-                                        }
-                                        ProtoIncompatible::UnnecessaryPort(x) => {
-                                            format!("port {x} is unnecessary")
-                                        }
-                                        ProtoIncompatible::IncompatibleParam(x) => {
-                                            format!("parameter {x} has incompatible type")
-                                        }
-                                        ProtoIncompatible::IncompatiblePort(x) => {
-                                            format!("port {x} has incompatible type")
-                                        }
-                                    };
-                                    self.errors.push(AnalyzerError::incompat_proto(
-                                        &arg.identifier.identifier_token.to_string(),
-                                        &symbol.found.token.to_string(),
-                                        &cause,
-                                        self.text,
-                                        &arg.identifier.identifier_token.token.into(),
-                                    ));
-                                }
+                                self.push_incompat_errors(
+                                    errors,
+                                    &arg.identifier,
+                                    &symbol.found.token.to_string(),
+                                );
                             }
                         }
                     } else {
@@ -81,4 +100,62 @@ impl VerylaGrammarTrait for CheckProto<'_> {
         }
         Ok(())
     }
+
+    fn interface_declaration(&mut self, arg: &InterfaceDeclaration) -> Result<(), ParolError> {
+        if let HandlerPoint::Before = self.point {
+            if let Some(ref x) = arg.interface_declaration_opt1 {
+                if let Ok(symbol) = symbol_table::resolve(x.scoped_identifier.as_ref()) {
+                    if let SymbolKind::ProtoInterface(proto) = symbol.found.kind {
+                        if let Ok(interface) = symbol_table::resolve(arg.identifier.as_ref()) {
+                            if let SymbolKind::Interface(ref x) = interface.found.kind {
+                                let errors = proto.check_compat(x, &interface.found);
+                                self.push_incompat_errors(
+                                    errors,
+                                    &arg.identifier,
+                                    &symbol.found.token.to_string(),
+                                );
+                            }
+                        }
+                    } else {
+                        self.errors.push(AnalyzerError::mismatch_type(
+                            &symbol.found.token.to_string(),
+                            "interface prototype",
+                            &symbol.found.kind.to_kind_name(),
+                            self.text,
+                            &x.scoped_identifier.identifier().token.into(),
+                        ));
+                    }
+                }
+            }
+        }
+        Ok(())
+    }
+
+    fn package_declaration(&mut self, arg: &PackageDeclaration) -> Result<(), ParolError> {
+        if let HandlerPoint::Before = self.point {
+            if let Some(ref x) = arg.package_declaration_opt1 {
+                if let Ok(symbol) = symbol_table::resolve(x.scoped_identifier.as_ref()) {
+                    if let SymbolKind::ProtoPackage(proto) = symbol.found.kind {
+                        if let Ok(package) = symbol_table::resolve(arg.identifier.as_ref()) {
+                            let errors = proto.check_compat(&package.found);
+                            self.push_incompat_errors(
+                                errors,
+                                &arg.identifier,
+                                &symbol.found.token.to_string(),
+                            );
+                        }
+                    } else {
+                        self.errors.push(AnalyzerError::mismatch_type(
+                            &symbol.found.token.to_string(),
+                            "package prototype",
+                            &symbol.found.kind.to_kind_name(),
+                            self.text,
+                            &x.scoped_identifier.identifier().token.into(),
+                        ));
+                    }
+                }
+            }
+        }
+        Ok(())
+    }
 }
@@ -1,4 +1,4 @@
-use crate::analyzer_error::AnalyzerError;
+use crate::analyzer_error::{AnalyzerError, TextEdit};
 use crate::attribute::AllowItem;
 use crate::attribute::Attribute as Attr;
 use crate::attribute_table;
@@ -66,6 +66,29 @@ fn is_casting_type(symbol: &Symbol) -> bool {
     }
 }
 
+/// Connect missing ports to the variables of the same name
+fn missing_port_fixes(arg: &InstDeclaration, ports: &[String]) -> Vec<TextEdit> {
+    if ports.is_empty() {
+        return Vec::new();
+    }
+    let ports = ports.join(", ");
+
+    let edit = if let Some(ref x) = arg.inst_declaration_opt1 {
+        let text = match &x.inst_declaration_opt2 {
+            Some(x) if x.inst_port_list.inst_port_list_opt.is_some() => format!(" {ports},"),
+            Some(_) => format!(", {ports}"),
+            None => ports,
+        };
+        TextEdit::insert(x.r_paren.r_paren_token.token.pos, &text)
+    } else {
+        TextEdit::insert(
+            arg.semicolon.semicolon_token.token.pos,
+            &format!(" ({ports})"),
+        )
+    };
+    vec![edit]
+}
+
 impl VerylaGrammarTrait for CheckType<'_> {
     fn user_defined_type(&mut self, _arg: &UserDefinedType) -> Result<(), ParolError> {
         match self.point {
@@ -327,13 +350,15 @@ impl VerylaGrammarTrait for CheckType<'_> {
                         if let GenericBoundKind::Proto(ref x) = x.bound {
                             if let Ok(symbol) = symbol_table::resolve((x, &symbol.found.namespace))
                             {
-                                if let SymbolKind::ProtoEntity(x) = symbol.found.kind {
-                                    params.append(&mut x.parameters.clone());
-                                    ports.append(&mut x.ports.clone());
-                                    check_port_connection = true;
-                                    None
-                                } else {
-                                    Some("entity or interface")
+                                match symbol.found.kind {
+                                    SymbolKind::ProtoEntity(ref x) => {
+                                        params.append(&mut x.parameters.clone());
+                                        ports.append(&mut x.ports.clone());
+                                        check_port_connection = true;
+                                        None
+                                    }
+                                    SymbolKind::ProtoInterface(_) => None,
+                                    _ => Some("entity or interface"),
                                 }
                             } else {
                                 None
@@ -362,22 +387,27 @@ impl VerylaGrammarTrait for CheckType<'_> {
                 }
 
                 if check_port_connection {
-                    for port in &ports {
-                        if !connected_ports.contains(&port.name())
-                            && port.property().default_value.is_none()
-                            && !attribute_table::contains(
-                                &arg.inst.inst_token.token,
-                                Attr::Allow(AllowItem::MissingPort),
-                            )
-                        {
-                            let port = resource_table::get_str_value(port.name()).unwrap();
-                            self.errors.push(AnalyzerError::missing_port(
-                                name,
-                                &port,
-                                self.text,
-                                &arg.identifier.as_ref().into(),
-                            ));
-                        }
+                    let missing_ports: Vec<_> = ports
+                        .iter()
+                        .filter(|x| {
+                            !connected_ports.contains(&x.name())
+                                && x.property().default_value.is_none()
+                                && !attribute_table::contains(
+                                    &arg.inst.inst_token.token,
+                                    Attr::Allow(AllowItem::MissingPort),
+                                )
+                        })
+                        .map(|x| resource_table::get_str_value(x.name()).unwrap())
+                        .collect();
+                    let fixes = missing_port_fixes(arg, &missing_ports);
+                    for port in &missing_ports {
+                        self.errors.push(AnalyzerError::missing_port(
+                            name,
+                            port,
+                            &fixes,
+                            self.text,
+                            &arg.identifier.as_ref().into(),
+                        ));
                     }
                     for param in &connected_params {
                         if !params.iter().any(|x| &x.name == param) {
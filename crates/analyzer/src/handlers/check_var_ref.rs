@@ -2,16 +2,17 @@ use crate::analyzer_error::AnalyzerError;
 use crate::attribute::Attribute as Attr;
 use crate::attribute::{AllowItem, CondTypeItem};
 use crate::attribute_table;
+use crate::evaluator::{Evaluated, Evaluator};
 use crate::symbol::{Direction, Symbol, SymbolId, SymbolKind, TypeKind};
 use crate::symbol_table;
 use crate::var_ref::{
-    AssignDeclarationType, AssignPosition, AssignPositionType, AssignStatementBranchItemType,
-    AssignStatementBranchType, ExpressionTargetType, VarRef, VarRefAffiliation, VarRefPath,
-    VarRefType,
+    AssignBranchCondition, AssignDeclarationType, AssignPosition, AssignPositionType,
+    AssignStatementBranchItemType, AssignStatementBranchType, ExpressionTargetType, VarRef,
+    VarRefAffiliation, VarRefPath, VarRefType,
 };
 use std::collections::HashMap;
 use veryla_parser::veryla_grammar_trait::*;
-use veryla_parser::veryla_token::Token;
+use veryla_parser::veryla_token::{Token, TokenRange};
 use veryla_parser::veryla_walker::{Handler, HandlerPoint};
 use veryla_parser::ParolError;
 
@@ -70,6 +71,18 @@ impl<'a> CheckVarRef<'a> {
         symbol_table::add_var_ref(&expression);
     }
 
+    fn source_text(&self, beg: u32, end: u32) -> String {
+        let text = self.text.get(beg as usize..end as usize).unwrap_or("");
+        text.trim().to_string()
+    }
+
+    fn branch_condition(&self, token: &Token, end: &TokenRange) -> AssignBranchCondition {
+        AssignBranchCondition {
+            token: *token,
+            text: self.source_text(token.pos, end.end.pos + end.end.length),
+        }
+    }
+
     fn push_function_call(&mut self, identifier: &ExpressionIdentifier) {
         if let Ok(func) = symbol_table::resolve(identifier) {
             let ports = match func.found.kind {
@@ -449,10 +462,23 @@ impl VerylaGrammarTrait for CheckVarRef<'_> {
                 let has_explicit_default = arg.if_statement_opt.is_some();
                 let has_cond_type = has_cond_type(&arg.r#if.if_token.token);
                 let has_default = has_explicit_default | has_cond_type;
+                let mut conditions = vec![self
+                    .branch_condition(&arg.r#if.if_token.token, &arg.expression.as_ref().into())];
+                for x in &arg.if_statement_list {
+                    conditions.push(self.branch_condition(
+                        &x.r#else.else_token.token,
+                        &x.expression.as_ref().into(),
+                    ));
+                }
+                if let Some(ref x) = arg.if_statement_opt {
+                    let token = &x.r#else.else_token.token;
+                    conditions.push(self.branch_condition(token, &token.into()));
+                }
                 self.assign_position
                     .push(AssignPositionType::StatementBranch {
                         token: arg.r#if.if_token.token,
                         branches,
+                        conditions,
                         has_default,
                         allow_missing_enable_statement: false,
                         r#type: AssignStatementBranchType::If,
@@ -486,10 +512,23 @@ impl VerylaGrammarTrait for CheckVarRef<'_> {
                     &arg.if_enable.if_enable_token.token,
                     Attr::Allow(AllowItem::MissingEnableStatement),
                 );
+                let token = &arg.if_enable.if_enable_token.token;
+                let mut conditions = vec![self.branch_condition(token, &token.into())];
+                for x in &arg.if_enable_statement_list {
+                    conditions.push(self.branch_condition(
+                        &x.r#else.else_token.token,
+                        &x.expression.as_ref().into(),
+                    ));
+                }
+                if let Some(ref x) = arg.if_enable_statement_opt {
+                    let token = &x.r#else.else_token.token;
+                    conditions.push(self.branch_condition(token, &token.into()));
+                }
                 self.assign_position
                     .push(AssignPositionType::StatementBranch {
                         token: arg.if_enable.if_enable_token.token,
                         branches,
+                        conditions,
                         has_default,
                         allow_missing_enable_statement,
                         r#type: AssignStatementBranchType::IfEnable,
@@ -511,13 +550,65 @@ impl VerylaGrammarTrait for CheckVarRef<'_> {
     }
 
     fn for_statement(&mut self, arg: &ForStatement) -> Result<(), ParolError> {
-        if let HandlerPoint::Before = self.point {
-            if let Ok(path) = VarRefPath::try_from(arg.identifier.as_ref()) {
-                self.assign_position.push(AssignPositionType::Statement {
-                    token: arg.r#for.for_token.token,
-                    enabletable: false,
-                });
-                self.add_assign(&path);
+        match self.point {
+            HandlerPoint::Before => {
+                if let Ok(path) = VarRefPath::try_from(arg.identifier.as_ref()) {
+                    self.assign_position.push(AssignPositionType::Statement {
+                        token: arg.r#for.for_token.token,
+                        enabletable: false,
+                    });
+                    self.add_assign(&path);
+                }
+
+                // The loop body is always executed unless the range is statically empty
+                let range = arg.range.as_ref();
+                let end = range
+                    .range_opt
+                    .as_ref()
+                    .map(|x| x.expression.as_ref())
+                    .unwrap_or(range.expression.as_ref());
+                let mut evaluator = Evaluator::new();
+                let beg_value = evaluator.expression(&range.expression);
+                let is_empty = if let Some(ref x) = range.range_opt {
+                    let end_value = evaluator.expression(&x.expression);
+                    let inclusive = matches!(*x.range_operator, RangeOperator::DotDotEqu(_));
+                    match (beg_value, end_value) {
+                        (
+                            Evaluated::Fixed { value: beg, .. },
+                            Evaluated::Fixed { value: end, .. },
+                        ) => {
+                            if inclusive {
+                                beg > end
+                            } else {
+                                beg >= end
+                            }
+                        }
+                        _ => false,
+                    }
+                } else {
+                    false
+                };
+
+                let token = arg.r#for.for_token.token;
+                self.assign_position
+                    .push(AssignPositionType::StatementBranch {
+                        token,
+                        branches: 1,
+                        conditions: vec![self.branch_condition(&token, &end.into())],
+                        has_default: !is_empty,
+                        allow_missing_enable_statement: false,
+                        r#type: AssignStatementBranchType::For,
+                    });
+                self.assign_position
+                    .push(AssignPositionType::StatementBranchItem {
+                        token,
+                        index: 0,
+                        r#type: AssignStatementBranchItemType::For,
+                    });
+            }
+            HandlerPoint::After => {
+                self.assign_position.pop();
+                self.assign_position.pop();
             }
         }
         Ok(())
@@ -536,10 +627,30 @@ impl VerylaGrammarTrait for CheckVarRef<'_> {
                 });
                 let has_cond_type = has_cond_type(&arg.case.case_token.token);
                 let has_default = has_explicit_default | has_cond_type;
+                let conditions = arg
+                    .case_statement_list
+                    .iter()
+                    .map(|x| {
+                        let beg = match x.case_item.case_item_group.as_ref() {
+                            CaseItemGroup::CaseCondition(x) => {
+                                let range: TokenRange =
+                                    x.case_condition.range_item.range.expression.as_ref().into();
+                                range.beg
+                            }
+                            CaseItemGroup::Defaul(x) => x.defaul.default_token.token,
+                        };
+                        let token = x.case_item.colon.colon_token.token;
+                        AssignBranchCondition {
+                            token,
+                            text: self.source_text(beg.pos, token.pos),
+                        }
+                    })
+                    .collect();
                 self.assign_position
                     .push(AssignPositionType::StatementBranch {
                         token: arg.case.case_token.token,
                         branches,
+                        conditions,
                         has_default,
                         allow_missing_enable_statement: false,
                         r#type: AssignStatementBranchType::Case,
@@ -570,6 +681,73 @@ impl VerylaGrammarTrait for CheckVarRef<'_> {
         Ok(())
     }
 
+    fn switch_statement(&mut self, arg: &SwitchStatement) -> Result<(), ParolError> {
+        match self.point {
+            HandlerPoint::Before => {
+                self.branch_index = 0;
+                let branches = arg.switch_statement_list.len();
+                let has_explicit_default = arg.switch_statement_list.iter().any(|x| {
+                    matches!(
+                        x.switch_item.switch_item_group.as_ref(),
+                        SwitchItemGroup::Defaul(_)
+                    )
+                });
+                let has_cond_type = has_cond_type(&arg.switch.switch_token.token);
+                let has_default = has_explicit_default | has_cond_type;
+                let conditions = arg
+                    .switch_statement_list
+                    .iter()
+                    .map(|x| {
+                        let beg = match x.switch_item.switch_item_group.as_ref() {
+                            SwitchItemGroup::SwitchCondition(x) => {
+                                let range: TokenRange =
+                                    x.switch_condition.expression.as_ref().into();
+                                range.beg
+                            }
+                            SwitchItemGroup::Defaul(x) => x.defaul.default_token.token,
+                        };
+                        let token = x.switch_item.colon.colon_token.token;
+                        AssignBranchCondition {
+                            token,
+                            text: self.source_text(beg.pos, token.pos),
+                        }
+                    })
+                    .collect();
+                self.assign_position
+                    .push(AssignPositionType::StatementBranch {
+                        token: arg.switch.switch_token.token,
+                        branches,
+                        conditions,
+                        has_default,
+                        allow_missing_enable_statement: false,
+                        r#type: AssignStatementBranchType::Switch,
+                    });
+            }
+            HandlerPoint::After => {
+                self.assign_position.pop();
+            }
+        }
+        Ok(())
+    }
+
+    fn switch_item(&mut self, arg: &SwitchItem) -> Result<(), ParolError> {
+        match self.point {
+            HandlerPoint::Before => {
+                self.assign_position
+                    .push(AssignPositionType::StatementBranchItem {
+                        token: arg.colon.colon_token.token,
+                        index: self.branch_index,
+                        r#type: AssignStatementBranchItemType::Switch,
+                    });
+                self.branch_index += 1;
+            }
+            HandlerPoint::After => {
+                self.assign_position.pop();
+            }
+        }
+        Ok(())
+    }
+
     fn let_declaration(&mut self, arg: &LetDeclaration) -> Result<(), ParolError> {
         if let HandlerPoint::After = self.point {
             if let Ok(path) = VarRefPath::try_from(arg.identifier.as_ref()) {
@@ -651,7 +829,8 @@ impl VerylaGrammarTrait for CheckVarRef<'_> {
     }
 
     fn inst_declaration(&mut self, arg: &InstDeclaration) -> Result<(), ParolError> {
-        if let HandlerPoint::Before = self.point {
+        // Generic instance referred by the type is created while walking the declaration
+        if let HandlerPoint::After = self.point {
             if let Ok(symbol) = symbol_table::resolve(arg.identifier.as_ref()) {
                 if let SymbolKind::Instance(ref x) = symbol.found.kind {
                     let mut ports = HashMap::new();
@@ -662,7 +841,13 @@ impl VerylaGrammarTrait for CheckVarRef<'_> {
                         &x.type_name.mangled_path(),
                         &symbol.found.namespace,
                     )) {
-                        match x.found.kind {
+                        let found = match x.found.kind {
+                            SymbolKind::GenericInstance(ref x) => {
+                                symbol_table::get(x.base).unwrap()
+                            }
+                            _ => x.found,
+                        };
+                        match found.kind {
                             SymbolKind::Entity(ref x) => {
                                 for port in &x.ports {
                                     ports.insert(port.name(), port.property());
@@ -0,0 +1,267 @@
+use crate::comb_graph::CombDependency;
+use crate::symbol::{SymbolId, SymbolKind};
+use crate::symbol_table;
+use crate::var_ref::VarRefPath;
+use veryla_parser::resource_table::TokenId;
+use veryla_parser::veryla_grammar_trait::*;
+use veryla_parser::veryla_token::Token;
+use veryla_parser::veryla_walker::{Handler, HandlerPoint};
+use veryla_parser::ParolError;
+
+#[derive(Default)]
+pub struct CreateCombDependency {
+    point: HandlerPoint,
+    entity: Option<SymbolId>,
+    in_declaration: bool,
+    always_comb: Option<Token>,
+    /// Signals read by enclosing branch conditions
+    conditions: Vec<Vec<VarRefPath>>,
+    sources: Vec<VarRefPath>,
+    assign_target: Option<TokenId>,
+}
+
+impl CreateCombDependency {
+    fn in_comb(&self) -> bool {
+        self.entity.is_some() && (self.in_declaration || self.always_comb.is_some())
+    }
+
+    fn add_statement(&mut self, token: Token, target: Option<VarRefPath>) {
+        let mut sources: Vec<_> = self.conditions.iter().flatten().cloned().collect();
+        sources.append(&mut self.sources);
+
+        if let (Some(entity), Some(target)) = (self.entity, target) {
+            let dependency = CombDependency::Statement {
+                token,
+                block: self.always_comb,
+                sources,
+                targets: vec![target],
+            };
+            symbol_table::add_comb_dependency(entity, &dependency);
+        }
+    }
+
+    fn push_condition(&mut self) {
+        if self.always_comb.is_some() {
+            self.conditions.push(Vec::new());
+        }
+    }
+
+    fn pop_condition(&mut self) {
+        if self.always_comb.is_some() {
+            self.conditions.pop();
+        }
+    }
+
+    fn take_condition(&mut self) {
+        if let Some(x) = self.conditions.last_mut() {
+            x.append(&mut self.sources);
+        }
+    }
+}
+
+impl Handler for CreateCombDependency {
+    fn set_point(&mut self, p: HandlerPoint) {
+        self.point = p;
+    }
+}
+
+impl VerylaGrammarTrait for CreateCombDependency {
+    fn expression_identifier(&mut self, arg: &ExpressionIdentifier) -> Result<(), ParolError> {
+        if let HandlerPoint::Before = self.point {
+            if !self.in_comb() {
+                return Ok(());
+            }
+
+            // The assignment target of statement is not a source
+            let token = arg.identifier().token;
+            if self.assign_target == Some(token.id) {
+                self.assign_target = None;
+                return Ok(());
+            }
+
+            if let Ok(path) = VarRefPath::try_from(arg) {
+                let full_path = path.full_path();
+                let symbol = symbol_table::get(*full_path.last().unwrap()).unwrap();
+                if matches!(symbol.kind, SymbolKind::Variable(_) | SymbolKind::Port(_)) {
+                    self.sources.push(path);
+                }
+            }
+        }
+        Ok(())
+    }
+
+    fn statement_block(&mut self, _arg: &StatementBlock) -> Result<(), ParolError> {
+        if let HandlerPoint::Before = self.point {
+            self.take_condition();
+        }
+        Ok(())
+    }
+
+    fn let_statement(&mut self, arg: &LetStatement) -> Result<(), ParolError> {
+        if let HandlerPoint::After = self.point {
+            if self.always_comb.is_some() {
+                let target = VarRefPath::try_from(arg.identifier.as_ref()).ok();
+                self.add_statement(arg.r#let.let_token.token, target);
+            }
+        }
+        Ok(())
+    }
+
+    fn identifier_statement(&mut self, arg: &IdentifierStatement) -> Result<(), ParolError> {
+        let IdentifierStatementGroup::Assignment(x) = arg.identifier_statement_group.as_ref()
+        else {
+            if let HandlerPoint::After = self.point {
+                self.sources.clear();
+            }
+            return Ok(());
+        };
+
+        match self.point {
+            HandlerPoint::Before => {
+                self.assign_target = Some(arg.expression_identifier.identifier().token.id);
+            }
+            HandlerPoint::After => {
+                if self.always_comb.is_some() {
+                    let token = match x.assignment.assignment_group.as_ref() {
+                        AssignmentGroup::Equ(x) => x.equ.equ_token.token,
+                        AssignmentGroup::AssignmentOperator(x) => {
+                            x.assignment_operator.assignment_operator_token.token
+                        }
+                    };
+                    let target = VarRefPath::try_from(arg.expression_identifier.as_ref()).ok();
+
+                    // Compound assignment reads the target too
+                    if let (AssignmentGroup::AssignmentOperator(_), Some(target)) =
+                        (x.assignment.assignment_group.as_ref(), &target)
+                    {
+                        self.sources.push(target.clone());
+                    }
+                    self.add_statement(token, target);
+                }
+                self.assign_target = None;
+            }
+        }
+        Ok(())
+    }
+
+    fn if_statement(&mut self, _arg: &IfStatement) -> Result<(), ParolError> {
+        match self.point {
+            HandlerPoint::Before => self.push_condition(),
+            HandlerPoint::After => self.pop_condition(),
+        }
+        Ok(())
+    }
+
+    fn if_enable_statement(&mut self, _arg: &IfEnableStatement) -> Result<(), ParolError> {
+        match self.point {
+            HandlerPoint::Before => self.push_condition(),
+            HandlerPoint::After => self.pop_condition(),
+        }
+        Ok(())
+    }
+
+    fn for_statement(&mut self, _arg: &ForStatement) -> Result<(), ParolError> {
+        match self.point {
+            HandlerPoint::Before => self.push_condition(),
+            HandlerPoint::After => self.pop_condition(),
+        }
+        Ok(())
+    }
+
+    fn case_statement(&mut self, _arg: &CaseStatement) -> Result<(), ParolError> {
+        match self.point {
+            HandlerPoint::Before => self.push_condition(),
+            HandlerPoint::After => self.pop_condition(),
+        }
+        Ok(())
+    }
+
+    fn case_item(&mut self, _arg: &CaseItem) -> Result<(), ParolError> {
+        if let HandlerPoint::Before = self.point {
+            self.take_condition();
+        }
+        Ok(())
+    }
+
+    fn switch_statement(&mut self, _arg: &SwitchStatement) -> Result<(), ParolError> {
+        match self.point {
+            HandlerPoint::Before => self.push_condition(),
+            HandlerPoint::After => self.pop_condition(),
+        }
+        Ok(())
+    }
+
+    fn let_declaration(&mut self, arg: &LetDeclaration) -> Result<(), ParolError> {
+        match self.point {
+            HandlerPoint::Before => {
+                self.in_declaration = true;
+                self.sources.clear();
+            }
+            HandlerPoint::After => {
+                let target = VarRefPath::try_from(arg.identifier.as_ref()).ok();
+                self.add_statement(arg.r#let.let_token.token, target);
+                self.in_declaration = false;
+            }
+        }
+        Ok(())
+    }
+
+    fn always_comb_declaration(&mut self, arg: &AlwaysCombDeclaration) -> Result<(), ParolError> {
+        match self.point {
+            HandlerPoint::Before => {
+                self.always_comb = Some(arg.always_comb.always_comb_token.token);
+                self.conditions.clear();
+                self.sources.clear();
+            }
+            HandlerPoint::After => {
+                self.always_comb = None;
+            }
+        }
+        Ok(())
+    }
+
+    fn assign_declaration(&mut self, arg: &AssignDeclaration) -> Result<(), ParolError> {
+        match self.point {
+            HandlerPoint::Before => {
+                self.in_declaration = true;
+                self.sources.clear();
+            }
+            HandlerPoint::After => {
+                let target = VarRefPath::try_from(arg.hierarchical_identifier.as_ref()).ok();
+                self.add_statement(arg.assign.assign_token.token, target);
+                self.in_declaration = false;
+            }
+        }
+        Ok(())
+    }
+
+    fn inst_declaration(&mut self, arg: &InstDeclaration) -> Result<(), ParolError> {
+        if let HandlerPoint::Before = self.point {
+            let Some(entity) = self.entity else {
+                return Ok(());
+            };
+            if let Ok(symbol) = symbol_table::resolve(arg.identifier.as_ref()) {
+                let dependency = CombDependency::Instance {
+                    token: arg.identifier.identifier_token.token,
+                    symbol: symbol.found.id,
+                };
+                symbol_table::add_comb_dependency(entity, &dependency);
+            }
+        }
+        Ok(())
+    }
+
+    fn entity_declaration(&mut self, arg: &EntityDeclaration) -> Result<(), ParolError> {
+        match self.point {
+            HandlerPoint::Before => {
+                self.entity = symbol_table::resolve(arg.identifier.as_ref())
+                    .ok()
+                    .map(|x| x.found.id);
+            }
+            HandlerPoint::After => {
+                self.entity = None;
+            }
+        }
+        Ok(())
+    }
+}
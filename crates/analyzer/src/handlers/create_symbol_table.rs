@@ -15,7 +15,8 @@ use crate::symbol::{
     GenericBoundKind, GenericParameterProperty, InstanceProperty, InterfaceProperty,
     ModportFunctionMemberProperty, ModportProperty, ModportVariableMemberProperty, EntityProperty,
     PackageProperty, Parameter, ParameterKind, ParameterProperty, Port, PortProperty,
-    ProtoEntityProperty, StructMemberProperty, StructProperty, Symbol, SymbolId, SymbolKind,
+    ProtoConstProperty, ProtoEntityProperty, ProtoFunctionProperty, ProtoInterfaceProperty,
+    ProtoPackageProperty, StructMemberProperty, StructProperty, Symbol, SymbolId, SymbolKind,
     TestProperty, TestType, TypeDefProperty, TypeKind, UnionMemberProperty, UnionProperty,
     VariableAffiliation, VariableProperty,
 };
@@ -23,6 +24,7 @@ use crate::symbol_path::{GenericSymbolPath, SymbolPath, SymbolPathNamespace};
 use crate::symbol_table;
 use crate::symbol_table::Import as SymImport;
 use std::collections::{HashMap, HashSet};
+use std::rc::Rc;
 use veryla_metadata::PowerType;
 use veryla_metadata::{Build, EnableType};
 use veryla_parser::doc_comment_table;
@@ -92,6 +94,8 @@ pub struct CreateSymbolTable<'a> {
     function_ids: HashMap<StrId, SymbolId>,
     exist_power_without_domain: bool,
     in_proto: bool,
+    proto_public: bool,
+    proto_members: Vec<SymbolId>,
     file_scope_import_item: Vec<SymbolPathNamespace>,
     file_scope_import_wildcard: Vec<SymbolPathNamespace>,
 }
@@ -153,7 +157,8 @@ impl<'a> CreateSymbolTable<'a> {
         };
         let mut symbol = Symbol::new(token, kind, &self.namespace, public, doc_comment);
 
-        if attribute_table::contains(token, Attr::Allow(AllowItem::UnusedVariable)) {
+        if self.in_proto || attribute_table::contains(token, Attr::Allow(AllowItem::UnusedVariable))
+        {
             symbol.allow_unused = true;
         }
 
@@ -169,6 +174,20 @@ impl<'a> CreateSymbolTable<'a> {
         id
     }
 
+    fn link_modport_function_member(&mut self) {
+        for id in &self.modport_member_ids {
+            let mut mp_member = symbol_table::get(*id).unwrap();
+            if let SymbolKind::ModportFunctionMember(_) = mp_member.kind {
+                if let Some(id) = self.function_ids.get(&mp_member.token.text) {
+                    let property = ModportFunctionMemberProperty { function: *id };
+                    let kind = SymbolKind::ModportFunctionMember(property);
+                    mp_member.kind = kind;
+                    symbol_table::update(mp_member);
+                }
+            }
+        }
+    }
+
     fn insert_power_domain(&mut self, power_domain: &PowerDomain) -> SymPowerDomain {
         // '_ is implicit power domain
         if power_domain.identifier.identifier_token.to_string() == "_" {
@@ -628,7 +647,9 @@ impl VerylaGrammarTrait for CreateSymbolTable<'_> {
             if let Some(id) =
                 self.insert_symbol(&arg.identifier.identifier_token.token, kind.clone(), false)
             {
-                if self.is_default_power_candidate(kind.clone()) {
+                if self.in_proto {
+                    self.proto_members.push(id);
+                } else if self.is_default_power_candidate(kind.clone()) {
                     self.default_power_candidates.push(id);
                 } else if self.is_default_enable_candidate(kind.clone()) {
                     self.defualt_enable_candidates.push(id);
@@ -712,7 +733,13 @@ impl VerylaGrammarTrait for CreateSymbolTable<'_> {
 
             let property = ModportProperty { members };
             let kind = SymbolKind::Modport(property);
-            self.insert_symbol(&arg.identifier.identifier_token.token, kind, false);
+            if let Some(id) =
+                self.insert_symbol(&arg.identifier.identifier_token.token, kind, false)
+            {
+                if self.in_proto {
+                    self.proto_members.push(id);
+                }
+            }
         }
         Ok(())
     }
@@ -1146,6 +1173,7 @@ impl VerylaGrammarTrait for CreateSymbolTable<'_> {
                     generic_references,
                     ports,
                     ret,
+                    body: Rc::new(arg.statement_block.as_ref().clone()),
                 };
 
                 if let Some(id) = self.insert_symbol(
@@ -1341,9 +1369,14 @@ impl VerylaGrammarTrait for CreateSymbolTable<'_> {
 
                 let range =
                     TokenRange::new(&arg.interface.interface_token, &arg.r_brace.r_brace_token);
+                let proto = arg
+                    .interface_declaration_opt1
+                    .as_ref()
+                    .map(|x| x.scoped_identifier.as_ref().into());
 
                 let property = InterfaceProperty {
                     range,
+                    proto,
                     generic_parameters,
                     generic_references,
                     parameters,
@@ -1355,18 +1388,7 @@ impl VerylaGrammarTrait for CreateSymbolTable<'_> {
                     public,
                 );
 
-                //  link modport function
-                for id in &self.modport_member_ids {
-                    let mut mp_member = symbol_table::get(*id).unwrap();
-                    if let SymbolKind::ModportFunctionMember(_) = mp_member.kind {
-                        if let Some(id) = self.function_ids.get(&mp_member.token.text) {
-                            let property = ModportFunctionMemberProperty { function: *id };
-                            let kind = SymbolKind::ModportFunctionMember(property);
-                            mp_member.kind = kind;
-                            symbol_table::update(mp_member);
-                        }
-                    }
-                }
+                self.link_modport_function_member();
             }
         }
         Ok(())
@@ -1390,9 +1412,14 @@ impl VerylaGrammarTrait for CreateSymbolTable<'_> {
                 let (generic_parameters, generic_references) = self.generic_context.pop();
 
                 let range = TokenRange::new(&arg.package.package_token, &arg.r_brace.r_brace_token);
+                let proto = arg
+                    .package_declaration_opt1
+                    .as_ref()
+                    .map(|x| x.scoped_identifier.as_ref().into());
 
                 let property = PackageProperty {
                     range,
+                    proto,
                     generic_parameters,
                     generic_references,
                 };
@@ -1407,6 +1434,20 @@ impl VerylaGrammarTrait for CreateSymbolTable<'_> {
         Ok(())
     }
 
+    fn proto_declaration(&mut self, arg: &ProtoDeclaration) -> Result<(), ParolError> {
+        match self.point {
+            HandlerPoint::Before => {
+                self.in_proto = true;
+                self.proto_public = arg.proto_declaration_opt.is_some();
+            }
+            HandlerPoint::After => {
+                self.in_proto = false;
+                self.proto_public = false;
+            }
+        }
+        Ok(())
+    }
+
     fn proto_entity_declaration(&mut self, arg: &ProtoEntityDeclaration) -> Result<(), ParolError> {
         let name = arg.identifier.identifier_token.token.text;
         match self.point {
@@ -1415,12 +1456,10 @@ impl VerylaGrammarTrait for CreateSymbolTable<'_> {
                 self.parameters.push(Vec::new());
                 self.ports.push(Vec::new());
                 self.affiliation.push(VariableAffiliation::Entity);
-                self.in_proto = true;
             }
             HandlerPoint::After => {
                 self.namespace.pop();
                 self.affiliation.pop();
-                self.in_proto = false;
 
                 let parameters: Vec<_> = self.parameters.pop().unwrap();
                 let ports: Vec<_> = self.ports.pop().unwrap();
@@ -1436,17 +1475,162 @@ impl VerylaGrammarTrait for CreateSymbolTable<'_> {
                     parameters,
                     ports,
                 };
-                let public = arg.proto_entity_declaration_opt.is_some();
                 self.insert_symbol(
                     &arg.identifier.identifier_token.token,
                     SymbolKind::ProtoEntity(property),
-                    public,
+                    self.proto_public,
                 );
             }
         }
         Ok(())
     }
 
+    fn proto_interface_declaration(
+        &mut self,
+        arg: &ProtoInterfaceDeclaration,
+    ) -> Result<(), ParolError> {
+        let name = arg.identifier.identifier_token.token.text;
+        match self.point {
+            HandlerPoint::Before => {
+                self.namespace.push(name);
+                self.parameters.push(Vec::new());
+                self.affiliation.push(VariableAffiliation::Interface);
+                self.function_ids.clear();
+                self.modport_member_ids.clear();
+                self.proto_members.clear();
+            }
+            HandlerPoint::After => {
+                self.namespace.pop();
+                self.affiliation.pop();
+
+                let parameters: Vec<_> = self.parameters.pop().unwrap();
+                let members: Vec<_> = self.proto_members.drain(0..).collect();
+
+                let range =
+                    TokenRange::new(&arg.interface.interface_token, &arg.r_brace.r_brace_token);
+
+                let property = ProtoInterfaceProperty {
+                    range,
+                    parameters,
+                    members,
+                };
+                self.insert_symbol(
+                    &arg.identifier.identifier_token.token,
+                    SymbolKind::ProtoInterface(property),
+                    self.proto_public,
+                );
+
+                self.link_modport_function_member();
+            }
+        }
+        Ok(())
+    }
+
+    fn proto_package_declaration(
+        &mut self,
+        arg: &ProtoPackageDeclaration,
+    ) -> Result<(), ParolError> {
+        let name = arg.identifier.identifier_token.token.text;
+        match self.point {
+            HandlerPoint::Before => {
+                self.namespace.push(name);
+                self.affiliation.push(VariableAffiliation::Package);
+                self.proto_members.clear();
+            }
+            HandlerPoint::After => {
+                self.namespace.pop();
+                self.affiliation.pop();
+
+                let members: Vec<_> = self.proto_members.drain(0..).collect();
+
+                let range = TokenRange::new(&arg.package.package_token, &arg.r_brace.r_brace_token);
+
+                let property = ProtoPackageProperty { range, members };
+                self.insert_symbol(
+                    &arg.identifier.identifier_token.token,
+                    SymbolKind::ProtoPackage(property),
+                    self.proto_public,
+                );
+            }
+        }
+        Ok(())
+    }
+
+    fn proto_const_declaration(&mut self, arg: &ProtoConstDeclaration) -> Result<(), ParolError> {
+        if let HandlerPoint::Before = self.point {
+            let token = arg.identifier.identifier_token.token;
+            let r#type: SymType = match &*arg.proto_const_declaration_group {
+                ProtoConstDeclarationGroup::ArrayType(x) => x.array_type.as_ref().into(),
+                ProtoConstDeclarationGroup::Type(_) => SymType {
+                    modifier: vec![],
+                    kind: TypeKind::Type,
+                    width: vec![],
+                    array: vec![],
+                    is_const: false,
+                },
+            };
+            let property = ProtoConstProperty { token, r#type };
+            let kind = SymbolKind::ProtoConst(property);
+            if let Some(id) = self.insert_symbol(&token, kind, false) {
+                self.proto_members.push(id);
+            }
+        }
+        Ok(())
+    }
+
+    fn proto_type_def_declaration(
+        &mut self,
+        arg: &ProtoTypeDefDeclaration,
+    ) -> Result<(), ParolError> {
+        if let HandlerPoint::Before = self.point {
+            let token = &arg.identifier.identifier_token.token;
+            if let Some(id) = self.insert_symbol(token, SymbolKind::ProtoTypeDef, false) {
+                self.proto_members.push(id);
+            }
+        }
+        Ok(())
+    }
+
+    fn proto_function_declaration(
+        &mut self,
+        arg: &ProtoFunctionDeclaration,
+    ) -> Result<(), ParolError> {
+        let name = arg.identifier.identifier_token.token.text;
+        match self.point {
+            HandlerPoint::Before => {
+                self.namespace.push(name);
+                self.ports.push(Vec::new());
+                self.affiliation.push(VariableAffiliation::Function);
+            }
+            HandlerPoint::After => {
+                self.namespace.pop();
+                self.affiliation.pop();
+
+                let ports: Vec<_> = self.ports.pop().unwrap();
+
+                let ret = arg
+                    .proto_function_declaration_opt0
+                    .as_ref()
+                    .map(|x| (&*x.scalar_type).into());
+
+                let range =
+                    TokenRange::new(&arg.function.function_token, &arg.semicolon.semicolon_token);
+
+                let property = ProtoFunctionProperty { range, ports, ret };
+                if let Some(id) = self.insert_symbol(
+                    &arg.identifier.identifier_token.token,
+                    SymbolKind::ProtoFunction(property),
+                    false,
+                ) {
+                    self.proto_members.push(id);
+                    self.function_ids
+                        .insert(arg.identifier.identifier_token.token.text, id);
+                }
+            }
+        }
+        Ok(())
+    }
+
     fn embed_declaration(&mut self, arg: &EmbedDeclaration) -> Result<(), ParolError> {
         if let HandlerPoint::Before = self.point {
             let way = arg.identifier.identifier_token.to_string();
@@ -1454,8 +1638,8 @@ impl VerylaGrammarTrait for CreateSymbolTable<'_> {
 
             let attrs = attribute_table::get(&arg.embed.embed_token.token);
             for attr in attrs {
-                if let Attr::Test(x, y) = attr {
-                    test_attr = Some((x, y));
+                if let Attr::Test(x, y, z) = attr {
+                    test_attr = Some((x, y, z));
                 }
             }
 
@@ -1466,7 +1650,7 @@ impl VerylaGrammarTrait for CreateSymbolTable<'_> {
                 _ => None,
             };
 
-            if let (Some((token, top)), Some(r#type)) = (test_attr, r#type) {
+            if let (Some((token, top, sweep)), Some(r#type)) = (test_attr, r#type) {
                 let path = if let TokenSource::File(x) = content.source {
                     x
                 } else {
@@ -1478,7 +1662,12 @@ impl VerylaGrammarTrait for CreateSymbolTable<'_> {
                         .push(AnalyzerError::invalid_test("`cocotb` test requires top entity name at the second argument of `#[test]` attribute", self.text, &token.into()));
                 }
 
-                let property = TestProperty { r#type, path, top };
+                let property = TestProperty {
+                    r#type,
+                    path,
+                    top,
+                    sweep,
+                };
                 self.insert_symbol(&token, SymbolKind::Test(property), false);
             }
         }
@@ -1492,8 +1681,8 @@ impl VerylaGrammarTrait for CreateSymbolTable<'_> {
 
             let attrs = attribute_table::get(&arg.include.include_token.token);
             for attr in attrs {
-                if let Attr::Test(x, y) = attr {
-                    test_attr = Some((x, y));
+                if let Attr::Test(x, y, z) = attr {
+                    test_attr = Some((x, y, z));
                 }
             }
 
@@ -1504,7 +1693,7 @@ impl VerylaGrammarTrait for CreateSymbolTable<'_> {
                 _ => None,
             };
 
-            if let (Some((token, top)), Some(r#type)) = (test_attr, r#type) {
+            if let (Some((token, top, sweep)), Some(r#type)) = (test_attr, r#type) {
                 let path = if let TokenSource::File(x) = content.source {
                     x
                 } else {
@@ -1516,7 +1705,12 @@ impl VerylaGrammarTrait for CreateSymbolTable<'_> {
                         .push(AnalyzerError::invalid_test("`cocotb` test requires top entity name at the second argument of `#[test]` attribute", self.text, &token.into()));
                 }
 
-                let property = TestProperty { r#type, path, top };
+                let property = TestProperty {
+                    r#type,
+                    path,
+                    top,
+                    sweep,
+                };
                 self.insert_symbol(&token, SymbolKind::Test(property), false);
             }
         }
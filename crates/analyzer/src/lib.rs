@@ -2,11 +2,16 @@ pub mod analyzer;
 pub mod analyzer_error;
 pub mod attribute;
 pub mod attribute_table;
+pub mod case_coverage;
+pub mod comb_graph;
+pub mod distribution;
+pub mod elaborator;
 pub mod evaluator;
 pub mod handlers;
 pub mod msb_table;
 pub mod namespace;
 pub mod namespace_table;
+pub mod power_domain_table;
 pub mod range_table;
 pub mod symbol;
 pub mod symbol_path;
@@ -16,6 +21,6 @@ pub mod r#unsafe;
 pub mod unsafe_table;
 pub mod var_ref;
 pub use analyzer::Analyzer;
-pub use analyzer_error::AnalyzerError;
+pub use analyzer_error::{AnalyzerError, TextEdit};
 #[cfg(test)]
 mod tests;
@@ -0,0 +1,37 @@
+use crate::symbol::{PowerDomain, SymbolId};
+use std::cell::RefCell;
+use std::collections::HashMap;
+
+/// Power domains inferred for variables and functions without explicit domain
+#[derive(Clone, Default, Debug)]
+pub struct PowerDomainTable {
+    table: HashMap<SymbolId, PowerDomain>,
+}
+
+impl PowerDomainTable {
+    pub fn insert(&mut self, id: SymbolId, power_domain: PowerDomain) {
+        self.table.insert(id, power_domain);
+    }
+
+    pub fn get(&self, id: SymbolId) -> Option<&PowerDomain> {
+        self.table.get(&id)
+    }
+
+    pub fn clear(&mut self) {
+        self.table.clear()
+    }
+}
+
+thread_local!(static POWER_DOMAIN_TABLE: RefCell<PowerDomainTable> = RefCell::new(PowerDomainTable::default()));
+
+pub fn insert(id: SymbolId, power_domain: PowerDomain) {
+    POWER_DOMAIN_TABLE.with(|f| f.borrow_mut().insert(id, power_domain))
+}
+
+pub fn get(id: SymbolId) -> Option<PowerDomain> {
+    POWER_DOMAIN_TABLE.with(|f| f.borrow().get(id).cloned())
+}
+
+pub fn clear() {
+    POWER_DOMAIN_TABLE.with(|f| f.borrow_mut().clear())
+}
@@ -1,4 +1,4 @@
-use crate::attribute::EnumEncodingItem;
+use crate::attribute::{EnumEncodingItem, SweepItem};
 use crate::evaluator::{Evaluated, Evaluator};
 use crate::namespace::Namespace;
 use crate::symbol_path::{GenericSymbolPath, SymbolPath};
@@ -6,6 +6,7 @@ use crate::symbol_table;
 use std::cell::{Cell, RefCell};
 use std::collections::HashMap;
 use std::fmt;
+use std::rc::Rc;
 use veryla_parser::resource_table::{PathId, StrId};
 use veryla_parser::veryla_grammar_trait as syntax_tree;
 use veryla_parser::veryla_token::{Token, TokenRange, VerylaToken};
@@ -131,7 +132,7 @@ impl Symbol {
                             TypeKind::EnableLow => Evaluated::EnableLow,
                             _ => unreachable!(),
                         }
-                    } else if let Some(width) = evaluator.type_width(x.r#type.clone()) {
+                    } else if let Some(width) = evaluator.packed_width(&x.r#type, &self.namespace) {
                         if x.loop_variable {
                             Evaluated::UnknownStatic
                         } else {
@@ -158,14 +159,34 @@ impl Symbol {
                 }
                 SymbolKind::Parameter(x) => {
                     let mut evaluator = Evaluator::new();
-                    if let Some(width) = evaluator.type_width(x.r#type.clone()) {
+                    if let Some(width) = evaluator.packed_width(&x.r#type, &self.namespace) {
                         evaluator.context_width.push(width);
                     }
+                    evaluator.real_context = x.r#type.kind.is_real();
                     evaluator.expression(&x.value)
                 }
-                SymbolKind::EnumMember(_) => {
-                    // TODO: Actually Evaluate its Width
-                    Evaluated::UnknownStatic
+                SymbolKind::EnumMember(x) => {
+                    let width = match self.get_parent().map(|x| x.kind) {
+                        Some(SymbolKind::Enum(x)) => Some(x.width),
+                        _ => None,
+                    };
+                    let value = match &x.value {
+                        EnumMemberValue::ExplicitValue(x, None) => {
+                            let mut evaluator = Evaluator::new();
+                            if let Some(width) = width {
+                                evaluator.context_width.push(width);
+                            }
+                            match evaluator.expression(x) {
+                                Evaluated::Fixed { value, .. } => Some(value),
+                                _ => None,
+                            }
+                        }
+                        x => x.value().and_then(|x| isize::try_from(x).ok()),
+                    };
+                    match (width, value) {
+                        (Some(width), Some(value)) => Evaluated::Fixed { width, value },
+                        _ => Evaluated::UnknownStatic,
+                    }
                 }
                 SymbolKind::Genvar => Evaluated::UnknownStatic,
                 _ => Evaluated::Unknown,
@@ -305,11 +326,16 @@ pub enum SymbolKind {
     Entity(EntityProperty),
     ProtoEntity(ProtoEntityProperty),
     Interface(InterfaceProperty),
+    ProtoInterface(ProtoInterfaceProperty),
     Function(FunctionProperty),
     Parameter(ParameterProperty),
     Instance(InstanceProperty),
     Block,
     Package(PackageProperty),
+    ProtoPackage(ProtoPackageProperty),
+    ProtoConst(ProtoConstProperty),
+    ProtoTypeDef,
+    ProtoFunction(ProtoFunctionProperty),
     Struct(StructProperty),
     StructMember(StructMemberProperty),
     Union(UnionProperty),
@@ -343,11 +369,16 @@ impl SymbolKind {
             SymbolKind::Entity(_) => "entity".to_string(),
             SymbolKind::ProtoEntity(_) => "proto entity".to_string(),
             SymbolKind::Interface(_) => "interface".to_string(),
+            SymbolKind::ProtoInterface(_) => "proto interface".to_string(),
             SymbolKind::Function(_) => "function".to_string(),
             SymbolKind::Parameter(_) => "parameter".to_string(),
             SymbolKind::Instance(_) => "instance".to_string(),
             SymbolKind::Block => "block".to_string(),
             SymbolKind::Package(_) => "package".to_string(),
+            SymbolKind::ProtoPackage(_) => "proto package".to_string(),
+            SymbolKind::ProtoConst(_) => "proto const".to_string(),
+            SymbolKind::ProtoTypeDef => "proto typedef".to_string(),
+            SymbolKind::ProtoFunction(_) => "proto function".to_string(),
             SymbolKind::Struct(_) => "struct".to_string(),
             SymbolKind::StructMember(_) => "struct member".to_string(),
             SymbolKind::Union(_) => "union".to_string(),
@@ -418,6 +449,8 @@ impl SymbolKind {
     pub fn proto(&self) -> Option<SymbolPath> {
         match self {
             SymbolKind::Entity(x) => x.proto.clone(),
+            SymbolKind::Interface(x) => x.proto.clone(),
+            SymbolKind::Package(x) => x.proto.clone(),
             SymbolKind::GenericParameter(x) => match x.bound {
                 GenericBoundKind::Proto(ref x) => Some(x.clone()),
                 _ => None,
@@ -462,6 +495,13 @@ impl fmt::Display for SymbolKind {
                     x.parameters.len()
                 )
             }
+            SymbolKind::ProtoInterface(x) => {
+                format!(
+                    "proto interface ({} params, {} members)",
+                    x.parameters.len(),
+                    x.members.len()
+                )
+            }
             SymbolKind::Function(x) => {
                 format!(
                     "function ({} generic, {} args)",
@@ -489,6 +529,16 @@ impl fmt::Display for SymbolKind {
             SymbolKind::Package(x) => {
                 format!("package ({} generic)", x.generic_parameters.len())
             }
+            SymbolKind::ProtoPackage(x) => {
+                format!("proto package ({} members)", x.members.len())
+            }
+            SymbolKind::ProtoConst(x) => {
+                format!("proto localparam ({})", x.r#type)
+            }
+            SymbolKind::ProtoTypeDef => "proto typedef".to_string(),
+            SymbolKind::ProtoFunction(x) => {
+                format!("proto function ({} args)", x.ports.len())
+            }
             SymbolKind::Struct(_) => "struct".to_string(),
             SymbolKind::StructMember(x) => {
                 format!("struct member ({})", x.r#type)
@@ -623,6 +673,10 @@ impl TypeKind {
                 | TypeKind::EnableLow
         )
     }
+
+    pub fn is_real(&self) -> bool {
+        matches!(self, TypeKind::F32 | TypeKind::F64)
+    }
 }
 
 #[derive(Debug, Clone, PartialEq, Eq)]
@@ -1101,10 +1155,12 @@ pub struct ProtoEntityProperty {
 pub enum ProtoIncompatible {
     MissingParam(StrId),
     MissingPort(StrId),
+    MissingMember(StrId),
     UnnecessaryParam(StrId),
     UnnecessaryPort(StrId),
     IncompatibleParam(StrId),
     IncompatiblePort(StrId),
+    IncompatibleMember(StrId),
 }
 
 impl ProtoEntityProperty {
@@ -1164,11 +1220,142 @@ impl ProtoEntityProperty {
 #[derive(Debug, Clone)]
 pub struct InterfaceProperty {
     pub range: TokenRange,
+    pub proto: Option<SymbolPath>,
     pub generic_parameters: Vec<SymbolId>,
     pub generic_references: Vec<GenericSymbolPath>,
     pub parameters: Vec<Parameter>,
 }
 
+#[derive(Debug, Clone)]
+pub struct ProtoInterfaceProperty {
+    pub range: TokenRange,
+    pub parameters: Vec<Parameter>,
+    pub members: Vec<SymbolId>,
+}
+
+impl ProtoInterfaceProperty {
+    pub fn check_compat(&self, p: &InterfaceProperty, symbol: &Symbol) -> Vec<ProtoIncompatible> {
+        let mut ret = Vec::new();
+
+        let actual_params: HashMap<_, _> = p
+            .parameters
+            .iter()
+            .map(|x| (x.name, x.property()))
+            .collect();
+        let mut proto_params: HashMap<_, _> = self
+            .parameters
+            .iter()
+            .map(|x| (x.name, x.property()))
+            .collect();
+
+        for (name, actual_param) in actual_params {
+            if let Some(proto_param) = proto_params.remove(&name) {
+                if proto_param.r#type.to_string() != actual_param.r#type.to_string() {
+                    ret.push(ProtoIncompatible::IncompatibleParam(name));
+                }
+            } else {
+                ret.push(ProtoIncompatible::UnnecessaryParam(name));
+            }
+        }
+        for (name, _) in proto_params {
+            ret.push(ProtoIncompatible::MissingParam(name));
+        }
+
+        ret.append(&mut check_member_compat(&self.members, symbol));
+        ret
+    }
+}
+
+#[derive(Debug, Clone)]
+pub struct ProtoPackageProperty {
+    pub range: TokenRange,
+    pub members: Vec<SymbolId>,
+}
+
+impl ProtoPackageProperty {
+    pub fn check_compat(&self, symbol: &Symbol) -> Vec<ProtoIncompatible> {
+        check_member_compat(&self.members, symbol)
+    }
+}
+
+#[derive(Debug, Clone)]
+pub struct ProtoConstProperty {
+    pub token: Token,
+    pub r#type: Type,
+}
+
+#[derive(Debug, Clone)]
+pub struct ProtoFunctionProperty {
+    pub range: TokenRange,
+    pub ports: Vec<Port>,
+    pub ret: Option<Type>,
+}
+
+fn check_member_compat(members: &[SymbolId], symbol: &Symbol) -> Vec<ProtoIncompatible> {
+    let mut ret = Vec::new();
+
+    for id in members {
+        let Some(member) = symbol_table::get(*id) else {
+            continue;
+        };
+        let name = member.token.text;
+        let path = SymbolPath::new(&[symbol.token.text, name]);
+        let Ok(actual) = symbol_table::resolve((&path, &symbol.namespace)) else {
+            ret.push(ProtoIncompatible::MissingMember(name));
+            continue;
+        };
+        let actual = actual.found;
+
+        let compatible = match (&member.kind, &actual.kind) {
+            (SymbolKind::Variable(x), SymbolKind::Variable(y)) => {
+                x.r#type.to_string() == y.r#type.to_string()
+            }
+            (SymbolKind::ProtoConst(x), SymbolKind::Parameter(y)) => {
+                x.r#type.to_string() == y.r#type.to_string()
+            }
+            (SymbolKind::ProtoTypeDef, kind) => matches!(
+                kind,
+                SymbolKind::TypeDef(_)
+                    | SymbolKind::Struct(_)
+                    | SymbolKind::Union(_)
+                    | SymbolKind::Enum(_)
+            ),
+            (SymbolKind::ProtoFunction(x), SymbolKind::Function(y)) => {
+                is_compatible_ports(&x.ports, &y.ports)
+                    && x.ret.as_ref().map(|x| x.to_string())
+                        == y.ret.as_ref().map(|x| x.to_string())
+            }
+            (SymbolKind::Modport(x), SymbolKind::Modport(y)) => {
+                let members = |ids: &[SymbolId]| -> HashMap<StrId, String> {
+                    ids.iter()
+                        .filter_map(|x| symbol_table::get(*x))
+                        .map(|x| (x.token.text, x.kind.to_kind_name()))
+                        .collect()
+                };
+                members(&x.members) == members(&y.members)
+            }
+            _ => false,
+        };
+
+        if !compatible {
+            ret.push(ProtoIncompatible::IncompatibleMember(name));
+        }
+    }
+
+    ret
+}
+
+fn is_compatible_ports(x: &[Port], y: &[Port]) -> bool {
+    x.len() == y.len()
+        && x.iter().zip(y.iter()).all(|(x, y)| {
+            let x = x.property();
+            let y = y.property();
+            x.token.text == y.token.text
+                && x.direction == y.direction
+                && x.r#type.map(|x| x.to_string()) == y.r#type.map(|x| x.to_string())
+        })
+}
+
 #[derive(Debug, Clone)]
 pub struct FunctionProperty {
     pub range: TokenRange,
@@ -1176,6 +1363,7 @@ pub struct FunctionProperty {
     pub generic_references: Vec<GenericSymbolPath>,
     pub ports: Vec<Port>,
     pub ret: Option<Type>,
+    pub body: Rc<syntax_tree::StatementBlock>,
 }
 
 #[derive(Debug, Clone)]
@@ -1237,6 +1425,7 @@ pub struct InstanceProperty {
 #[derive(Debug, Clone)]
 pub struct PackageProperty {
     pub range: TokenRange,
+    pub proto: Option<SymbolPath>,
     pub generic_parameters: Vec<SymbolId>,
     pub generic_references: Vec<GenericSymbolPath>,
 }
@@ -1358,4 +1547,5 @@ pub struct TestProperty {
     pub r#type: TestType,
     pub path: PathId,
     pub top: Option<StrId>,
+    pub sweep: Vec<SweepItem>,
 }
@@ -1,3 +1,5 @@
+use crate::comb_graph::CombDependency;
+use crate::distribution::DEFINED_DISTRIBUTIONS;
 use crate::evaluator::Evaluated;
 use crate::namespace::Namespace;
 use crate::symbol::{DocComment, Symbol, SymbolId, SymbolKind, TypeKind};
@@ -50,6 +52,7 @@ pub struct SymbolTable {
     symbol_table: HashMap<SymbolId, Symbol>,
     project_local_table: HashMap<StrId, HashMap<StrId, StrId>>,
     var_ref_list: HashMap<VarRefAffiliation, Vec<VarRef>>,
+    comb_dependency_list: HashMap<SymbolId, Vec<CombDependency>>,
     import_list: Vec<Import>,
 }
 
@@ -72,7 +75,7 @@ impl SymbolTable {
             let _ = ret.insert(&token, symbol);
         }
 
-        for func in DEFINED_SYSTEM_FUNCTIONS {
+        for func in DEFINED_SYSTEM_FUNCTIONS.iter().chain(DEFINED_DISTRIBUTIONS.iter()) {
             let token = Token::new(func, 0, 0, 0, 0, TokenSource::Builtin);
             let symbol = Symbol::new(
                 &token,
@@ -242,7 +245,11 @@ impl SymbolTable {
                             context.namespace = found.inner_namespace();
                             context.inner = true;
                         }
-                        SymbolKind::Enum(_) | SymbolKind::SystemVerilog | SymbolKind::Namespace => {
+                        SymbolKind::Enum(_)
+                        | SymbolKind::ProtoInterface(_)
+                        | SymbolKind::ProtoPackage(_)
+                        | SymbolKind::SystemVerilog
+                        | SymbolKind::Namespace => {
                             context.namespace = found.inner_namespace();
                             context.inner = true;
                         }
@@ -275,6 +282,9 @@ impl SymbolTable {
                         // don't trace inner item
                         SymbolKind::Function(_)
                         | SymbolKind::ProtoEntity(_)
+                        | SymbolKind::ProtoConst(_)
+                        | SymbolKind::ProtoTypeDef
+                        | SymbolKind::ProtoFunction(_)
                         | SymbolKind::Struct(_)
                         | SymbolKind::Union(_)
                         | SymbolKind::Modport(_)
@@ -479,6 +489,17 @@ impl SymbolTable {
             .collect()
     }
 
+    pub fn add_comb_dependency(&mut self, entity: SymbolId, dependency: &CombDependency) {
+        self.comb_dependency_list
+            .entry(entity)
+            .and_modify(|x| x.push(dependency.clone()))
+            .or_insert(vec![dependency.clone()]);
+    }
+
+    pub fn get_comb_dependency_list(&self) -> HashMap<SymbolId, Vec<CombDependency>> {
+        self.comb_dependency_list.clone()
+    }
+
     pub fn clear(&mut self) {
         self.clone_from(&Self::new());
     }
@@ -563,7 +584,7 @@ impl ResolveContext<'_> {
     }
 }
 
-const DEFINED_NAMESPACES: [&str; 2] = ["$sv", "$std"];
+const DEFINED_NAMESPACES: [&str; 3] = ["$sv", "$std", "$corner"];
 
 // Refer IEEE Std 1800-2023 Table B.1 - Reserved keywords
 // This list must be sorted to enable binary search
@@ -1094,6 +1115,14 @@ pub fn get_assign_list() -> Vec<Assign> {
     SYMBOL_TABLE.with(|f| f.borrow_mut().get_assign_list())
 }
 
+pub fn add_comb_dependency(entity: SymbolId, dependency: &CombDependency) {
+    SYMBOL_TABLE.with(|f| f.borrow_mut().add_comb_dependency(entity, dependency))
+}
+
+pub fn get_comb_dependency_list() -> HashMap<SymbolId, Vec<CombDependency>> {
+    SYMBOL_TABLE.with(|f| f.borrow().get_comb_dependency_list())
+}
+
 pub fn clear() {
     SYMBOL_TABLE.with(|f| f.borrow_mut().clear())
 }
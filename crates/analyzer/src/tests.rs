@@ -1,10 +1,13 @@
-use crate::{symbol_table, Analyzer, AnalyzerError};
+use crate::evaluator::Evaluated;
+use crate::symbol::SymbolKind;
+use crate::{attribute_table, power_domain_table, symbol_table, Analyzer, AnalyzerError, TextEdit};
 use veryla_metadata::Metadata;
-use veryla_parser::Parser;
+use veryla_parser::{resource_table, Parser};
 
 #[track_caller]
 fn analyze(code: &str) -> Vec<AnalyzerError> {
     symbol_table::clear();
+    attribute_table::clear();
 
     let metadata: Metadata =
         toml::from_str(&Metadata::create_default_toml("prj").unwrap()).unwrap();
@@ -16,6 +19,7 @@ fn analyze(code: &str) -> Vec<AnalyzerError> {
     Analyzer::analyze_post_pass1();
     errors.append(&mut analyzer.analyze_pass2(&"prj", &code, &"", &parser.veryla));
     errors.append(&mut analyzer.analyze_pass3(&"prj", &code, &"", &parser.veryla));
+    errors.append(&mut analyzer.analyze_elaboration("prj", &[(code, &parser.veryla)]));
     dbg!(&errors);
     errors
 }
@@ -497,6 +501,44 @@ fn invalid_allow() {
     assert!(matches!(errors[0], AnalyzerError::InvalidAllow { .. }));
 }
 
+#[test]
+fn allow_rule() {
+    let code = r#"
+    pub entity EntityA of component {
+        function FuncA () -> logic {
+            return 1;
+        }
+
+        #[allow(unused_return)]
+        initial {
+            FuncA();
+        }
+
+        initial {
+            FuncA();
+        }
+    }
+    "#;
+
+    let errors = analyze(code);
+    assert_eq!(errors.len(), 1);
+    assert!(matches!(errors[0], AnalyzerError::UnusedReturn { .. }));
+
+    symbol_table::clear();
+    let toml = format!(
+        "{}\n[lint.rules]\nunused_return = \"allow\"\n",
+        Metadata::create_default_toml("prj").unwrap()
+    );
+    let metadata: Metadata = toml::from_str(&toml).unwrap();
+    let parser = Parser::parse(code, &"").unwrap();
+    let analyzer = Analyzer::new(&metadata);
+    let mut errors = analyzer.analyze_pass1("prj", code, "", &parser.veryla);
+    Analyzer::analyze_post_pass1();
+    errors.append(&mut analyzer.analyze_pass2("prj", code, "", &parser.veryla));
+    errors.append(&mut analyzer.analyze_pass3("prj", code, "", &parser.veryla));
+    assert!(errors.is_empty());
+}
+
 #[test]
 fn invalid_assignment() {
     let code = r#"
@@ -1253,6 +1295,101 @@ fn mismatch_type() {
     assert!(errors.is_empty());
 }
 
+#[test]
+fn incompat_proto() {
+    let code = r#"
+    proto package ProtoA {
+        const WIDTH: u32;
+        type data_t;
+        function f (a: input logic) -> logic;
+    }
+
+    pub package PackageA for ProtoA {
+        const WIDTH: u32 = 8;
+        type data_t = logic<WIDTH>;
+        function f (a: input logic) -> logic {
+            return a;
+        }
+    }
+
+    proto interface ProtoB {
+        var a: logic;
+        modport mp {
+            a: input,
+        }
+    }
+
+    interface InterfaceB for ProtoB {
+        var a: logic;
+        modport mp {
+            a: input,
+        }
+    }
+
+    pub entity EntityC of component::<P: ProtoA, I: ProtoB> (
+        i_a: input  logic<P::WIDTH>,
+        o_b: output logic<P::WIDTH>,
+    ) {
+        inst u: I;
+        assign o_b = i_a;
+    }
+
+    pub entity EntityD of component (
+        i_a: input  logic<8>,
+        o_b: output logic<8>,
+    ) {
+        inst u: EntityC::<PackageA, InterfaceB> (
+            i_a,
+            o_b,
+        );
+    }
+    "#;
+
+    let errors = analyze(code);
+    assert!(errors.is_empty());
+
+    let code = r#"
+    proto package ProtoA {
+        const WIDTH: u32;
+        type data_t;
+    }
+
+    pub package PackageA for ProtoA {
+        const WIDTH: bit = 1;
+    }
+    "#;
+
+    let errors = analyze(code);
+    assert_eq!(errors.len(), 2);
+    assert!(errors
+        .iter()
+        .all(|x| matches!(x, AnalyzerError::IncompatProto { .. })));
+
+    let code = r#"
+    proto interface ProtoB {
+        var a: logic;
+        var b: logic;
+    }
+
+    interface InterfaceB for ProtoB {
+        var a: logic<2>;
+        var b: logic;
+    }
+    "#;
+
+    let errors = analyze(code);
+    assert!(matches!(errors[0], AnalyzerError::IncompatProto { .. }));
+
+    let code = r#"
+    proto interface ProtoC {}
+
+    pub package PackageC for ProtoC {}
+    "#;
+
+    let errors = analyze(code);
+    assert!(matches!(errors[0], AnalyzerError::MismatchType { .. }));
+}
+
 #[test]
 fn missing_if_enable() {
     let code = r#"
@@ -1521,6 +1658,64 @@ fn missing_enable_statement() {
     ));
 }
 
+#[test]
+fn incomplete_enable_initialisation() {
+    let code = r#"
+    entity EntityA of component (
+        pwr: input power,
+        en: input enable,
+        o_c: output logic,
+    ) {
+        var a: logic;
+        var b: logic;
+
+        sequence (pwr, en) {
+            if_enable {
+                a = 0;
+            } else {
+                a = 1;
+                b = 1;
+            }
+            o_c = a;
+        }
+    }
+    "#;
+
+    let errors = analyze(code);
+    let names: Vec<_> = errors
+        .iter()
+        .filter_map(|x| match x {
+            AnalyzerError::IncompleteEnableInitialisation { names, .. } => Some(names.as_str()),
+            _ => None,
+        })
+        .collect();
+    assert_eq!(names, ["b, o_c"]);
+
+    let code = r#"
+    entity EntityB of component (
+        pwr: input power,
+        en: input enable,
+        o_c: output logic,
+    ) {
+        var a: logic;
+
+        sequence (pwr, en) {
+            #[allow(missing_enable_statement)]
+            if_enable {
+            } else {
+                a = 1;
+            }
+            o_c = a;
+        }
+    }
+    "#;
+
+    let errors = analyze(code);
+    assert!(!errors
+        .iter()
+        .any(|x| matches!(x, AnalyzerError::IncompleteEnableInitialisation { .. })));
+}
+
 #[test]
 fn missing_tri() {
     let code = r#"
@@ -2018,6 +2213,102 @@ fn unused_variable() {
     assert!(matches!(errors[0], AnalyzerError::UnusedVariable { .. }));
 }
 
+#[test]
+fn unused_items() {
+    let code = r#"
+    pub entity EntityA of component #(
+        param N: u32 = 1,
+        param _M: u32 = 1,
+    ) (
+        i_a: input logic,
+        _i_b: input logic,
+        o_c: output logic,
+    ) {
+        function FuncA () -> logic {
+            return 1;
+        }
+        assign o_c = 0;
+    }
+    "#;
+
+    let errors = analyze(code);
+    assert_eq!(errors.len(), 3);
+    assert!(matches!(errors[0], AnalyzerError::UnusedParam { .. }));
+    assert!(matches!(errors[1], AnalyzerError::UnusedPort { .. }));
+    assert!(matches!(errors[2], AnalyzerError::UnusedFunction { .. }));
+
+    let code = r#"
+    package PackageA {
+        const X: u32 = 1;
+    }
+
+    pub package PackageB {
+        const X: u32 = 1;
+    }
+
+    entity EntityB of component {}
+
+    pub entity EntityC of component {}
+    "#;
+
+    let errors = analyze(code);
+    assert_eq!(errors.len(), 2);
+    assert!(matches!(errors[0], AnalyzerError::UnusedPackage { .. }));
+    assert!(matches!(errors[1], AnalyzerError::UnusedEntity { .. }));
+
+    symbol_table::clear();
+    let toml = format!(
+        "{}\n[lint.rules]\nunused_entity = \"allow\"\nunused_package = \"allow\"\n",
+        Metadata::create_default_toml("prj").unwrap()
+    );
+    let metadata: Metadata = toml::from_str(&toml).unwrap();
+    let parser = Parser::parse(code, &"").unwrap();
+    let analyzer = Analyzer::new(&metadata);
+    let mut errors = analyzer.analyze_pass1("prj", code, "", &parser.veryla);
+    Analyzer::analyze_post_pass1();
+    errors.append(&mut analyzer.analyze_pass2("prj", code, "", &parser.veryla));
+    errors.append(&mut analyzer.analyze_pass3("prj", code, "", &parser.veryla));
+    assert!(errors.is_empty());
+}
+
+#[test]
+fn fixes() {
+    let code = r#"
+    pub entity EntityA of component (
+        x: inout logic,
+    ) {
+        let a: logic = 1;
+        var i_a: logic;
+        var i_b: logic;
+        inst u: EntityB;
+        assign i_a = 0;
+        assign i_b = 0;
+    }
+
+    pub entity EntityB of component (
+        i_a: input logic,
+        i_b: input logic,
+    ) {}
+    "#;
+
+    let errors = analyze(code);
+    assert_eq!(errors.len(), 4);
+    let fixes: Vec<_> = errors.iter().map(|x| x.fixes()).collect();
+    let (code, count) = TextEdit::apply(code, &fixes);
+    // Missing ports i_a and i_b are reported separately with the same fix
+    assert_eq!(count, 3);
+    assert!(code.contains("x: inout tri logic"));
+    assert!(code.contains("let _a: logic = 1;"));
+    assert!(code.contains("inst u: EntityB (i_a, i_b);"));
+
+    let errors = analyze(&code);
+    assert!(errors.is_empty());
+
+    let edits = [TextEdit::insert(0, "a"), TextEdit::insert(0, "b")];
+    let fixes = [&edits[0..1], &edits[1..2], &edits[0..1]];
+    assert_eq!(TextEdit::apply("c", &fixes), ("ac".to_string(), 1));
+}
+
 #[test]
 fn unused_return() {
     let code = r#"
@@ -2293,7 +2584,7 @@ fn unassign_variable() {
 #[test]
 fn uncovered_branch() {
     let code = r#"
-    entity EntityA {
+    pub entity EntityA of component {
         var a: logic;
         var b: logic;
         let x: logic = 1;
@@ -2323,7 +2614,7 @@ fn uncovered_branch() {
     assert!(errors.is_empty());
 
     let code = r#"
-    entity EntityB {
+    pub entity EntityB of component {
         var a: logic;
         let x: logic = 1;
 
@@ -2336,7 +2627,7 @@ fn uncovered_branch() {
     "#;
 
     let errors = analyze(code);
-    assert!(matches!(errors[0], AnalyzerError::UncoveredBranch { .. }));
+    assert!(matches!(errors[..], [AnalyzerError::LatchInferred { .. }]));
 }
 
 #[test]
@@ -2703,6 +2994,130 @@ fn enum_non_const_exception() {
     assert!(errors.is_empty());
 }
 
+#[test]
+fn case_coverage() {
+    let code = r#"
+    entity EntityA of component (
+        i_sel: input  logic<2>,
+        i_a  : input  logic<4>,
+        o_b  : output logic,
+    ) {
+        always_comb {
+            case i_sel {
+                2'd0: o_b = i_a[0];
+                2'd1: o_b = i_a[1];
+            }
+        }
+    }
+    "#;
+
+    let errors = analyze(code);
+    let error = errors
+        .iter()
+        .find(|x| matches!(x, AnalyzerError::NonExhaustiveCase { .. }))
+        .unwrap();
+    assert_eq!(
+        error.to_string(),
+        "case doesn't cover all values: 2'b10, 2'b11"
+    );
+
+    let code = r#"
+    entity EntityB of component (
+        i_sel: input  logic<2>,
+        i_a  : input  logic<4>,
+        o_b  : output logic,
+        o_c  : output logic,
+    ) {
+        always_comb {
+            case i_sel {
+                0..2 : o_b = i_a[0];
+                2'b1x: o_b = i_a[1];
+                2'd3 : o_b = i_a[2];
+            }
+        }
+
+        assign o_c = switch {
+            1         : i_a[0],
+            i_sel == 0: i_a[1],
+            default   : i_a[2],
+        };
+    }
+    "#;
+
+    let errors = analyze(code);
+    let unreachable = errors
+        .iter()
+        .filter(|x| matches!(x, AnalyzerError::UnreachableCaseArm { .. }))
+        .count();
+    assert_eq!(unreachable, 2);
+    assert!(!errors
+        .iter()
+        .any(|x| matches!(x, AnalyzerError::NonExhaustiveCase { .. })));
+
+    let code = r#"
+    entity EntityC of component (
+        i_sel: input  logic,
+        i_a  : input  logic<4>,
+        o_b  : output logic,
+    ) {
+        enum EnumA: logic<2> {
+            X,
+            Y,
+            Z,
+        }
+
+        var s: EnumA;
+
+        assign s = switch {
+            i_sel  : EnumA::X,
+            default: EnumA::Z,
+        };
+
+        always_comb {
+            case s {
+                EnumA::X: o_b = i_a[0];
+                EnumA::Y: o_b = i_a[1];
+            }
+        }
+    }
+    "#;
+
+    let errors = analyze(code);
+    let error = errors
+        .iter()
+        .find(|x| matches!(x, AnalyzerError::NonExhaustiveCase { .. }))
+        .unwrap();
+    assert_eq!(error.to_string(), "case doesn't cover all values: EnumA::Z");
+
+    let code = r#"
+    entity EntityD of component (
+        i_sel: input  logic<2>,
+        i_a  : input  logic<4>,
+        o_b  : output logic,
+    ) {
+        always_comb {
+            case i_sel {
+                3'd4            : o_b = i_a[0];
+                3'b1x1          : o_b = i_a[1];
+                2'd1, 2'd2, 2'd3: o_b = i_a[2];
+            }
+        }
+    }
+    "#;
+
+    let errors = analyze(code);
+    let unreachable = errors
+        .iter()
+        .filter(|x| matches!(x, AnalyzerError::UnreachableCaseArm { .. }))
+        .count();
+    assert_eq!(unreachable, 2);
+    let error = errors
+        .iter()
+        .find(|x| matches!(x, AnalyzerError::NonExhaustiveCase { .. }))
+        .unwrap();
+    assert_eq!(error.to_string(), "case doesn't cover all values: 2'b00");
+}
+
 #[test]
 fn invalid_case_condition_expression() {
     let code = r#"
@@ -2854,101 +3269,1201 @@ fn invalid_test() {
 }
 
 #[test]
-fn power_domain() {
+fn test_sweep() {
     let code = r#"
-    entity EntityA (
-        i_pwr: input  `a power,
-        i_dat: input  `a logic,
-        o_dat: output `b logic,
-    ) {
-        assign o_dat = i_dat;
-    }
+    pub entity EntityA of component {}
+
+    #[test(TestA, sweep(R = [1k, 10k], C = [1n, 2.5p]))]
+    embed (inline) sv {{{
+    }}}
     "#;
 
     let errors = analyze(code);
-    assert!(matches!(
-        errors[0],
-        AnalyzerError::MismatchPowerDomain { .. }
-    ));
+    assert!(errors.is_empty());
+
+    let test = symbol_table::get_all()
+        .into_iter()
+        .find_map(|x| {
+            if let SymbolKind::Test(x) = x.kind {
+                Some(x)
+            } else {
+                None
+            }
+        })
+        .unwrap();
+    let values: Vec<_> = test.sweep.iter().map(|x| x.values.clone()).collect();
+    assert_eq!(
+        values,
+        vec![
+            vec!["1000", "10000"],
+            vec!["0.000000001", "0.0000000000025"]
+        ]
+    );
+
+    let code = r#"
+    pub entity EntityA of component {}
+
+    #[test(TestA, sweep(R = 1k))]
+    embed (inline) sv {{{
+    }}}
+    "#;
+
+    let errors = analyze(code);
+    assert!(matches!(
+        errors[0],
+        AnalyzerError::MismatchAttributeArgs { .. }
+    ));
+}
+
+#[test]
+fn corner_namespace() {
+    let code = r#"
+    pub entity EntityA of component (
+        o: output analog,
+    ) {
+        assign o = $corner::temperature;
+    }
+    "#;
+
+    let errors = analyze(code);
+    assert!(matches!(errors[..], [AnalyzerError::UnknownMember { .. }]));
+
+    let analyze_with_corners = |code: &str| {
+        symbol_table::clear();
+        let toml = format!(
+            "{}\n[corners.ss]\ntemperature = 125.0\nvoltages = {{vdd = 0.9}}\n",
+            Metadata::create_default_toml("prj").unwrap()
+        );
+        let metadata: Metadata = toml::from_str(&toml).unwrap();
+        let parser = Parser::parse(code, &"").unwrap();
+        let analyzer = Analyzer::new(&metadata);
+        let mut errors = analyzer.analyze_pass1("prj", code, "", &parser.veryla);
+        Analyzer::analyze_post_pass1();
+        errors.append(&mut analyzer.analyze_pass2("prj", code, "", &parser.veryla));
+        errors.append(&mut analyzer.analyze_pass3("prj", code, "", &parser.veryla));
+        errors
+    };
+
+    let errors = analyze_with_corners(code);
+    assert!(errors.is_empty());
+
+    let code = r#"
+    pub entity EntityA of component (
+        o: output analog,
+    ) {
+        assign o = $corner::vdd;
+    }
+    "#;
+
+    let errors = analyze_with_corners(code);
+    assert!(errors.is_empty());
+
+    let code = r#"
+    pub entity EntityA of component (
+        o: output analog,
+    ) {
+        assign o = $corner::vddd;
+    }
+    "#;
+
+    let errors = analyze_with_corners(code);
+    assert!(matches!(errors[..], [AnalyzerError::UnknownMember { .. }]));
+}
+
+#[test]
+fn distribution() {
+    let code = r#"
+    pub entity EntityA of component #(
+        param N: u32 = uniform(2, 6),
+        param M: u32 = uniform(2, 5),
+        param VTH: f64 = gauss(0.45, 0.01),
+    ) {}
+    "#;
+
+    let errors = analyze(code);
+    assert!(errors.is_empty());
+
+    let symbol = symbol_table::get_all()
+        .into_iter()
+        .find(|x| x.token.to_string() == "N")
+        .unwrap();
+    assert!(matches!(
+        symbol.evaluate(),
+        Evaluated::Fixed { value: 4, .. }
+    ));
+
+    // Nominal value 3.5 is rounded like the emitted parameter
+    let symbol = symbol_table::get_all()
+        .into_iter()
+        .find(|x| x.token.to_string() == "M")
+        .unwrap();
+    assert!(matches!(
+        symbol.evaluate(),
+        Evaluated::Fixed { value: 4, .. }
+    ));
+
+    // Real-typed parameter is not truncated to an integer
+    let symbol = symbol_table::get_all()
+        .into_iter()
+        .find(|x| x.token.to_string() == "VTH")
+        .unwrap();
+    assert!(matches!(symbol.evaluate(), Evaluated::UnknownStatic));
+
+    let code = r#"
+    pub entity EntityA of component #(
+        param X: f64 = 0.01,
+        param VTH: f64 = gauss(0.45, X),
+    ) {}
+    "#;
+
+    let errors = analyze(code);
+    assert!(matches!(
+        errors[0],
+        AnalyzerError::InvalidDistribution { .. }
+    ));
+
+    let code = r#"
+    pub entity EntityA of component #(
+        param VTH: f64 = gauss(0.45),
+    ) {}
+    "#;
+
+    let errors = analyze(code);
+    assert!(matches!(
+        errors[0],
+        AnalyzerError::MismatchFunctionArity { .. }
+    ));
+}
+
+#[test]
+fn elaboration() {
+    let code = r#"
+    entity Top of system #(
+        param N: u32 = 3,
+    ) (
+        pwr: input `a power,
+        din: input `a logic<8>,
+    ) {
+        inst u_a: Leaf #(W: N * 2) (pwr, i: din, o: _);
+        for i in 0..N :g_lane {
+            inst u_l: Leaf #(W: i) (pwr, i: din, o: _);
+        }
+        if N >: 2 :g_big {
+            inst u_big: Leaf (pwr, i: din, o: _);
+        } else {
+            inst u_small: Leaf (pwr, i: din, o: _);
+        }
+    }
+
+    entity Leaf of component #(
+        param W: u32 = 1,
+        param D: u32 = W + 1,
+    ) (
+        pwr: input `a power,
+        i: input `a logic<8>,
+        o: output `a logic<8>,
+    ) {
+        const BIG: u32 = W * 2;
+        if BIG >: 4 :g_wide {
+            inst u_wide: Cell (pwr);
+        } else {
+            inst u_narrow: Cell (pwr);
+        }
+        if i == 0 :g_zero {
+            inst u_zero: Cell (pwr);
+        }
+        assign o = i;
+    }
+
+    entity Cell of component (
+        pwr: input `a power,
+    ) {}
+    "#;
+
+    symbol_table::clear();
+
+    let metadata: Metadata =
+        toml::from_str(&Metadata::create_default_toml("prj").unwrap()).unwrap();
+    let parser = Parser::parse(code, &"").unwrap();
+    let analyzer = Analyzer::new(&metadata);
+    analyzer.analyze_pass1("prj", code, "", &parser.veryla);
+    Analyzer::analyze_post_pass1();
+
+    let (tree, errors) = Analyzer::elaborate("prj", &[(code, &parser.veryla)], None);
+    assert!(errors.is_empty());
+    assert_eq!(tree.tops.len(), 1);
+
+    let d = resource_table::insert_str("D");
+    let u_a = tree.find("Top.u_a").unwrap();
+    assert!(matches!(
+        u_a.parameter(d),
+        Some(Evaluated::Fixed { value: 7, .. })
+    ));
+
+    let u_l = tree.find("Top.g_lane[2].u_l").unwrap();
+    assert!(matches!(
+        u_l.parameter(d),
+        Some(Evaluated::Fixed { value: 3, .. })
+    ));
+
+    assert!(tree.find("Top.g_big.u_big").is_some());
+    assert!(tree.find("Top.u_small").is_none());
+
+    // Constants are evaluated with the parameters of each instance
+    assert!(tree.find("Top.u_a.g_wide.u_wide").is_some());
+    assert!(tree.find("Top.u_a.u_narrow").is_none());
+    assert!(tree.find("Top.g_big.u_big.g_wide").is_none());
+    assert!(tree.find("Top.g_big.u_big.u_narrow").is_some());
+
+    // Condition depending on a port is not elaborated
+    assert!(tree.find("Top.u_a.g_zero.u_zero").is_none());
+    assert_eq!(u_a.unresolved, vec!["Top.u_a.g_zero".to_string()]);
+}
+
+#[test]
+fn elaboration_generic() {
+    let code = r#"
+    proto entity of component ProtoCell;
+
+    entity Top of system {
+        inst u_a: Mid::<3, CellA>();
+        inst u_b: Mid::<1, CellB>();
+    }
+
+    entity Mid of component ::<W: const, T: ProtoCell> {
+        const BIG: u32 = W * 2;
+        if BIG >: 4 :g_wide {
+            inst u_cell: T;
+        }
+        inst u_leaf: Leaf::<W>();
+    }
+
+    entity Leaf of component ::<N: const> #(
+        param D: u32 = N + 1,
+    ) {}
+
+    entity CellA of component for ProtoCell {}
+    entity CellB of component for ProtoCell {}
+    "#;
+
+    symbol_table::clear();
+
+    let metadata: Metadata =
+        toml::from_str(&Metadata::create_default_toml("prj").unwrap()).unwrap();
+    let parser = Parser::parse(code, &"").unwrap();
+    let analyzer = Analyzer::new(&metadata);
+    analyzer.analyze_pass1("prj", code, "", &parser.veryla);
+    Analyzer::analyze_post_pass1();
+    analyzer.analyze_pass2("prj", code, "", &parser.veryla);
+
+    let (tree, errors) = Analyzer::elaborate("prj", &[(code, &parser.veryla)], None);
+    assert!(errors.is_empty());
+
+    let u_a = tree.find("Top.u_a").unwrap();
+    assert_eq!(u_a.generic_arguments, vec!["3", "CellA"]);
+
+    // Type generic parameter is replaced by the given entity
+    let u_cell = tree.find("Top.u_a.g_wide.u_cell").unwrap();
+    let entity = symbol_table::get(u_cell.entity).unwrap();
+    assert_eq!(entity.token.to_string(), "CellA");
+    assert!(tree.find("Top.u_b.g_wide").is_none());
+
+    // Const generic parameter is passed through the parent
+    let d = resource_table::insert_str("D");
+    let u_leaf = tree.find("Top.u_a.u_leaf").unwrap();
+    assert_eq!(u_leaf.generic_arguments, vec!["3"]);
+    assert!(matches!(
+        u_leaf.parameter(d),
+        Some(Evaluated::Fixed { value: 4, .. })
+    ));
+    let u_leaf = tree.find("Top.u_b.u_leaf").unwrap();
+    assert!(matches!(
+        u_leaf.parameter(d),
+        Some(Evaluated::Fixed { value: 2, .. })
+    ));
+}
+
+#[test]
+fn elaboration_limit_exceeded() {
+    let code = r#"
+    pub entity Top of system {
+        inst u: Rec;
+    }
+
+    entity Rec of component {
+        inst u_a: Rec;
+        inst u_b: Rec;
+    }
+    "#;
+
+    let errors = analyze(code);
+    assert_eq!(errors.len(), 1);
+    assert!(matches!(
+        errors[0],
+        AnalyzerError::ElaborationLimitExceeded { .. }
+    ));
+
+    let code = r#"
+    pub entity Top of system {
+        for i in 0..100000 :g {}
+    }
+    "#;
+
+    let errors = analyze(code);
+    assert_eq!(errors.len(), 1);
+    assert!(matches!(
+        errors[0],
+        AnalyzerError::ElaborationLimitExceeded { .. }
+    ));
+}
+
+#[test]
+fn port_connection() {
+    let leaf = r#"
+    pub entity Leaf of component #(
+        param W: u32 = 8,
+    ) (
+        pwr: input `a power,
+        i  : input `a logic<W>,
+        v  : input `a analog,
+        d  : input `a logic<W> [2],
+    ) {}
+    "#;
+
+    let code = format!(
+        r#"{leaf}
+    pub entity Top of component (
+        pwr: input `a power,
+        x: input `a logic<4>,
+        v: input `a analog,
+        d: input `a logic<4> [2],
+    ) {{
+        inst u: Leaf #(W: 4) (pwr, i: x, v, d);
+    }}
+    "#
+    );
+
+    let errors = analyze(&code);
+    assert!(errors.is_empty());
+
+    let code = format!(
+        r#"{leaf}
+    pub entity Top of component (
+        pwr: input `a power,
+        x: input `a logic<4>,
+        v: input `a analog,
+        d: input `a logic<8> [2],
+    ) {{
+        inst u: Leaf (pwr, i: x, v, d);
+    }}
+    "#
+    );
+
+    let errors = analyze(&code);
+    assert!(matches!(
+        errors[0],
+        AnalyzerError::MismatchPortWidth {
+            expected: 8,
+            actual: 4,
+            ..
+        }
+    ));
+
+    let code = format!(
+        r#"{leaf}
+    pub entity Top of component (
+        pwr: input `a power,
+        x: input `a logic<8>,
+        d: input `a logic<8> [2],
+    ) {{
+        inst u: Leaf (pwr, i: x, v: x, d);
+    }}
+    "#
+    );
+
+    let errors = analyze(&code);
+    assert!(matches!(errors[0], AnalyzerError::MismatchType { .. }));
+
+    let code = format!(
+        r#"{leaf}
+    pub entity Top of component (
+        pwr: input `a power,
+        v: input `a analog,
+        d: input `a logic<8> [2],
+    ) {{
+        inst u: Leaf (pwr, i: pwr, v, d);
+    }}
+    "#
+    );
+
+    let errors = analyze(&code);
+    assert!(matches!(errors[0], AnalyzerError::MismatchType { .. }));
+
+    let code = format!(
+        r#"{leaf}
+    pub entity Top of component (
+        pwr: input `a power,
+        x: input `a logic<8>,
+        v: input `a analog,
+        d: input `a logic<8> [3],
+    ) {{
+        inst u: Leaf (pwr, i: x, v, d);
+    }}
+    "#
+    );
+
+    let errors = analyze(&code);
+    assert!(matches!(
+        errors[0],
+        AnalyzerError::MismatchPortDimension { .. }
+    ));
+
+    let code = format!(
+        r#"{leaf}
+    pub entity Top of component (
+        pwr: input `a power,
+        x: input `a logic<16>,
+        v: input `a analog,
+        d: input `a logic<8> [2],
+    ) {{
+        inst u: Leaf (pwr, i: x[11:0], v, d);
+    }}
+    "#
+    );
+
+    let errors = analyze(&code);
+    assert!(matches!(
+        errors[0],
+        AnalyzerError::MismatchPortWidth {
+            expected: 8,
+            actual: 12,
+            ..
+        }
+    ));
+
+    let code = format!(
+        r#"{leaf}
+    pub entity Top of component (
+        pwr: input `a power,
+        x: input `a logic<4>,
+        v: input `a analog,
+        d: input `a logic<8> [2],
+    ) {{
+        inst u_a: Leaf (pwr, i: {{x, x}}, v, d);
+        inst u_b: Leaf (pwr, i: {{x, x, x}}, v, d);
+        inst u_c: Leaf (pwr, i: {{x, x}}, v, d: {{x, x}});
+    }}
+    "#
+    );
+
+    let errors = analyze(&code);
+    assert_eq!(errors.len(), 2);
+    assert!(matches!(
+        errors[0],
+        AnalyzerError::MismatchPortWidth {
+            expected: 8,
+            actual: 12,
+            ..
+        }
+    ));
+    assert!(matches!(
+        errors[1],
+        AnalyzerError::MismatchPortDimension { .. }
+    ));
+}
+
+#[test]
+fn width_mismatch() {
+    let code = r#"
+    pub entity EntityA of component (
+        i_a: input  logic<8>,
+        i_b: input  logic<4>,
+        i_s: input  i32,
+        o_a: output logic<4>,
+        o_b: output logic<8>,
+        o_c: output logic<4>,
+        o_d: output u32,
+        o_e: output logic<4>,
+    ) {
+        function FuncA (
+            a: input logic<4>,
+        ) -> logic<4> {
+            return a;
+        }
+
+        let x: logic<4> = 20;
+
+        assign o_a = i_a;
+        assign o_b = i_b;
+        assign o_c = i_a[3:0] + x + 1;
+        assign o_d = i_s;
+        assign o_e = FuncA(i_a);
+    }
+    "#;
+
+    let errors = analyze(code);
+    let messages: Vec<_> = errors.iter().map(|x| x.to_string()).collect();
+    assert_eq!(
+        messages,
+        [
+            "5-bit expression is truncated to 4-bit \"x\"",
+            "8-bit expression is truncated to 4-bit \"o_a\"",
+            "4-bit expression is extended to 8-bit \"o_b\"",
+            "signed expression is assigned to unsigned \"o_d\"",
+            "8-bit expression is truncated to 4-bit \"a\"",
+        ]
+    );
+
+    let code = r#"
+    pub entity EntityB of component (
+        i_a: input  logic<8>,
+        o_a: output logic<4>,
+    ) {
+        #[allow(implicit_truncation)]
+        always_comb {
+            o_a = i_a;
+        }
+    }
+    "#;
+
+    let errors = analyze(code);
+    assert!(errors.is_empty());
+}
+
+#[test]
+fn latch_inferred() {
+    let code = r#"
+    pub entity EntityA of component (
+        i_sel: input  logic<2>,
+        o_a  : output logic,
+        o_b  : output logic,
+        o_c  : output logic<4>,
+    ) {
+        always_comb {
+            switch {
+                i_sel == 0: o_a = 1;
+                default   : o_a = 0;
+            }
+        }
+
+        always_comb {
+            o_b = 0;
+            if i_sel == 1 {
+                o_b = 1;
+            }
+        }
+
+        always_comb {
+            for i: u32 in 0..4 {
+                o_c[i] = i_sel[0];
+            }
+        }
+    }
+    "#;
+
+    let errors = analyze(code);
+    assert!(errors.is_empty());
+
+    let code = r#"
+    pub entity EntityA of component (
+        i_sel: input  logic<2>,
+        o_a  : output logic,
+    ) {
+        always_comb {
+            if i_sel == 0 {
+                o_a = 1;
+            } else if i_sel == 1 {
+                o_a = 0;
+            }
+        }
+    }
+    "#;
+
+    let errors = analyze(code);
+    assert!(matches!(
+        errors[0],
+        AnalyzerError::LatchInferred { ref condition, .. } if condition == "implicit `else`"
+    ));
+
+    let code = r#"
+    pub entity EntityA of component (
+        i_sel: input  logic<2>,
+        o_a  : output logic,
+    ) {
+        always_comb {
+            switch {
+                i_sel == 0: o_a = 1;
+                i_sel == 1: {}
+                default   : o_a = 0;
+            }
+        }
+    }
+    "#;
+
+    let errors = analyze(code);
+    assert!(matches!(
+        errors[0],
+        AnalyzerError::LatchInferred { ref condition, .. } if condition == "`i_sel == 1`"
+    ));
+
+    let code = r#"
+    pub entity EntityA of component (
+        i_sel: input  logic<2>,
+        o_a  : output logic,
+    ) {
+        always_comb {
+            for i: u32 in 0..0 {
+                o_a = i_sel[i];
+            }
+        }
+    }
+    "#;
+
+    let errors = analyze(code);
+    assert!(matches!(errors[0], AnalyzerError::LatchInferred { .. }));
+
+    let code = r#"
+    pub entity EntityA of component (
+        i_sel: input  logic<2>,
+        o_a  : output logic,
+    ) {
+        #[allow(latch)]
+        always_comb {
+            if i_sel == 0 {
+                o_a = 1;
+            }
+        }
+    }
+    "#;
+
+    let errors = analyze(code);
+    assert!(errors.is_empty());
+}
+
+#[test]
+fn power_domain() {
+    let code = r#"
+    entity EntityA (
+        i_pwr: input  `a power,
+        i_dat: input  `a logic,
+        o_dat: output `b logic,
+    ) {
+        assign o_dat = i_dat;
+    }
+    "#;
+
+    let errors = analyze(code);
+    assert!(matches!(
+        errors[0],
+        AnalyzerError::MismatchPowerDomain { .. }
+    ));
+
+    let code = r#"
+    entity EntityB (
+        i_pwr : input  `a power,
+        i_dat0: input  `a logic,
+        i_dat1: input  `b logic,
+        o_dat : output `a logic,
+    ) {
+        assign o_dat = {i_dat0, i_dat1};
+    }
+    "#;
+
+    let errors = analyze(code);
+    assert!(matches!(
+        errors[0],
+        AnalyzerError::MismatchPowerDomain { .. }
+    ));
+
+    let code = r#"
+    entity EntityC (
+        i_pwr : input  `a power,
+        i_dat0: input  `a logic,
+        i_dat1: input  `b logic,
+        o_dat : output `a logic,
+    ) {
+        inst u: EntityD (
+            i_dat: i_dat1,
+            o_dat,
+        );
+    }
+
+    entity EntityD (
+        i_dat: input  logic,
+        o_dat: output logic,
+    ) {
+        assign o_dat = i_dat;
+    }
+    "#;
+
+    let errors = analyze(code);
+    assert!(matches!(
+        errors[0],
+        AnalyzerError::MismatchPowerDomain { .. }
+    ));
+
+    let code = r#"
+    entity EntityE (
+        i_pwr : input  `a power,
+        i_dat0: input  `a logic,
+        i_dat1: input  `b logic,
+        o_dat : output `a logic,
+    ) {
+        inst u: $sv::Entity (
+            i_dat: i_dat1,
+            o_dat,
+        );
+    }
+    "#;
+
+    let errors = analyze(code);
+    assert!(matches!(
+        errors[0],
+        AnalyzerError::MismatchPowerDomain { .. }
+    ));
+
+    let code = r#"
+    entity EntityF (
+        i_pwr : input  `a power,
+        i_dat0: input  `a logic,
+        i_dat1: input  `b logic,
+        o_dat : output `b logic,
+    ) {
+        var r_dat: `b logic;
+
+        sequence {
+            r_dat = i_dat1;
+        }
+
+        assign o_dat = r_dat;
+    }
+    "#;
+
+    let errors = analyze(code);
+    assert!(matches!(
+        errors[0],
+        AnalyzerError::MismatchPowerDomain { .. }
+    ));
+
+    let code = r#"
+    entity EntityG (
+        i_pwr: input   `a power,
+        i_dat: input   `a logic,
+        o_dat: modport `b InterfaceA::port,
+    ) {
+        assign o_dat.a = i_dat;
+    }
+
+    interface InterfaceA {
+        var a: logic;
+
+        modport port {
+            a: output,
+        }
+    }
+    "#;
+
+    let errors = analyze(code);
+    assert!(matches!(
+        errors[0],
+        AnalyzerError::MismatchPowerDomain { .. }
+    ));
+
+    let code = r#"
+    entity EntityH (
+        i_pwr: input  `a power,
+        i_dat: input  `a logic,
+        o_dat: output `b logic,
+    ) {
+        always_comb {
+            o_dat = i_dat;
+        }
+    }
+    "#;
+
+    let errors = analyze(code);
+    assert!(matches!(
+        errors[0],
+        AnalyzerError::MismatchPowerDomain { .. }
+    ));
+}
+
+#[test]
+fn r#unsafe() {
+    let code = r#"
+    entity EntityA {
+        unsafe(x) {
+        }
+    }
+    "#;
+
+    let errors = analyze(code);
+    assert!(matches!(errors[0], AnalyzerError::UnknownUnsafe { .. }));
+}
+
+#[test]
+fn detect_recursive() {
+    let code = r#"
+    entity EntityA (
+        x: input x,
+    ) {
+    }
+    "#;
+
+    let errors = analyze(code);
+    assert!(matches!(errors[0], AnalyzerError::MismatchType { .. }));
+}
+
+#[test]
+fn sv_keyword_usage() {
+    let code = r#"
+    entity EntityA {
+        var always: logic;
+        assign always = 1;
+    }
+    "#;
+
+    let errors = analyze(code);
+    assert!(matches!(errors[0], AnalyzerError::SvKeywordUsage { .. }));
+
+    let code = r#"
+    entity EntityA {
+        var r#always: logic;
+        assign r#always = 1;
+    }
+    "#;
+
+    let errors = analyze(code);
+    assert!(matches!(errors[0], AnalyzerError::SvKeywordUsage { .. }));
+}
+
+#[test]
+fn sv_with_implicit_enable() {
+    let code = r#"
+    entity EntityA {
+        var en: enable;
+
+        inst u: $sv::Entity (
+            en,
+        );
+    }
+    "#;
+
+    let errors = analyze(code);
+    assert!(matches!(
+        errors[0],
+        AnalyzerError::SvWithImplicitEnable { .. }
+    ));
+
+    let code = r#"
+    entity EntityB {
+        var en: enable_low;
+
+        inst u: $sv::Entity (
+            en,
+        );
+    }
+    "#;
+
+    let errors = analyze(code);
+    assert!(errors.is_empty());
+}
+
+#[test]
+fn conflict_with_mangled_enum_member() {
+    let code = r#"
+    entity EntityA {
+        enum EnumA: logic {
+            MemberA,
+        }
+        var EnumA_MemberA: logic;
+    }
+    "#;
+
+    let errors = analyze(code);
+    assert!(matches!(
+        errors[0],
+        AnalyzerError::DuplicatedIdentifier { .. }
+    ));
+}
 
+#[test]
+fn unresolvable_generic_argument() {
     let code = r#"
-    entity EntityB (
-        i_pwr : input  `a power,
-        i_dat0: input  `a logic,
-        i_dat1: input  `b logic,
-        o_dat : output `a logic,
+    entity EntityA {
+        const X: u32 = 1;
+        const Y: u32 = PackageA::<X>::W;
+    }
+
+    package PackageA::<T: const> {
+        const W: u32 = T;
+    }
+    "#;
+
+    let errors = analyze(code);
+    // This pattern also causes CyclicTypeDependency error
+    assert!(errors
+        .iter()
+        .any(|e| matches!(e, AnalyzerError::UnresolvableGenericArgument { .. })));
+}
+
+#[test]
+fn wrong_seperator() {
+    let code = r#"
+    package A {
+        enum B {
+            C,
+        }
+    }
+    entity Entity {
+        var _a: A::B;
+
+        always_comb {
+            _a = A.B.C;
+        }
+    }
+    "#;
+
+    let errors = analyze(code);
+    assert!(matches!(errors[0], AnalyzerError::WrongSeparator { .. }));
+
+    let code = r#"
+    package A {
+        enum B {
+            C,
+        }
+    }
+    entity Entity {
+        var _a: A::B;
+
+        always_comb {
+            _a = A::B.C;
+        }
+    }
+    "#;
+
+    let errors = analyze(code);
+    assert!(matches!(errors[0], AnalyzerError::WrongSeparator { .. }));
+
+    let code = r#"
+    entity Entity {
+        struct B {
+            b: logic,
+        }
+
+        var _a: B;
+        always_comb {
+            _a::b = '0;
+        }
+    }
+    "#;
+
+    let errors = analyze(code);
+    assert!(matches!(errors[0], AnalyzerError::WrongSeparator { .. }));
+
+    let code = r#"
+    interface B {
+        var c: logic;
+        modport mp {
+            c: input,
+        }
+    }
+    entity Entity (
+        b: modport B::mp
     ) {
-        assign o_dat = {i_dat0, i_dat1};
+        var _a: logic;
+        always_comb {
+            _a = b::c;
+        }
+    }
+    "#;
+
+    let errors = analyze(code);
+    assert!(matches!(errors[0], AnalyzerError::WrongSeparator { .. }));
+
+    let code = r#"
+    interface A {
+        var b: logic;
+    }
+    entity Entity {
+        inst a: A;
+        always_comb {
+            a::b = 0;
+        }
+    }
+    "#;
+
+    let errors = analyze(code);
+    assert!(matches!(errors[0], AnalyzerError::WrongSeparator { .. }));
+}
+
+#[test]
+fn combinational_loop() {
+    let code = r#"
+    pub entity EntityA of component (
+        i_a: input  logic,
+        o_a: output logic,
+    ) {
+        var a: logic;
+        var b: logic;
+        assign a = b & i_a;
+        assign b = a;
+        assign o_a = b;
     }
     "#;
 
     let errors = analyze(code);
     assert!(matches!(
         errors[0],
-        AnalyzerError::MismatchPowerDomain { .. }
+        AnalyzerError::CombinationalLoop { ref chain, .. } if chain == "a -> b -> a"
     ));
 
     let code = r#"
-    entity EntityC (
-        i_pwr : input  `a power,
-        i_dat0: input  `a logic,
-        i_dat1: input  `b logic,
-        o_dat : output `a logic,
+    pub entity EntityA of component (
+        i_a: input  logic,
+        o_a: output logic,
+        o_b: output logic,
     ) {
-        inst u: EntityD (
-            i_dat: i_dat1,
-            o_dat,
-        );
+        var a: logic<2>;
+        var t: logic;
+        assign a[0] = i_a;
+        assign a[1] = a[0];
+        assign o_a  = a[1];
+        always_comb {
+            t   = i_a;
+            o_b = t;
+        }
     }
+    "#;
 
-    entity EntityD (
-        i_dat: input  logic,
-        o_dat: output logic,
+    let errors = analyze(code);
+    assert!(errors.is_empty());
+
+    let code = r#"
+    pub entity Leaf of component (
+        i_a: input  logic,
+        i_b: input  logic,
+        o_a: output logic,
+        o_b: output logic,
     ) {
-        assign o_dat = i_dat;
+        assign o_a = i_a;
+        assign o_b = i_b;
+    }
+
+    pub entity Top of component (
+        i_a: input  logic,
+        o_a: output logic,
+    ) {
+        var a: logic;
+        var b: logic;
+        var c: logic;
+        assign a = b | i_a;
+        assign c = a;
+        inst u: Leaf (i_a: a, i_b: c, o_a: b, o_b: o_a);
     }
     "#;
 
     let errors = analyze(code);
+    assert_eq!(errors.len(), 1);
     assert!(matches!(
         errors[0],
-        AnalyzerError::MismatchPowerDomain { .. }
+        AnalyzerError::CombinationalLoop { ref chain, .. } if chain == "a -> b -> a"
     ));
 
     let code = r#"
-    entity EntityE (
-        i_pwr : input  `a power,
-        i_dat0: input  `a logic,
-        i_dat1: input  `b logic,
-        o_dat : output `a logic,
+    pub entity Leaf of component (
+        i_a: input  logic,
+        i_b: input  logic,
+        o_a: output logic,
+        o_b: output logic,
     ) {
-        inst u: $sv::Entity (
-            i_dat: i_dat1,
-            o_dat,
-        );
+        assign o_a = i_b;
+        assign o_b = i_b;
+    }
+
+    pub entity Top of component (
+        i_a: input  logic,
+        o_a: output logic,
+    ) {
+        var a: logic;
+        var b: logic;
+        assign a = b | i_a;
+        inst u: Leaf (i_a: a, i_b: i_a, o_a: b, o_b: o_a);
+    }
+    "#;
+
+    let errors = analyze(code);
+    assert!(errors.is_empty());
+}
+
+#[test]
+fn power_domain_inference() {
+    let code = r#"
+    pub entity EntityA of component (
+        i_pwr: input  `b power,
+        i_a  : input  `a logic,
+        o_b  : output `b logic,
+    ) {
+        let x: logic = i_a;
+        let y: logic = x;
+        assign o_b = y;
     }
     "#;
 
     let errors = analyze(code);
     assert!(matches!(
-        errors[0],
-        AnalyzerError::MismatchPowerDomain { .. }
+        &errors[0],
+        AnalyzerError::MismatchPowerDomain { other_domain, .. } if other_domain == "'a (inferred)"
     ));
 
     let code = r#"
-    entity EntityF (
-        i_pwr : input  `a power,
-        i_dat0: input  `a logic,
-        i_dat1: input  `b logic,
-        o_dat : output `b logic,
+    pub entity EntityB of component (
+        i_pwr_a: input  `a power,
+        i_pwr_b: input  `b power,
+        i_a    : input  `a logic,
+        o_b    : output `b logic,
     ) {
-        var r_dat: `b logic;
+        var r_a: logic;
+        var r_b: logic;
 
-        sequence {
-            r_dat = i_dat1;
+        sequence (i_pwr_a) {
+            r_a = i_a;
         }
 
-        assign o_dat = r_dat;
+        sequence (i_pwr_b) {
+            r_b = r_a;
+        }
+
+        assign o_b = r_b;
+    }
+    "#;
+
+    let errors = analyze(code);
+    assert!(!errors.is_empty());
+    assert!(errors
+        .iter()
+        .all(|x| matches!(x, AnalyzerError::MismatchPowerDomain { .. })));
+
+    let r_b = symbol_table::get_all()
+        .into_iter()
+        .find(|x| x.token.to_string() == "r_b")
+        .unwrap();
+    assert_eq!(
+        power_domain_table::get(r_b.id).map(|x| x.to_string()),
+        Some("'b".to_string())
+    );
+
+    let code = r#"
+    pub entity EntityC of component (
+        i_pwr: input  `b power,
+        i_a  : input  `a logic,
+        o_b  : output `b logic,
+    ) {
+        function FuncA () -> logic {
+            return i_a;
+        }
+
+        assign o_b = FuncA();
     }
     "#;
 
@@ -2959,19 +4474,100 @@ fn power_domain() {
     ));
 
     let code = r#"
-    entity EntityG (
-        i_pwr: input   `a power,
-        i_dat: input   `a logic,
-        o_dat: modport `b InterfaceA::port,
+    pub entity EntityD of component (
+        i_pwr: input  `a power,
+        i_a  : input  `a logic,
+        o_a  : output `a logic,
     ) {
-        assign o_dat.a = i_dat;
+        let x: logic = i_a;
+        assign o_a = x;
     }
+    "#;
 
-    interface InterfaceA {
-        var a: logic;
+    let errors = analyze(code);
+    assert!(errors.is_empty());
+
+    let code = r#"
+    pub entity EntityE of component (
+        i_pwr: input `a power,
+        i_a  : input `a logic,
+        i_b  : input `b logic,
+    ) {
+        let x: logic = i_a;
+        inst u: EntityF (
+            i_a: x,
+            i_b,
+            i_pwr,
+        );
+    }
+
+    entity EntityF of component (
+        i_a  : input `c logic,
+        i_b  : input `c logic,
+        i_pwr: input `c power,
+    ) {}
+    "#;
+
+    let errors = analyze(code);
+    assert_eq!(errors.len(), 1);
+    assert!(matches!(
+        &errors[0],
+        AnalyzerError::MismatchPowerDomain { other_domain, .. } if other_domain == "'a (inferred)"
+    ));
+}
+
+#[test]
+fn synchronizer() {
+    let sync = r#"
+    pub entity Sync of component (
+        i_pwr: input  `b power,
+        i_d  : input  `a logic,
+        o_d  : output `b logic,
+    ) {
+        var r_d: `b logic<2>;
+
+        sequence (i_pwr) {
+            r_d[0] = i_d;
+            r_d[1] = r_d[0];
+        }
+
+        assign o_d = r_d[1];
+    }
+    "#;
+
+    let top = r#"
+    pub entity Top of component (
+        i_pwr: input  `b power,
+        i_a  : input  `a logic,
+        o_b  : output `b logic,
+    ) {
+        inst u: Sync (i_pwr, i_d: i_a, o_d: o_b);
+    }
+    "#;
+
+    let code = format!("{sync}{top}");
+    let errors = analyze(&code);
+    assert!(matches!(
+        errors[0],
+        AnalyzerError::MismatchPowerDomain { .. }
+    ));
+
+    let code = format!("#[synchronizer(stages = 1)]{sync}{top}");
+    let errors = analyze(&code);
+    assert!(matches!(
+        errors[0],
+        AnalyzerError::MismatchAttributeArgs { .. }
+    ));
 
-        modport port {
-            a: output,
+    let code = r#"
+    pub entity EntityA of component (
+        i_pwr: input  `b power,
+        i_a  : input  `a logic,
+        i_b  : input  `b logic,
+        o_b  : output `b logic,
+    ) {
+        unsafe (pdc) {
+            assign o_b = i_a & i_b;
         }
     }
     "#;
@@ -2979,233 +4575,540 @@ fn power_domain() {
     let errors = analyze(code);
     assert!(matches!(
         errors[0],
-        AnalyzerError::MismatchPowerDomain { .. }
+        AnalyzerError::UnsynchronizedCrossing { .. }
     ));
 
     let code = r#"
-    entity EntityH (
-        i_pwr: input  `a power,
-        i_dat: input  `a logic,
-        o_dat: output `b logic,
+    pub entity EntityA of component (
+        i_pwr: input  `b power,
+        i_a  : input  `a logic,
+        o_b  : output `b logic,
     ) {
-        always_comb {
-            o_dat = i_dat;
+        var r_a: `b logic;
+
+        unsafe (pdc) {
+            sequence (i_pwr) {
+                r_a = i_a;
+            }
         }
+
+        assign o_b = r_a;
     }
     "#;
 
     let errors = analyze(code);
+    assert!(errors.is_empty());
+
+    let code = format!("#[synchronizer(stages = 2)]{sync}{top}");
+    let errors = analyze(&code);
+    assert!(errors.is_empty());
+
+    // Output is registered only twice
+    let code = format!("#[synchronizer(stages = 3)]{sync}{top}");
+    let errors = analyze(&code);
+    assert_eq!(errors.len(), 1);
     assert!(matches!(
         errors[0],
-        AnalyzerError::MismatchPowerDomain { .. }
+        AnalyzerError::InsufficientSynchronizerStages {
+            required: 3,
+            actual: 2,
+            ..
+        }
     ));
-}
 
-#[test]
-fn r#unsafe() {
-    let code = r#"
-    entity EntityA {
-        unsafe(x) {
+    let sync = r#"
+    #[synchronizer(stages = 2)]
+    pub entity Sync of component (
+        i_pwr: input  `b power,
+        i_d  : input  `a logic,
+        o_d  : output `b logic,
+    ) {
+        var r_meta: `b logic;
+        var r_sync: `b logic;
+
+        sequence (i_pwr) {
+            r_meta = i_d;
+            r_sync = r_meta;
         }
+
+        assign o_d = r_meta;
     }
     "#;
 
-    let errors = analyze(code);
-    assert!(matches!(errors[0], AnalyzerError::UnknownUnsafe { .. }));
+    let code = format!("{sync}{top}");
+    let errors = analyze(&code);
+    assert!(errors.iter().any(|x| matches!(
+        x,
+        AnalyzerError::InsufficientSynchronizerStages {
+            required: 2,
+            actual: 1,
+            ..
+        }
+    )));
 }
 
 #[test]
-fn detect_recursive() {
+fn std_synchronizer() {
+    let std = include_str!("../../std/veryla/src/synchronizer/synchronizer.veryla");
     let code = r#"
-    entity EntityA (
-        x: input x,
+    pub entity Top of component (
+        i_pwr: input  `b power,
+        i_a  : input  `a logic<4>,
+        o_b  : output `b logic<4>,
     ) {
+        inst u: $std::synchronizer #(WIDTH: 4) (i_pwr, i_d: i_a, o_d: o_b);
     }
     "#;
 
-    let errors = analyze(code);
-    assert!(matches!(errors[0], AnalyzerError::MismatchType { .. }));
+    symbol_table::clear();
+
+    let metadata: Metadata =
+        toml::from_str(&Metadata::create_default_toml("prj").unwrap()).unwrap();
+    let std_parser = Parser::parse(std, &"synchronizer.veryla").unwrap();
+    let parser = Parser::parse(code, &"").unwrap();
+    let analyzer = Analyzer::new(&metadata);
+
+    let mut errors = analyzer.analyze_pass1("$std", std, "", &std_parser.veryla);
+    errors.append(&mut analyzer.analyze_pass1("prj", code, "", &parser.veryla));
+    Analyzer::analyze_post_pass1();
+    errors.append(&mut analyzer.analyze_pass2("$std", std, "", &std_parser.veryla));
+    errors.append(&mut analyzer.analyze_pass2("prj", code, "", &parser.veryla));
+    errors.append(&mut analyzer.analyze_pass3("$std", std, "", &std_parser.veryla));
+    errors.append(&mut analyzer.analyze_pass3("prj", code, "", &parser.veryla));
+    dbg!(&errors);
+    assert!(errors.is_empty());
 }
 
 #[test]
-fn sv_keyword_usage() {
-    let code = r#"
-    entity EntityA {
-        var always: logic;
-        assign always = 1;
+fn glitch_prone_crossing() {
+    let sync = r#"
+    #[synchronizer(stages = 2)]
+    pub entity Sync of component (
+        i_pwr: input  `b power,
+        i_d  : input  `a logic,
+        o_d  : output `b logic,
+    ) {
+        var r_d: `b logic<2>;
+
+        sequence (i_pwr) {
+            r_d[0] = i_d;
+            r_d[1] = r_d[0];
+        }
+
+        assign o_d = r_d[1];
     }
     "#;
 
-    let errors = analyze(code);
-    assert!(matches!(errors[0], AnalyzerError::SvKeywordUsage { .. }));
+    let top = r#"
+    pub entity Top of component (
+        i_pwr_a: input  `a power,
+        i_pwr_b: input  `b power,
+        i_a0   : input  `a logic,
+        i_a1   : input  `a logic,
+        o_b    : output `b logic,
+    ) {
+        var r_a0: `a logic;
+        var r_a1: `a logic;
+
+        sequence (i_pwr_a) {
+            r_a0 = i_a0;
+            r_a1 = i_a1;
+        }
+
+        let x: logic = r_a0 & r_a1;
+        inst u: Sync (i_pwr: i_pwr_b, i_d: x, o_d: o_b);
+    }
+    "#;
+
+    let code = format!("{sync}{top}");
+    let errors = analyze(&code);
+    assert_eq!(errors.len(), 1);
+    assert!(matches!(
+        &errors[0],
+        AnalyzerError::GlitchProneCrossing { signals, .. } if signals == "r_a0, r_a1"
+    ));
+
+    let top = r#"
+    pub entity Top of component (
+        i_pwr_a: input  `a power,
+        i_pwr_b: input  `b power,
+        i_a0   : input  `a logic,
+        o_b    : output `b logic,
+    ) {
+        var r_a0: `a logic;
+
+        sequence (i_pwr_a) {
+            r_a0 = i_a0;
+        }
+
+        let x: logic = ~r_a0;
+        inst u: Sync (i_pwr: i_pwr_b, i_d: x, o_d: o_b);
+    }
+    "#;
+
+    let code = format!("{sync}{top}");
+    let errors = analyze(&code);
+    assert!(errors.is_empty());
 
     let code = r#"
-    entity EntityA {
-        var r#always: logic;
-        assign r#always = 1;
+    pub entity EntityA of component (
+        i_pwr_b: input  `b power,
+        i_a    : input  `a logic,
+        i_c    : input  `c logic,
+        o_b    : output `b logic,
+    ) {
+        var r_b: `b logic;
+
+        unsafe (pdc) {
+            let x: logic = i_a | i_c;
+            sequence (i_pwr_b) {
+                r_b = x;
+            }
+        }
+
+        assign o_b = r_b;
     }
     "#;
 
     let errors = analyze(code);
-    assert!(matches!(errors[0], AnalyzerError::SvKeywordUsage { .. }));
+    assert!(errors.iter().any(|x| matches!(
+        x,
+        AnalyzerError::GlitchProneCrossing { signals, .. } if signals == "i_a, i_c"
+    )));
 }
 
 #[test]
-fn sv_with_implicit_enable() {
+fn constant_condition() {
+    let code = r#"
+    pub entity EntityA of component #(
+        param W: u32 = 8,
+    ) (
+        i_a: input  logic,
+        o_a: output logic,
+        o_b: output logic,
+    ) {
+        const N: u32 = 2;
+
+        if W == 8 :g_a {
+            assign o_a = i_a;
+        } else {
+            assign o_a = 0;
+        }
+
+        always_comb {
+            if i_a {
+                o_b = 1;
+            } else {
+                o_b = 0;
+            }
+        }
+    }
+    "#;
+
+    let errors = analyze(code);
+    assert!(errors.is_empty());
+
     let code = r#"
-    entity EntityA {
-        var en: enable;
+    pub entity EntityA of component (
+        i_a: input  logic,
+        o_a: output logic,
+    ) {
+        const N: u32 = 2;
 
-        inst u: $sv::Entity (
-            en,
-        );
+        if N == 2 :g_a {
+            assign o_a = i_a;
+        } else {
+            assign o_a = 0;
+        }
     }
     "#;
 
     let errors = analyze(code);
+    assert_eq!(errors.len(), 1);
     assert!(matches!(
         errors[0],
-        AnalyzerError::SvWithImplicitEnable { .. }
+        AnalyzerError::UnreachableGenerateBlock { .. }
     ));
 
     let code = r#"
-    entity EntityB {
-        var en: enable_low;
-
-        inst u: $sv::Entity (
-            en,
-        );
+    pub entity EntityA of component (
+        i_a: input  logic,
+        o_a: output logic,
+    ) {
+        always_comb {
+            if 1 == 1 {
+                o_a = i_a;
+            } else {
+                o_a = 0;
+            }
+        }
     }
     "#;
 
     let errors = analyze(code);
-    assert!(errors.is_empty());
-}
+    assert!(matches!(
+        errors[0],
+        AnalyzerError::ConstantCondition { ref value, .. } if value == "true"
+    ));
 
-#[test]
-fn conflict_with_mangled_enum_member() {
+    // Warnings are suppressed in generate blocks which are never elaborated
     let code = r#"
-    entity EntityA {
-        enum EnumA: logic {
-            MemberA,
+    pub entity EntityA of component (
+        i_a: input  logic,
+        o_a: output logic,
+    ) {
+        if 0 :g_a {
+            always_comb {
+                if 0 {
+                    o_a = i_a;
+                } else {
+                    o_a = 0;
+                }
+            }
+        } else {
+            assign o_a = i_a;
         }
-        var EnumA_MemberA: logic;
     }
     "#;
 
     let errors = analyze(code);
+    assert_eq!(errors.len(), 1);
     assert!(matches!(
         errors[0],
-        AnalyzerError::DuplicatedIdentifier { .. }
+        AnalyzerError::UnreachableGenerateBlock { .. }
     ));
 }
 
 #[test]
-fn unresolvable_generic_argument() {
-    let code = r#"
-    entity EntityA {
-        const X: u32 = 1;
-        const Y: u32 = PackageA::<X>::W;
+fn constant_function() {
+    let code = r#"
+    pub package PackageA {
+        function clog2 (
+            x: input u32,
+        ) -> u32 {
+            var ret: u32;
+            ret = 0;
+            for i: u32 in 0..32 {
+                if (1 << i) >= x {
+                    break;
+                }
+                ret += 1;
+            }
+            return ret;
+        }
+
+        function pick (
+            x: input u32,
+        ) -> u32 {
+            case x {
+                0      : return 10;
+                1..=3  : return 20;
+                default: return clog2(x);
+            }
+        }
+
+        const W: u32 = clog2(8);
+        const X: u32 = pick(2) + pick(16);
     }
 
-    package PackageA::<T: const> {
-        const W: u32 = T;
+    pub entity EntityA of component (
+        i_a: input  logic,
+        o_a: output logic,
+    ) {
+        if PackageA::W == 4 :g_a {
+            assign o_a = i_a;
+        } else if PackageA::X == 24 :g_b {
+            assign o_a = 0;
+        } else {
+            assign o_a = 1;
+        }
     }
     "#;
 
     let errors = analyze(code);
-    // This pattern also causes CyclicTypeDependency error
-    assert!(errors
-        .iter()
-        .any(|e| matches!(e, AnalyzerError::UnresolvableGenericArgument { .. })));
-}
+    assert_eq!(errors.len(), 2);
+    assert!(matches!(
+        errors[0],
+        AnalyzerError::UnreachableGenerateBlock { .. }
+    ));
+    assert!(matches!(
+        errors[1],
+        AnalyzerError::UnreachableGenerateBlock { .. }
+    ));
 
-#[test]
-fn wrong_seperator() {
     let code = r#"
-    package A {
-        enum B {
-            C,
+    pub package PackageA {
+        function f (
+            x: input u32,
+        ) -> u32 {
+            return f(x + 1);
         }
-    }
-    entity Entity {
-        var _a: A::B;
 
-        always_comb {
-            _a = A.B.C;
-        }
+        const W: u32 = f(0);
     }
     "#;
 
     let errors = analyze(code);
-    assert!(matches!(errors[0], AnalyzerError::WrongSeparator { .. }));
+    assert!(errors
+        .iter()
+        .any(|x| matches!(x, AnalyzerError::EvaluationLimitExceeded { .. })));
 
     let code = r#"
-    package A {
-        enum B {
-            C,
+    pub package PackageA {
+        function f (
+            x: input u32,
+        ) -> u32 {
+            var ret: u32;
+            ret = 0;
+            for i: u32 in 0..x step *= 1 {
+                ret += 1;
+            }
+            return ret;
         }
-    }
-    entity Entity {
-        var _a: A::B;
 
-        always_comb {
-            _a = A::B.C;
-        }
+        const W: u32 = f(4);
     }
     "#;
 
     let errors = analyze(code);
-    assert!(matches!(errors[0], AnalyzerError::WrongSeparator { .. }));
+    assert!(matches!(
+        errors[0],
+        AnalyzerError::EvaluationLimitExceeded { .. }
+    ));
+}
 
+#[test]
+fn typed_constant() {
     let code = r#"
-    entity Entity {
-        struct B {
-            b: logic,
+    pub package PackageA {
+        struct StructA {
+            a: logic<4>,
+            b: logic<4>,
         }
 
-        var _a: B;
-        always_comb {
-            _a::b = '0;
+        #[enum_encoding(onehot)]
+        enum EnumA {
+            X,
+            Y,
+            Z,
+        }
+
+        const A: StructA = {4'h1, 4'h2};
+        const B: u32 [4] = '{1, 2 repeat 2, 3};
+        const C: u32 = $bits(StructA);
+    }
+
+    pub entity EntityA of component (
+        i_a: input  logic,
+        o_a: output logic,
+    ) {
+        if PackageA::A.a == 1 && PackageA::A.b == 2 && PackageA::B[2] == 2 && PackageA::B[3] == 3
+            && PackageA::C == 8 && PackageA::EnumA::Z == 4 :g_a {
+            assign o_a = i_a;
+        } else {
+            assign o_a = 0;
         }
     }
     "#;
 
     let errors = analyze(code);
-    assert!(matches!(errors[0], AnalyzerError::WrongSeparator { .. }));
+    assert_eq!(errors.len(), 1);
+    assert!(matches!(
+        errors[0],
+        AnalyzerError::UnreachableGenerateBlock { .. }
+    ));
 
     let code = r#"
-    interface B {
-        var c: logic;
-        modport mp {
-            c: input,
+    pub package PackageB {
+        struct StructB {
+            a: logic<2>,
+            b: logic<2>,
         }
+
+        #[enum_encoding(gray)]
+        enum EnumB {
+            X,
+            Y,
+            Z,
+        }
+
+        const S: StructB = {2'd1, 2'd2};
     }
-    entity Entity (
-        b: modport B::mp
+
+    pub entity EntityB of component (
+        i_sel: input  logic<2>,
+        i_a  : input  logic<4>,
+        o_b  : output logic,
+        o_c  : output logic,
     ) {
-        var _a: logic;
         always_comb {
-            _a = b::c;
+            case i_sel {
+                PackageB::EnumB::X: o_b = i_a[0];
+                PackageB::S.a     : o_b = i_a[1];
+                PackageB::S.b     : o_b = i_a[2];
+                PackageB::EnumB::Z: o_b = i_a[3];
+            }
+        }
+
+        always_comb {
+            case i_sel {
+                PackageB::EnumB::X: o_c = i_a[0];
+                PackageB::S.a     : o_c = i_a[1];
+                PackageB::EnumB::Z: o_c = i_a[3];
+            }
         }
     }
     "#;
 
     let errors = analyze(code);
-    assert!(matches!(errors[0], AnalyzerError::WrongSeparator { .. }));
+    let messages: Vec<_> = errors
+        .iter()
+        .filter(|x| matches!(x, AnalyzerError::NonExhaustiveCase { .. }))
+        .map(|x| x.to_string())
+        .collect();
+    assert_eq!(messages, ["case doesn't cover all values: 2'b10"]);
 
     let code = r#"
-    interface A {
-        var b: logic;
-    }
-    entity Entity {
-        inst a: A;
-        always_comb {
-            a::b = 0;
+    pub package PackageC {
+        struct StructA {
+            a: logic<4>,
+            b: logic<4>,
         }
     }
+
+    pub entity EntityC of component (
+        i_a: input  logic<$bits(PackageC::StructA)>,
+        o_a: output logic<8>,
+        o_b: output logic<4>,
+        o_c: output logic,
+    ) {
+        var a: logic<$bits(PackageC::StructA)>;
+
+        assign a   = i_a;
+        assign o_a = a;
+        assign o_b = a;
+        assign o_c = a[msb];
+    }
     "#;
 
     let errors = analyze(code);
-    assert!(matches!(errors[0], AnalyzerError::WrongSeparator { .. }));
+    let messages: Vec<_> = errors.iter().map(|x| x.to_string()).collect();
+    assert_eq!(messages, ["8-bit expression is truncated to 4-bit \"o_b\""]);
+}
+
+#[test]
+fn warning_codes() {
+    let source = include_str!("analyzer_error.rs");
+    let mut codes: Vec<_> = source
+        .split("#[diagnostic(")
+        .skip(1)
+        .filter_map(|x| {
+            let x = x.trim_start();
+            let x = x.strip_prefix("severity(Warning),")?.trim_start();
+            let x = x.strip_prefix("code(")?;
+            x.split(')').next()
+        })
+        .collect();
+    codes.sort();
+    assert_eq!(codes, crate::analyzer_error::WARNING_CODES);
 }
@@ -496,6 +496,7 @@ pub enum AssignPositionType {
     StatementBranch {
         token: Token,
         branches: usize,
+        conditions: Vec<AssignBranchCondition>,
         has_default: bool,
         allow_missing_enable_statement: bool,
         r#type: AssignStatementBranchType,
@@ -536,6 +537,13 @@ impl AssignPositionType {
     }
 }
 
+/// Condition of a statement branch item, used to name uncovered branches
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct AssignBranchCondition {
+    pub token: Token,
+    pub text: String,
+}
+
 #[derive(Clone, Debug, PartialEq, Eq)]
 pub enum AssignDeclarationType {
     Let,
@@ -551,6 +559,8 @@ pub enum AssignStatementBranchType {
     If,
     IfEnable,
     Case,
+    Switch,
+    For,
 }
 
 #[derive(Clone, Debug, PartialEq, Eq)]
@@ -559,6 +569,8 @@ pub enum AssignStatementBranchItemType {
     IfEnable,
     Else,
     Case,
+    Switch,
+    For,
 }
 
 #[derive(Clone, Default, Debug)]
@@ -590,7 +602,11 @@ impl AssignPositionTree {
         self.children.push(node);
     }
 
-    pub fn check_always_comb_uncovered(&self) -> Option<Token> {
+    /// Get the first branch of `always_comb` which doesn't assign the variable
+    ///
+    /// The returned token points to the branch statement,
+    /// and the string describes the uncovered condition.
+    pub fn check_always_comb_uncovered(&self) -> Option<(Token, String)> {
         if let Some(AssignPositionType::Declaration { ref r#type, .. }) = self.r#type {
             if *r#type == AssignDeclarationType::AlwaysComb {
                 let children: Vec<_> = self
@@ -616,16 +632,36 @@ impl AssignPositionTree {
         None
     }
 
-    fn impl_always_comb_uncovered(&self) -> Option<Token> {
+    fn impl_always_comb_uncovered(&self) -> Option<(Token, String)> {
         match self.r#type {
             Some(AssignPositionType::StatementBranch {
                 token,
-                branches,
+                ref conditions,
                 has_default,
+                ref r#type,
                 ..
             }) => {
-                if !has_default || self.children.len() != branches {
-                    Some(token)
+                let uncovered = conditions.iter().find(|x| {
+                    !self.children.iter().any(|y| {
+                        matches!(
+                            y.r#type,
+                            Some(AssignPositionType::StatementBranchItem { token, .. }) if token == x.token
+                        )
+                    })
+                });
+                if let Some(x) = uncovered {
+                    Some((token, format!("`{}`", x.text)))
+                } else if !has_default {
+                    let text = match r#type {
+                        AssignStatementBranchType::If | AssignStatementBranchType::IfEnable => {
+                            "implicit `else`"
+                        }
+                        AssignStatementBranchType::Case | AssignStatementBranchType::Switch => {
+                            "implicit `default`"
+                        }
+                        AssignStatementBranchType::For => "the loop has no iteration",
+                    };
+                    Some((token, text.to_string()))
                 } else {
                     self.children
                         .iter()
@@ -1,9 +1,10 @@
 use std::fs;
-use std::path::Path;
+use std::path::{Path, PathBuf};
 use veryla_aligner::{align_kind, Aligner, Location};
 use veryla_analyzer::attribute::Attribute as Attr;
 use veryla_analyzer::attribute::{AllowItem, CondTypeItem, EnumEncodingItem};
 use veryla_analyzer::attribute_table;
+use veryla_analyzer::distribution::{self, Distribution, Sampler};
 use veryla_analyzer::evaluator::{Evaluated, Evaluator};
 use veryla_analyzer::namespace::Namespace;
 use veryla_analyzer::symbol::TypeModifier as SymTypeModifier;
@@ -13,7 +14,7 @@ use veryla_analyzer::symbol::{
 use veryla_analyzer::symbol_path::{GenericSymbolPath, SymbolPath};
 use veryla_analyzer::symbol_table::{self, ResolveError, ResolveResult};
 use veryla_analyzer::{msb_table, namespace_table};
-use veryla_metadata::{Build, BuiltinType, PowerType, Format, Metadata, EnableType, SourceMapTarget};
+use veryla_metadata::{Build, BuiltinType, PowerType, Format, Metadata, EnableType, SourceMapTarget, CORNER_PACKAGE};
 use veryla_parser::resource_table::{self, StrId};
 use veryla_parser::veryla_grammar_trait::*;
 use veryla_parser::veryla_token::{is_anonymous_token, Token, TokenSource, VerylaToken};
@@ -73,6 +74,8 @@ pub struct Emitter {
     generic_map: Vec<Vec<GenericMap>>,
     source_map: Option<SourceMap>,
     resolved_identifier: Vec<String>,
+    integer_parameter: bool,
+    project_path: PathBuf,
 }
 
 impl Default for Emitter {
@@ -112,6 +115,8 @@ impl Default for Emitter {
             generic_map: Vec::new(),
             source_map: None,
             resolved_identifier: Vec::new(),
+            integer_parameter: false,
+            project_path: PathBuf::new(),
         }
     }
 }
@@ -133,6 +138,11 @@ impl Emitter {
             format_opt: metadata.format.clone(),
             aligner: Aligner::new(),
             source_map: Some(source_map),
+            project_path: metadata
+                .metadata_path
+                .parent()
+                .map(|x| x.to_path_buf())
+                .unwrap_or_default(),
             ..Default::default()
         }
     }
@@ -986,6 +996,53 @@ impl Emitter {
         self.r_paren(&function_call.r_paren);
     }
 
+    fn emit_distribution(
+        &mut self,
+        distribution: Distribution,
+        identifier: &ExpressionIdentifier,
+        function_call: &FunctionCall,
+    ) {
+        let args = distribution::arguments(function_call).unwrap();
+        let token = identifier.identifier();
+        let value = if let Some(seed) = self.build_opt.sample_seed {
+            let mut sampler = Sampler::new(seed, &token.token, &self.project_path);
+            distribution.sample(&args, &mut sampler)
+        } else {
+            distribution.nominal(&args)
+        };
+
+        let text = if self.integer_parameter {
+            let value = distribution::to_integer(value);
+            if value < 0 {
+                format!("({})", value)
+            } else {
+                format!("{}", value)
+            }
+        } else if value < 0.0 {
+            format!("({:?})", value)
+        } else {
+            format!("{:?}", value)
+        };
+        self.veryla_token(&token.replace(&text));
+    }
+
+    fn is_integer_parameter(&self, identifier: &Identifier) -> bool {
+        if let Ok(symbol) = symbol_table::resolve(identifier) {
+            if let SymbolKind::Parameter(x) = symbol.found.kind {
+                return matches!(
+                    x.r#type.kind,
+                    TypeKind::Bit
+                        | TypeKind::Logic
+                        | TypeKind::U32
+                        | TypeKind::U64
+                        | TypeKind::I32
+                        | TypeKind::I64
+                );
+            }
+        }
+        false
+    }
+
     fn resolve_symbol_with_generics(
         &self,
         arg: &ScopedIdentifier,
@@ -1562,6 +1619,19 @@ impl VerylaWalker for Emitter {
 
     /// Semantic action for non-terminal 'IdentifierFactor'
     fn identifier_factor(&mut self, arg: &IdentifierFactor) {
+        if let Some(ref x) = arg.identifier_factor_opt {
+            if let Ok(symbol) = symbol_table::resolve(arg.expression_identifier.as_ref()) {
+                if let Some(distribution) = Distribution::from_symbol(&symbol.found) {
+                    self.emit_distribution(
+                        distribution,
+                        &arg.expression_identifier,
+                        &x.function_call,
+                    );
+                    return;
+                }
+            }
+        }
+
         self.expression_identifier(&arg.expression_identifier);
         if let Some(ref x) = arg.identifier_factor_opt {
             self.emit_function_call(&arg.expression_identifier, &x.function_call);
@@ -2290,7 +2360,9 @@ impl VerylaWalker for Emitter {
                     self.str("`");
                     self.identifier(&arg.identifier);
                     self.space(1);
-                    if let AttributeItem::Identifier(x) = &*x.attribute_list.attribute_item {
+                    if let AttributeItem::IdentifierAttributeItemOpt(x) =
+                        &*x.attribute_list.attribute_item
+                    {
                         self.identifier(&x.identifier);
                     }
                     self.newline();
@@ -2321,7 +2393,9 @@ impl VerylaWalker for Emitter {
             }
             "test" => {
                 if let Some(ref x) = arg.attribute_opt {
-                    if let AttributeItem::Identifier(x) = &*x.attribute_list.attribute_item {
+                    if let AttributeItem::IdentifierAttributeItemOpt(x) =
+                        &*x.attribute_list.attribute_item
+                    {
                         let test_name = x.identifier.identifier_token.to_string();
                         let text = format!(
                             "`ifdef __veryla_test_{}_{}__",
@@ -2466,7 +2540,9 @@ impl VerylaWalker for Emitter {
         self.space(1);
         self.equ(&arg.equ);
         self.space(1);
+        self.integer_parameter = self.is_integer_parameter(&arg.identifier);
         self.expression(&arg.expression);
+        self.integer_parameter = false;
         self.semicolon(&arg.semicolon);
     }
 
@@ -3165,7 +3241,9 @@ impl VerylaWalker for Emitter {
         self.equ(&arg.equ);
         self.space(1);
         self.align_start(align_kind::EXPRESSION);
+        self.integer_parameter = self.is_integer_parameter(&arg.identifier);
         self.expression(&arg.expression);
+        self.integer_parameter = false;
         self.align_finish(align_kind::EXPRESSION);
     }
 
@@ -3482,7 +3560,7 @@ impl VerylaWalker for Emitter {
             if !file_scope_import.is_empty() {
                 self.newline_pop();
             }
-            if let Some(ref x) = arg.interface_declaration_opt1 {
+            if let Some(ref x) = arg.interface_declaration_opt2 {
                 self.space(1);
                 self.with_parameter(&x.with_parameter);
             }
@@ -3769,7 +3847,7 @@ impl VerylaWalker for Emitter {
                 self.package_declaration(&x.package_declaration)
             }
             // proto is not emitted at SystemVerilog
-            DescriptionItem::ProtoEntityDeclaration(_) => (),
+            DescriptionItem::ProtoDeclaration(_) => (),
             // file scope import is not emitted at SystemVerilog
             DescriptionItem::ImportDeclaration(_) => (),
             DescriptionItem::EmbedDeclaration(x) => self.embed_declaration(&x.embed_declaration),
@@ -3870,6 +3948,10 @@ fn namespace_string(namespace: &Namespace, context: &SymbolContext) -> String {
             // "$sv" namespace should be removed
             if text == "$sv_" {
                 in_sv_namespace = true;
+            } else if text == "$corner_" {
+                // "$corner" namespace is mapped to the package generated by build and test runners
+                in_sv_namespace = true;
+                ret.push_str(&format!("{}::", CORNER_PACKAGE));
             } else {
                 let emit_prj_prefix = if context.build_opt.omit_project_prefix {
                     context.project_name != Some(*path)
@@ -3972,7 +4054,13 @@ pub fn symbol_string(token: &VerylaToken, symbol: &Symbol, context: &SymbolConte
             }
             ret.push_str(&token_text);
         }
-        SymbolKind::GenericParameter(_) | SymbolKind::ProtoEntity(_) => (),
+        SymbolKind::GenericParameter(_)
+        | SymbolKind::ProtoEntity(_)
+        | SymbolKind::ProtoInterface(_)
+        | SymbolKind::ProtoPackage(_)
+        | SymbolKind::ProtoConst(_)
+        | SymbolKind::ProtoTypeDef
+        | SymbolKind::ProtoFunction(_) => (),
         SymbolKind::Port(x) => {
             if let Some(ref x) = x.prefix {
                 ret.push_str(x);
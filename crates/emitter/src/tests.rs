@@ -602,3 +602,31 @@ endmodule
 
     assert_eq!(ret, expect);
 }
+
+#[test]
+fn distribution_nominal() {
+    let code = r#"entity EntityA of component #(
+    param N: u32 = uniform(2, 5),
+    param VTH: f64 = uniform(2, 5),
+) {}
+"#;
+
+    let expect = r#"entity prj_EntityA #(
+    parameter int unsigned N   = 4  ,
+    parameter real         VTH = 3.5
+);
+endentity
+//# sourceMappingURL=test.sv.map
+"#;
+
+    let metadata: Metadata =
+        toml::from_str(&Metadata::create_default_toml("prj").unwrap()).unwrap();
+
+    let ret = if cfg!(windows) {
+        emit(&metadata, code).replace("\r\n", "\n")
+    } else {
+        emit(&metadata, code)
+    };
+
+    assert_eq!(ret, expect);
+}
@@ -304,6 +304,17 @@ impl Formatter {
     }
 }
 
+fn entity_type_token(arg: &EntityType) -> &VerylaToken {
+    match arg {
+        EntityType::Component(x) => &x.component.component_token,
+        EntityType::Element(x) => &x.element.element_token,
+        EntityType::Requirement(x) => &x.requirement.requirement_token,
+        EntityType::Subcomponent(x) => &x.subcomponent.subcomponent_token,
+        EntityType::Subsystem(x) => &x.subsystem.subsystem_token,
+        EntityType::System(x) => &x.system.system_token,
+    }
+}
+
 impl VerylaWalker for Formatter {
     /// Semantic action for non-terminal 'VerylaToken'
     fn veryla_token(&mut self, arg: &VerylaToken) {
@@ -1088,6 +1099,23 @@ impl VerylaWalker for Formatter {
         }
     }
 
+    /// Semantic action for non-terminal 'AttributeArgument'
+    fn attribute_argument(&mut self, arg: &AttributeArgument) {
+        match arg {
+            AttributeArgument::LParenAttributeListRParen(x) => {
+                self.l_paren(&x.l_paren);
+                self.attribute_list(&x.attribute_list);
+                self.r_paren(&x.r_paren);
+            }
+            AttributeArgument::EquAttributeItem(x) => {
+                self.space(1);
+                self.equ(&x.equ);
+                self.space(1);
+                self.attribute_item(&x.attribute_item);
+            }
+        }
+    }
+
     /// Semantic action for non-terminal 'LetDeclaration'
     fn let_declaration(&mut self, arg: &LetDeclaration) {
         self.r#let(&arg.r#let);
@@ -1927,6 +1955,10 @@ impl VerylaWalker for Formatter {
         self.entity(&arg.entity);
         self.space(1);
         self.identifier(&arg.identifier);
+        self.space(1);
+        self.veryla_token(&arg.of.of_token);
+        self.space(1);
+        self.veryla_token(entity_type_token(&arg.entity_type));
         if let Some(ref x) = arg.entity_declaration_opt0 {
             self.with_generic_parameter(&x.with_generic_parameter);
         }
@@ -1988,6 +2020,12 @@ impl VerylaWalker for Formatter {
         }
         self.space(1);
         if let Some(ref x) = arg.interface_declaration_opt1 {
+            self.r#for(&x.r#for);
+            self.space(1);
+            self.scoped_identifier(&x.scoped_identifier);
+            self.space(1);
+        }
+        if let Some(ref x) = arg.interface_declaration_opt2 {
             self.with_parameter(&x.with_parameter);
             self.space(1);
         }
@@ -2129,6 +2167,12 @@ impl VerylaWalker for Formatter {
             self.with_generic_parameter(&x.with_generic_parameter);
         }
         self.space(1);
+        if let Some(ref x) = arg.package_declaration_opt1 {
+            self.r#for(&x.r#for);
+            self.space(1);
+            self.scoped_identifier(&x.scoped_identifier);
+            self.space(1);
+        }
         self.token_will_push(&arg.l_brace.l_brace_token);
         for (i, x) in arg.package_declaration_list.iter().enumerate() {
             self.newline_list(i);
@@ -2158,25 +2202,126 @@ impl VerylaWalker for Formatter {
         }
     }
 
-    /// Semantic action for non-terminal 'ProtoEntityDeclaration'
-    fn proto_entity_declaration(&mut self, arg: &ProtoEntityDeclaration) {
-        if let Some(ref x) = arg.proto_entity_declaration_opt {
+    /// Semantic action for non-terminal 'ProtoDeclaration'
+    fn proto_declaration(&mut self, arg: &ProtoDeclaration) {
+        if let Some(ref x) = arg.proto_declaration_opt {
             self.r#pub(&x.r#pub);
             self.space(1);
         }
         self.proto(&arg.proto);
         self.space(1);
+        match &*arg.proto_declaration_group {
+            ProtoDeclarationGroup::ProtoEntityDeclaration(x) => {
+                self.proto_entity_declaration(&x.proto_entity_declaration)
+            }
+            ProtoDeclarationGroup::ProtoInterfaceDeclaration(x) => {
+                self.proto_interface_declaration(&x.proto_interface_declaration)
+            }
+            ProtoDeclarationGroup::ProtoPackageDeclaration(x) => {
+                self.proto_package_declaration(&x.proto_package_declaration)
+            }
+        }
+    }
+
+    /// Semantic action for non-terminal 'ProtoEntityDeclaration'
+    fn proto_entity_declaration(&mut self, arg: &ProtoEntityDeclaration) {
         self.entity(&arg.entity);
         self.space(1);
+        self.veryla_token(&arg.of.of_token);
+        self.space(1);
+        self.veryla_token(entity_type_token(&arg.entity_type));
+        self.space(1);
         self.identifier(&arg.identifier);
+        if let Some(ref x) = arg.proto_entity_declaration_opt {
+            self.space(1);
+            self.with_parameter(&x.with_parameter);
+        }
         if let Some(ref x) = arg.proto_entity_declaration_opt0 {
             self.space(1);
+            self.port_declaration(&x.port_declaration);
+        }
+        self.semicolon(&arg.semicolon);
+    }
+
+    /// Semantic action for non-terminal 'ProtoInterfaceDeclaration'
+    fn proto_interface_declaration(&mut self, arg: &ProtoInterfaceDeclaration) {
+        self.interface(&arg.interface);
+        self.space(1);
+        self.identifier(&arg.identifier);
+        self.space(1);
+        if let Some(ref x) = arg.proto_interface_declaration_opt {
             self.with_parameter(&x.with_parameter);
+            self.space(1);
+        }
+        self.token_will_push(&arg.l_brace.l_brace_token);
+        for (i, x) in arg.proto_interface_declaration_list.iter().enumerate() {
+            self.newline_list(i);
+            self.proto_interface_item(&x.proto_interface_item);
+        }
+        self.newline_list_post(arg.proto_interface_declaration_list.is_empty());
+        self.r_brace(&arg.r_brace);
+    }
+
+    /// Semantic action for non-terminal 'ProtoPackageDeclaration'
+    fn proto_package_declaration(&mut self, arg: &ProtoPackageDeclaration) {
+        self.package(&arg.package);
+        self.space(1);
+        self.identifier(&arg.identifier);
+        self.space(1);
+        self.token_will_push(&arg.l_brace.l_brace_token);
+        for (i, x) in arg.proto_package_declaration_list.iter().enumerate() {
+            self.newline_list(i);
+            self.proto_package_item(&x.proto_package_item);
+        }
+        self.newline_list_post(arg.proto_package_declaration_list.is_empty());
+        self.r_brace(&arg.r_brace);
+    }
+
+    /// Semantic action for non-terminal 'ProtoConstDeclaration'
+    fn proto_const_declaration(&mut self, arg: &ProtoConstDeclaration) {
+        self.r#const(&arg.r#const);
+        self.space(1);
+        self.align_start(align_kind::IDENTIFIER);
+        self.identifier(&arg.identifier);
+        self.align_finish(align_kind::IDENTIFIER);
+        self.colon(&arg.colon);
+        self.space(1);
+        match &*arg.proto_const_declaration_group {
+            ProtoConstDeclarationGroup::ArrayType(x) => {
+                self.array_type(&x.array_type);
+            }
+            ProtoConstDeclarationGroup::Type(x) => {
+                self.align_start(align_kind::TYPE);
+                self.r#type(&x.r#type);
+                self.align_finish(align_kind::TYPE);
+            }
         }
-        if let Some(ref x) = arg.proto_entity_declaration_opt1 {
+        self.semicolon(&arg.semicolon);
+    }
+
+    /// Semantic action for non-terminal 'ProtoTypeDefDeclaration'
+    fn proto_type_def_declaration(&mut self, arg: &ProtoTypeDefDeclaration) {
+        self.r#type(&arg.r#type);
+        self.space(1);
+        self.identifier(&arg.identifier);
+        self.semicolon(&arg.semicolon);
+    }
+
+    /// Semantic action for non-terminal 'ProtoFunctionDeclaration'
+    fn proto_function_declaration(&mut self, arg: &ProtoFunctionDeclaration) {
+        self.function(&arg.function);
+        self.space(1);
+        self.identifier(&arg.identifier);
+        if let Some(ref x) = arg.proto_function_declaration_opt {
             self.space(1);
             self.port_declaration(&x.port_declaration);
         }
+        if let Some(ref x) = arg.proto_function_declaration_opt0 {
+            self.space(1);
+            self.minus_g_t(&x.minus_g_t);
+            self.space(1);
+            self.scalar_type(&x.scalar_type);
+        }
         self.semicolon(&arg.semicolon);
     }
 
@@ -1,2 +1,4 @@
 pub mod formatter;
 pub use formatter::Formatter;
+#[cfg(test)]
+mod tests;
@@ -0,0 +1,36 @@
+use crate::Formatter;
+use veryla_metadata::Metadata;
+use veryla_parser::Parser;
+
+#[track_caller]
+fn format(metadata: &Metadata, code: &str) -> String {
+    let parser = Parser::parse(code, &"").unwrap();
+
+    let mut formatter = Formatter::new(metadata);
+    formatter.format(&parser.veryla);
+    formatter.as_str().to_string()
+}
+
+#[test]
+fn entity_type() {
+    let code = r#"entity   EntityA   of   subsystem(
+    i_a: input logic,
+) {}
+
+entity EntityB of component {}
+"#;
+
+    let expect = r#"entity EntityA of subsystem (
+    i_a: input logic,
+) {}
+
+entity EntityB of component {}
+"#;
+
+    let metadata: Metadata =
+        toml::from_str(&Metadata::create_default_toml("prj").unwrap()).unwrap();
+
+    let ret = format(&metadata, code);
+    assert_eq!(ret, expect);
+    assert_eq!(format(&metadata, &ret), expect);
+}
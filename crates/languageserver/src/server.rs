@@ -11,7 +11,7 @@ use veryla_analyzer::namespace::Namespace;
 use veryla_analyzer::symbol::SymbolKind as VerylaSymbolKind;
 use veryla_analyzer::symbol::{Symbol, TypeKind};
 use veryla_analyzer::symbol_path::SymbolPath;
-use veryla_analyzer::{namespace_table, symbol_table, Analyzer, AnalyzerError};
+use veryla_analyzer::{namespace_table, power_domain_table, symbol_table, Analyzer, AnalyzerError};
 use veryla_formatter::Formatter;
 use veryla_metadata::Metadata;
 use veryla_parser::veryla_token::Token;
@@ -335,11 +335,16 @@ impl Server {
                     VerylaSymbolKind::Entity(_) => SymbolKind::MODULE,
                     VerylaSymbolKind::ProtoEntity(_) => SymbolKind::MODULE,
                     VerylaSymbolKind::Interface(_) => SymbolKind::INTERFACE,
+                    VerylaSymbolKind::ProtoInterface(_) => SymbolKind::INTERFACE,
                     VerylaSymbolKind::Function(_) => SymbolKind::FUNCTION,
                     VerylaSymbolKind::Parameter(_) => SymbolKind::CONSTANT,
                     VerylaSymbolKind::Instance(_) => SymbolKind::OBJECT,
                     VerylaSymbolKind::Block => SymbolKind::NAMESPACE,
                     VerylaSymbolKind::Package(_) => SymbolKind::PACKAGE,
+                    VerylaSymbolKind::ProtoPackage(_) => SymbolKind::PACKAGE,
+                    VerylaSymbolKind::ProtoConst(_) => SymbolKind::CONSTANT,
+                    VerylaSymbolKind::ProtoTypeDef => SymbolKind::TYPE_PARAMETER,
+                    VerylaSymbolKind::ProtoFunction(_) => SymbolKind::FUNCTION,
                     VerylaSymbolKind::Struct(_) => SymbolKind::STRUCT,
                     VerylaSymbolKind::StructMember(_) => SymbolKind::VARIABLE,
                     VerylaSymbolKind::Union(_) => SymbolKind::STRUCT,
@@ -391,7 +396,10 @@ impl Server {
                             SymbolPath::from(finder.token_group.as_slice())
                         };
                         if let Ok(symbol) = symbol_table::resolve((&path, &namespace)) {
-                            let text = symbol.found.kind.to_string();
+                            let mut text = symbol.found.kind.to_string();
+                            if let Some(x) = power_domain_table::get(symbol.found.id) {
+                                text.push_str(&format!(" {x} (inferred)"));
+                            }
                             let hover = Hover {
                                 contents: HoverContents::Scalar(MarkedString::String(text)),
                                 range: None,
@@ -34,6 +34,12 @@ pub struct Build {
     pub exclude_std: bool,
     #[serde(default)]
     pub emit_cond_type: bool,
+    /// Seed to sample distribution built-ins instead of using nominal values
+    #[serde(skip)]
+    pub sample_seed: Option<u64>,
+    /// Directory to build into instead of the output paths of the target
+    #[serde(skip)]
+    pub output_dir: Option<PathBuf>,
 }
 
 #[derive(Clone, Copy, Debug, Default, Serialize, Deserialize, PartialEq, Eq)]
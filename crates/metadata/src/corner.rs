@@ -0,0 +1,108 @@
+use serde::{Deserialize, Serialize};
+use std::collections::BTreeMap;
+use std::fmt;
+
+/// SystemVerilog package name of the `$corner` built-in package
+pub const CORNER_PACKAGE: &str = "veryla_corner";
+
+#[derive(Clone, Debug, Default, Serialize, Deserialize, PartialEq)]
+#[serde(deny_unknown_fields)]
+pub struct Corner {
+    #[serde(default)]
+    pub temperature: Option<f64>,
+    #[serde(default)]
+    pub voltages: BTreeMap<String, f64>,
+    #[serde(default)]
+    pub parameters: BTreeMap<String, CornerValue>,
+    #[serde(default)]
+    pub defines: BTreeMap<String, CornerValue>,
+}
+
+impl Corner {
+    /// Constants exposed through the `$corner` built-in package
+    pub fn constants(&self, name: &str) -> Vec<(String, CornerValue)> {
+        let mut ret = vec![("name".to_string(), CornerValue::String(name.to_string()))];
+        if let Some(x) = self.temperature {
+            ret.push(("temperature".to_string(), CornerValue::Float(x)));
+        }
+        for (domain, x) in &self.voltages {
+            ret.push((domain.clone(), CornerValue::Float(*x)));
+        }
+        ret
+    }
+}
+
+/// Constants of the nominal `$corner` package
+///
+/// Every name declared by any corner is included, and each takes the value of the first corner
+/// declaring it. Names missing from `selected` are filled in the same way.
+pub fn corner_constants(
+    corners: &BTreeMap<String, Corner>,
+    selected: Option<(&str, &Corner)>,
+) -> Vec<(String, CornerValue)> {
+    let mut ret = if let Some((name, corner)) = selected {
+        corner.constants(name)
+    } else {
+        vec![]
+    };
+    for (name, corner) in corners {
+        for (name, value) in corner.constants(name) {
+            if ret.iter().all(|(x, _)| *x != name) {
+                ret.push((name, value));
+            }
+        }
+    }
+    ret
+}
+
+/// SystemVerilog source of the `$corner` built-in package
+pub fn corner_package(constants: &[(String, CornerValue)]) -> String {
+    let mut ret = format!("package {};\n", CORNER_PACKAGE);
+    for (name, value) in constants {
+        ret.push_str(&format!(
+            "    localparam {} {} = {};\n",
+            value.sv_type(),
+            name,
+            value.sv_literal()
+        ));
+    }
+    ret.push_str("endpackage\n");
+    ret
+}
+
+#[derive(Clone, Debug, Serialize, Deserialize, PartialEq)]
+#[serde(untagged)]
+pub enum CornerValue {
+    Integer(i64),
+    Float(f64),
+    String(String),
+}
+
+impl CornerValue {
+    /// SystemVerilog type of the value
+    pub fn sv_type(&self) -> &'static str {
+        match self {
+            CornerValue::Integer(_) => "int",
+            CornerValue::Float(_) => "real",
+            CornerValue::String(_) => "string",
+        }
+    }
+
+    /// SystemVerilog literal of the value
+    pub fn sv_literal(&self) -> String {
+        match self {
+            CornerValue::String(x) => format!("\"{}\"", x),
+            x => x.to_string(),
+        }
+    }
+}
+
+impl fmt::Display for CornerValue {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            CornerValue::Integer(x) => x.fmt(f),
+            CornerValue::Float(x) => write!(f, "{:?}", x),
+            CornerValue::String(x) => x.fmt(f),
+        }
+    }
+}
@@ -1,4 +1,5 @@
 mod build;
+mod corner;
 mod doc;
 mod format;
 mod git;
@@ -13,9 +14,10 @@ mod test;
 #[cfg(test)]
 mod tests;
 pub use build::{Build, BuiltinType, PowerType, FilelistType, EnableType, SourceMapTarget, Target};
+pub use corner::{corner_constants, corner_package, Corner, CornerValue, CORNER_PACKAGE};
 pub use doc::Doc;
 pub use format::Format;
-pub use lint::{Case, Lint};
+pub use lint::{Case, Lint, LintLevel};
 pub use lockfile::Lockfile;
 pub use metadata::{BumpKind, Metadata, UrlPath};
 pub use metadata_error::MetadataError;
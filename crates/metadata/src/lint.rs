@@ -1,5 +1,6 @@
 use regex::Regex;
 use serde::{Deserialize, Serialize};
+use std::collections::BTreeMap;
 use std::fmt;
 
 #[derive(Clone, Debug, Default, Serialize, Deserialize)]
@@ -7,6 +8,25 @@ use std::fmt;
 pub struct Lint {
     #[serde(default)]
     pub naming: LintNaming,
+    /// Level of each diagnostic code
+    #[serde(default)]
+    pub rules: BTreeMap<String, LintLevel>,
+}
+
+impl Lint {
+    pub fn level(&self, code: &str) -> Option<LintLevel> {
+        self.rules.get(code).copied()
+    }
+}
+
+#[derive(Clone, Copy, Debug, PartialEq, Eq, Serialize, Deserialize)]
+pub enum LintLevel {
+    #[serde(rename = "allow")]
+    Allow,
+    #[serde(rename = "warn")]
+    Warn,
+    #[serde(rename = "deny")]
+    Deny,
 }
 
 #[derive(Clone, Debug, Default, Serialize, Deserialize)]
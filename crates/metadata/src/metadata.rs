@@ -1,4 +1,5 @@
 use crate::build::{Build, Target};
+use crate::corner::{Corner, CORNER_PACKAGE};
 use crate::doc::Doc;
 use crate::format::Format;
 use crate::git::Git;
@@ -15,7 +16,7 @@ use regex::Regex;
 use semver::VersionReq;
 use serde::{Deserialize, Serialize};
 use spdx::Expression;
-use std::collections::HashMap;
+use std::collections::{BTreeMap, HashMap};
 use std::env;
 use std::fmt;
 use std::fs;
@@ -48,6 +49,8 @@ pub struct Metadata {
     #[serde(default)]
     pub test: Test,
     #[serde(default)]
+    pub corners: BTreeMap<String, Corner>,
+    #[serde(default)]
     pub dependencies: HashMap<UrlPath, Dependency>,
     #[serde(skip)]
     pub metadata_path: PathBuf,
@@ -320,6 +323,15 @@ version = "0.1.0""###
         self.project_path().join("dependencies")
     }
 
+    pub fn analysis_cache_path(&self) -> PathBuf {
+        self.project_path().join(".build").join("analysis.json")
+    }
+
+    pub fn corner_package_path(&self) -> PathBuf {
+        self.metadata_path
+            .with_file_name(format!("{}.sv", CORNER_PACKAGE))
+    }
+
     pub fn filelist_path(&self) -> PathBuf {
         let filelist_name = match self.build.filelist_type {
             FilelistType::Absolute => format!("{}.f", self.project.name),
@@ -327,7 +339,11 @@ version = "0.1.0""###
             FilelistType::Flgen => format!("{}.list.rb", self.project.name),
         };
 
-        self.metadata_path.with_file_name(filelist_name)
+        if let Some(ref x) = self.build.output_dir {
+            x.join(filelist_name)
+        } else {
+            self.metadata_path.with_file_name(filelist_name)
+        }
     }
 
     pub fn doc_path(&self) -> PathBuf {
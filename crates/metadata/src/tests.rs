@@ -9,6 +9,16 @@ const GIT_IGNORE: &'static str = r#"
 Veryla.lock
 "#;
 
+const LINT_RULES_TOML: &str = r#"
+[project]
+name = "test"
+version = "0.1.0"
+
+[lint.rules]
+unused_variable = "allow"
+latch_inferred = "deny"
+"#;
+
 const TEST_TOML: &'static str = r#"
 [project]
 name = "test"
@@ -25,6 +35,22 @@ target = {type = "source"}
 indent_width = 4
 "#;
 
+const CORNER_TOML: &str = r#"
+[project]
+name = "test"
+version = "0.1.0"
+
+[corners.ss_0v9_125c]
+temperature = 125.0
+voltages = {vdd_core = 0.9, vdd_io = 1.62}
+parameters = {N = 4}
+defines = {SLOW = 1, MODEL = "ss"}
+
+[corners.ff_1v1_m40c]
+temperature = -40.0
+voltages = {vdd_core = 1.1}
+"#;
+
 const MAIN_TOML: &'static str = r#"
 [project]
 name = "main"
@@ -143,6 +169,70 @@ fn check_toml() {
     assert_eq!(metadata.format.indent_width, 4);
 }
 
+#[test]
+fn corners() {
+    let metadata: Metadata = toml::from_str(CORNER_TOML).unwrap();
+    let names: Vec<_> = metadata.corners.keys().cloned().collect();
+    assert_eq!(names, vec!["ff_1v1_m40c", "ss_0v9_125c"]);
+
+    let corner = &metadata.corners["ss_0v9_125c"];
+    assert_eq!(corner.temperature, Some(125.0));
+    assert_eq!(corner.voltages["vdd_io"], 1.62);
+    assert_eq!(corner.parameters["N"], CornerValue::Integer(4));
+    assert_eq!(
+        corner.defines["MODEL"],
+        CornerValue::String("ss".to_string())
+    );
+
+    let constants: Vec<_> = corner
+        .constants("ss_0v9_125c")
+        .into_iter()
+        .map(|(name, value)| format!("{} {} = {}", value.sv_type(), name, value.sv_literal()))
+        .collect();
+    assert_eq!(
+        constants,
+        vec![
+            "string name = \"ss_0v9_125c\"",
+            "real temperature = 125.0",
+            "real vdd_core = 0.9",
+            "real vdd_io = 1.62",
+        ]
+    );
+
+    let ff = &metadata.corners["ff_1v1_m40c"];
+    let constants: Vec<_> = corner_constants(&metadata.corners, Some(("ff_1v1_m40c", ff)))
+        .into_iter()
+        .map(|(name, value)| format!("{} = {}", name, value))
+        .collect();
+    assert_eq!(
+        constants,
+        vec![
+            "name = ff_1v1_m40c",
+            "temperature = -40.0",
+            "vdd_core = 1.1",
+            "vdd_io = 1.62",
+        ]
+    );
+
+    let nominal = corner_constants(&metadata.corners, None);
+    assert_eq!(nominal[0].1, CornerValue::String("ff_1v1_m40c".to_string()));
+    assert!(corner_package(&nominal).starts_with("package veryla_corner;\n"));
+}
+
+#[test]
+fn lint_rules() {
+    let metadata: Metadata = toml::from_str(LINT_RULES_TOML).unwrap();
+    assert_eq!(
+        metadata.lint.level("unused_variable"),
+        Some(LintLevel::Allow)
+    );
+    assert_eq!(metadata.lint.level("latch_inferred"), Some(LintLevel::Deny));
+    assert_eq!(metadata.lint.level("unused_return"), None);
+
+    let invalid = LINT_RULES_TOML.replace("\"deny\"", "\"error\"");
+    assert!(toml::from_str::<Metadata>(&invalid).is_err());
+}
+
 #[test]
 fn search_config() {
     let path = Metadata::search_from_current();
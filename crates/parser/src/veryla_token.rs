@@ -481,6 +481,17 @@ expression_token_range!(Expression02, expression03, expression02_list, expressio
 expression_token_range!(Expression01, expression02, expression01_list, expression02);
 expression_token_range!(Expression, expression01, expression_list, expression01);
 
+impl From<&InstPortItem> for TokenRange {
+    fn from(value: &InstPortItem) -> Self {
+        let mut range: TokenRange = value.identifier.as_ref().into();
+        if let Some(ref x) = value.inst_port_item_opt {
+            let end: TokenRange = x.expression.as_ref().into();
+            range.end = end.end;
+        }
+        range
+    }
+}
+
 impl From<&FixedType> for TokenRange {
     fn from(value: &FixedType) -> Self {
         let beg = match value {
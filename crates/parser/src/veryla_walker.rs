@@ -1847,12 +1847,45 @@ pub trait VerylaWalker {
     fn attribute_item(&mut self, arg: &AttributeItem) {
         before!(self, attribute_item, arg);
         match arg {
-            AttributeItem::Identifier(x) => self.identifier(&x.identifier),
+            AttributeItem::IdentifierAttributeItemOpt(x) => {
+                self.identifier(&x.identifier);
+                if let Some(ref x) = x.attribute_item_opt {
+                    self.attribute_argument(&x.attribute_argument);
+                }
+            }
             AttributeItem::StringLiteral(x) => self.string_literal(&x.string_literal),
+            AttributeItem::NumberAttributeItemOpt0(x) => {
+                self.number(&x.number);
+                if let Some(ref x) = x.attribute_item_opt0 {
+                    self.identifier(&x.identifier);
+                }
+            }
+            AttributeItem::LBracketAttributeListRBracket(x) => {
+                self.l_bracket(&x.l_bracket);
+                self.attribute_list(&x.attribute_list);
+                self.r_bracket(&x.r_bracket);
+            }
         }
         after!(self, attribute_item, arg);
     }
 
+    /// Semantic action for non-terminal 'AttributeArgument'
+    fn attribute_argument(&mut self, arg: &AttributeArgument) {
+        before!(self, attribute_argument, arg);
+        match arg {
+            AttributeArgument::LParenAttributeListRParen(x) => {
+                self.l_paren(&x.l_paren);
+                self.attribute_list(&x.attribute_list);
+                self.r_paren(&x.r_paren);
+            }
+            AttributeArgument::EquAttributeItem(x) => {
+                self.equ(&x.equ);
+                self.attribute_item(&x.attribute_item);
+            }
+        }
+        after!(self, attribute_argument, arg);
+    }
+
     /// Semantic action for non-terminal 'LetDeclaration'
     fn let_declaration(&mut self, arg: &LetDeclaration) {
         before!(self, let_declaration, arg);
@@ -2684,6 +2717,10 @@ pub trait VerylaWalker {
             self.with_generic_parameter(&x.with_generic_parameter);
         }
         if let Some(ref x) = arg.interface_declaration_opt1 {
+            self.r#for(&x.r#for);
+            self.scoped_identifier(&x.scoped_identifier);
+        }
+        if let Some(ref x) = arg.interface_declaration_opt2 {
             self.with_parameter(&x.with_parameter);
         }
         self.l_brace(&arg.l_brace);
@@ -2869,6 +2906,10 @@ pub trait VerylaWalker {
         if let Some(ref x) = arg.package_declaration_opt0 {
             self.with_generic_parameter(&x.with_generic_parameter);
         }
+        if let Some(ref x) = arg.package_declaration_opt1 {
+            self.r#for(&x.r#for);
+            self.scoped_identifier(&x.scoped_identifier);
+        }
         self.l_brace(&arg.l_brace);
         for x in &arg.package_declaration_list {
             self.package_group(&x.package_group);
@@ -2918,24 +2959,142 @@ pub trait VerylaWalker {
         after!(self, package_item, arg);
     }
 
+    /// Semantic action for non-terminal 'ProtoDeclaration'
+    fn proto_declaration(&mut self, arg: &ProtoDeclaration) {
+        before!(self, proto_declaration, arg);
+        if let Some(ref x) = arg.proto_declaration_opt {
+            self.r#pub(&x.r#pub);
+        }
+        self.proto(&arg.proto);
+        match &*arg.proto_declaration_group {
+            ProtoDeclarationGroup::ProtoEntityDeclaration(x) => {
+                self.proto_entity_declaration(&x.proto_entity_declaration)
+            }
+            ProtoDeclarationGroup::ProtoInterfaceDeclaration(x) => {
+                self.proto_interface_declaration(&x.proto_interface_declaration)
+            }
+            ProtoDeclarationGroup::ProtoPackageDeclaration(x) => {
+                self.proto_package_declaration(&x.proto_package_declaration)
+            }
+        }
+        after!(self, proto_declaration, arg);
+    }
+
     /// Semantic action for non-terminal 'ProtoEntityDeclaration'
     fn proto_entity_declaration(&mut self, arg: &ProtoEntityDeclaration) {
         before!(self, proto_entity_declaration, arg);
-        if let Some(ref x) = arg.proto_entity_declaration_opt {
-            self.r#pub(&x.r#pub);
-        }
         self.entity(&arg.entity);
         self.identifier(&arg.identifier);
-        if let Some(ref x) = arg.proto_entity_declaration_opt0 {
+        if let Some(ref x) = arg.proto_entity_declaration_opt {
             self.with_parameter(&x.with_parameter);
         }
-        if let Some(ref x) = arg.proto_entity_declaration_opt1 {
+        if let Some(ref x) = arg.proto_entity_declaration_opt0 {
             self.port_declaration(&x.port_declaration);
         }
         self.semicolon(&arg.semicolon);
         after!(self, proto_entity_declaration, arg);
     }
 
+    /// Semantic action for non-terminal 'ProtoInterfaceDeclaration'
+    fn proto_interface_declaration(&mut self, arg: &ProtoInterfaceDeclaration) {
+        before!(self, proto_interface_declaration, arg);
+        self.interface(&arg.interface);
+        self.identifier(&arg.identifier);
+        if let Some(ref x) = arg.proto_interface_declaration_opt {
+            self.with_parameter(&x.with_parameter);
+        }
+        self.l_brace(&arg.l_brace);
+        for x in &arg.proto_interface_declaration_list {
+            self.proto_interface_item(&x.proto_interface_item);
+        }
+        self.r_brace(&arg.r_brace);
+        after!(self, proto_interface_declaration, arg);
+    }
+
+    /// Semantic action for non-terminal 'ProtoInterfaceItem'
+    fn proto_interface_item(&mut self, arg: &ProtoInterfaceItem) {
+        before!(self, proto_interface_item, arg);
+        match arg {
+            ProtoInterfaceItem::VarDeclaration(x) => self.var_declaration(&x.var_declaration),
+            ProtoInterfaceItem::ProtoFunctionDeclaration(x) => {
+                self.proto_function_declaration(&x.proto_function_declaration)
+            }
+            ProtoInterfaceItem::ModportDeclaration(x) => {
+                self.modport_declaration(&x.modport_declaration)
+            }
+        }
+        after!(self, proto_interface_item, arg);
+    }
+
+    /// Semantic action for non-terminal 'ProtoPackageDeclaration'
+    fn proto_package_declaration(&mut self, arg: &ProtoPackageDeclaration) {
+        before!(self, proto_package_declaration, arg);
+        self.package(&arg.package);
+        self.identifier(&arg.identifier);
+        self.l_brace(&arg.l_brace);
+        for x in &arg.proto_package_declaration_list {
+            self.proto_package_item(&x.proto_package_item);
+        }
+        self.r_brace(&arg.r_brace);
+        after!(self, proto_package_declaration, arg);
+    }
+
+    /// Semantic action for non-terminal 'ProtoPackageItem'
+    fn proto_package_item(&mut self, arg: &ProtoPackageItem) {
+        before!(self, proto_package_item, arg);
+        match arg {
+            ProtoPackageItem::ProtoConstDeclaration(x) => {
+                self.proto_const_declaration(&x.proto_const_declaration)
+            }
+            ProtoPackageItem::ProtoTypeDefDeclaration(x) => {
+                self.proto_type_def_declaration(&x.proto_type_def_declaration)
+            }
+            ProtoPackageItem::ProtoFunctionDeclaration(x) => {
+                self.proto_function_declaration(&x.proto_function_declaration)
+            }
+        }
+        after!(self, proto_package_item, arg);
+    }
+
+    /// Semantic action for non-terminal 'ProtoConstDeclaration'
+    fn proto_const_declaration(&mut self, arg: &ProtoConstDeclaration) {
+        before!(self, proto_const_declaration, arg);
+        self.r#const(&arg.r#const);
+        self.identifier(&arg.identifier);
+        self.colon(&arg.colon);
+        match &*arg.proto_const_declaration_group {
+            ProtoConstDeclarationGroup::ArrayType(x) => self.array_type(&x.array_type),
+            ProtoConstDeclarationGroup::Type(x) => self.r#type(&x.r#type),
+        }
+        self.semicolon(&arg.semicolon);
+        after!(self, proto_const_declaration, arg);
+    }
+
+    /// Semantic action for non-terminal 'ProtoTypeDefDeclaration'
+    fn proto_type_def_declaration(&mut self, arg: &ProtoTypeDefDeclaration) {
+        before!(self, proto_type_def_declaration, arg);
+        self.r#type(&arg.r#type);
+        self.identifier(&arg.identifier);
+        self.semicolon(&arg.semicolon);
+        after!(self, proto_type_def_declaration, arg);
+    }
+
+    /// Semantic action for non-terminal 'ProtoFunctionDeclaration'
+    fn proto_function_declaration(&mut self, arg: &ProtoFunctionDeclaration) {
+        before!(self, proto_function_declaration, arg);
+        self.function(&arg.function);
+        self.identifier(&arg.identifier);
+        if let Some(ref x) = arg.proto_function_declaration_opt {
+            self.port_declaration(&x.port_declaration);
+        }
+        if let Some(ref x) = arg.proto_function_declaration_opt0 {
+            self.minus_g_t(&x.minus_g_t);
+            self.scalar_type(&x.scalar_type);
+        }
+        self.semicolon(&arg.semicolon);
+        after!(self, proto_function_declaration, arg);
+    }
+
     /// Semantic action for non-terminal 'EmbedDeclaration'
     fn embed_declaration(&mut self, arg: &EmbedDeclaration) {
         before!(self, embed_declaration, arg);
@@ -2998,9 +3157,7 @@ pub trait VerylaWalker {
             DescriptionItem::PackageDeclaration(x) => {
                 self.package_declaration(&x.package_declaration)
             }
-            DescriptionItem::ProtoEntityDeclaration(x) => {
-                self.proto_entity_declaration(&x.proto_entity_declaration)
-            }
+            DescriptionItem::ProtoDeclaration(x) => self.proto_declaration(&x.proto_declaration),
             DescriptionItem::ImportDeclaration(x) => self.import_declaration(&x.import_declaration),
             DescriptionItem::EmbedDeclaration(x) => self.embed_declaration(&x.embed_declaration),
             DescriptionItem::IncludeDeclaration(x) => {
@@ -0,0 +1,295 @@
+//! Analysis results persisted between `veryla check` / `veryla build`.
+//!
+//! Only pass3 is skipped for unchanged files. Persisting symbols to skip parse, pass1 and
+//! pass2 is out of scope: tokens and identifiers are interned per process and the syntax
+//! tree can't be serialized, so all files are still parsed and go through pass1/pass2.
+//! The cache records the files each file refers to and the pass3 diagnostics of each file,
+//! and pass3 is skipped for files which are not affected by changed files while their
+//! diagnostics are reported again from the cache.
+
+use log::debug;
+use miette::{Diagnostic, IntoDiagnostic, LabeledSpan, NamedSource, Result, Severity, SourceCode};
+use serde::{Deserialize, Serialize};
+use std::collections::{BTreeMap, BTreeSet, HashMap, HashSet};
+use std::fmt;
+use std::fs;
+use std::path::{Path, PathBuf};
+use veryla_analyzer::{symbol_table, AnalyzerError};
+use veryla_metadata::Metadata;
+use veryla_parser::veryla_token::TokenSource;
+
+#[derive(Default, Serialize, Deserialize)]
+pub struct AnalysisCache {
+    version: String,
+    metadata_hash: u64,
+    files: BTreeMap<PathBuf, CacheEntry>,
+    #[serde(skip)]
+    path: PathBuf,
+    #[serde(skip)]
+    hashes: BTreeMap<PathBuf, u64>,
+}
+
+#[derive(Clone, Default, Serialize, Deserialize)]
+struct CacheEntry {
+    hash: u64,
+    dependencies: BTreeSet<PathBuf>,
+    diagnostics: Vec<CachedDiagnostic>,
+}
+
+/// Diagnostic of pass3 which is reported again while the file is unchanged
+#[derive(Clone, Debug, Serialize, Deserialize)]
+pub struct CachedDiagnostic {
+    code: Option<String>,
+    severity: Option<String>,
+    message: String,
+    help: Option<String>,
+    url: Option<String>,
+    /// Name of the source, which is the path of the analyzed file
+    name: String,
+    /// Offset, length and label of each span
+    labels: Vec<(usize, usize, Option<String>)>,
+    #[serde(skip)]
+    source: Option<NamedSource<String>>,
+}
+
+impl CachedDiagnostic {
+    pub fn new(x: &AnalyzerError) -> Self {
+        let severity = x.severity().map(|x| {
+            match x {
+                Severity::Advice => "advice",
+                Severity::Warning => "warning",
+                Severity::Error => "error",
+            }
+            .to_string()
+        });
+        let labels = x
+            .labels()
+            .into_iter()
+            .flatten()
+            .map(|x| (x.offset(), x.len(), x.label().map(|x| x.to_string())))
+            .collect();
+        Self {
+            code: x.code().map(|x| x.to_string()),
+            severity,
+            message: x.to_string(),
+            help: x.help().map(|x| x.to_string()),
+            url: x.url().map(|x| x.to_string()),
+            name: x.location().map(|x| x.0).unwrap_or_default(),
+            labels,
+            source: None,
+        }
+    }
+}
+
+impl fmt::Display for CachedDiagnostic {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        self.message.fmt(f)
+    }
+}
+
+impl std::error::Error for CachedDiagnostic {}
+
+impl Diagnostic for CachedDiagnostic {
+    fn code<'a>(&'a self) -> Option<Box<dyn fmt::Display + 'a>> {
+        self.code
+            .as_ref()
+            .map(|x| Box::new(x) as Box<dyn fmt::Display>)
+    }
+
+    fn severity(&self) -> Option<Severity> {
+        match self.severity.as_deref() {
+            Some("advice") => Some(Severity::Advice),
+            Some("warning") => Some(Severity::Warning),
+            Some("error") => Some(Severity::Error),
+            _ => None,
+        }
+    }
+
+    fn help<'a>(&'a self) -> Option<Box<dyn fmt::Display + 'a>> {
+        self.help
+            .as_ref()
+            .map(|x| Box::new(x) as Box<dyn fmt::Display>)
+    }
+
+    fn url<'a>(&'a self) -> Option<Box<dyn fmt::Display + 'a>> {
+        self.url
+            .as_ref()
+            .map(|x| Box::new(x) as Box<dyn fmt::Display>)
+    }
+
+    fn source_code(&self) -> Option<&dyn SourceCode> {
+        self.source.as_ref().map(|x| x as &dyn SourceCode)
+    }
+
+    fn labels(&self) -> Option<Box<dyn Iterator<Item = LabeledSpan> + '_>> {
+        Some(Box::new(self.labels.iter().map(|(offset, len, label)| {
+            LabeledSpan::new(label.clone(), *offset, *len)
+        })))
+    }
+}
+
+/// FNV-1a is used because it is stable across toolchains unlike `DefaultHasher`
+fn hash(x: &str) -> u64 {
+    x.bytes().fold(0xcbf29ce484222325, |acc, x| {
+        (acc ^ x as u64).wrapping_mul(0x100000001b3)
+    })
+}
+
+impl AnalysisCache {
+    pub fn load(metadata: &Metadata) -> Self {
+        let path = metadata.analysis_cache_path();
+        let version = env!("CARGO_PKG_VERSION").to_string();
+        let metadata_hash = hash(&fs::read_to_string(&metadata.metadata_path).unwrap_or_default());
+
+        let cache: Option<AnalysisCache> = fs::read_to_string(&path)
+            .ok()
+            .and_then(|x| serde_json::from_str(&x).ok());
+
+        let files = match cache {
+            Some(x) if x.version == version && x.metadata_hash == metadata_hash => x.files,
+            _ => BTreeMap::new(),
+        };
+
+        Self {
+            version,
+            metadata_hash,
+            files,
+            path,
+            hashes: BTreeMap::new(),
+        }
+    }
+
+    pub fn add_file(&mut self, path: &Path, input: &str) {
+        self.hashes.insert(path.to_path_buf(), hash(input));
+    }
+
+    /// Files which should be analyzed again after pass2
+    ///
+    /// Changed files, files referred by them before and after the change, and files
+    /// depending on any of them are included. Referred files are needed because whether
+    /// their declarations are used depends on the changed files.
+    pub fn dirty_files(&self) -> HashSet<PathBuf> {
+        let mut ret: HashSet<PathBuf> = self
+            .hashes
+            .iter()
+            .filter(|(path, hash)| self.files.get(*path).map(|x| x.hash) != Some(**hash))
+            .map(|(path, _)| path.clone())
+            .collect();
+
+        // Removed files affect files depending on them too
+        let removed: Vec<_> = self
+            .files
+            .keys()
+            .filter(|x| !self.hashes.contains_key(*x) && !x.exists())
+            .cloned()
+            .collect();
+        ret.extend(removed);
+
+        let dependencies = reference_graph();
+        let mut referred = Vec::new();
+        for path in &ret {
+            if let Some(x) = self.files.get(path) {
+                referred.extend(x.dependencies.iter().cloned());
+            }
+            if let Some(x) = dependencies.get(path) {
+                referred.extend(x.iter().cloned());
+            }
+        }
+        ret.extend(referred);
+
+        loop {
+            let dependents: Vec<_> = self
+                .files
+                .iter()
+                .filter(|(path, entry)| {
+                    !ret.contains(*path) && entry.dependencies.iter().any(|x| ret.contains(x))
+                })
+                .map(|(path, _)| path.clone())
+                .collect();
+            if dependents.is_empty() {
+                break;
+            }
+            ret.extend(dependents);
+        }
+
+        ret.retain(|x| self.hashes.contains_key(x));
+        ret
+    }
+
+    /// Pass3 diagnostics of unchanged `path` whose text is `input`
+    pub fn diagnostics(&self, path: &Path, input: &str) -> Vec<CachedDiagnostic> {
+        let Some(entry) = self.files.get(path) else {
+            return Vec::new();
+        };
+        entry
+            .diagnostics
+            .iter()
+            .map(|x| {
+                let mut x = x.clone();
+                x.source = Some(NamedSource::new(x.name.clone(), input.to_string()));
+                x
+            })
+            .collect()
+    }
+
+    /// Rebuild entries from the analyzed symbol table.
+    /// `diagnostics` are pass3 diagnostics of analyzed files, and the diagnostics of
+    /// skipped files are taken over from the previous entries.
+    pub fn update(&mut self, diagnostics: &HashMap<PathBuf, Vec<CachedDiagnostic>>) {
+        let mut dependencies = reference_graph();
+        let mut previous = std::mem::take(&mut self.files);
+
+        // Entries of files which were not given this time are kept as long as they exist
+        for (path, entry) in &previous {
+            if !self.hashes.contains_key(path) && path.exists() {
+                self.files.insert(path.clone(), entry.clone());
+            }
+        }
+
+        for (path, hash) in &self.hashes {
+            let diagnostics = match diagnostics.get(path) {
+                Some(x) => x.clone(),
+                None => previous
+                    .remove(path)
+                    .map(|x| x.diagnostics)
+                    .unwrap_or_default(),
+            };
+            let entry = CacheEntry {
+                hash: *hash,
+                dependencies: dependencies.remove(path).unwrap_or_default(),
+                diagnostics,
+            };
+            self.files.insert(path.clone(), entry);
+        }
+    }
+
+    pub fn save(&self) -> Result<()> {
+        if let Some(dir) = self.path.parent() {
+            fs::create_dir_all(dir).into_diagnostic()?;
+        }
+        let text = serde_json::to_string(self).into_diagnostic()?;
+        fs::write(&self.path, text).into_diagnostic()?;
+        debug!("Output file ({})", self.path.to_string_lossy());
+        Ok(())
+    }
+}
+
+/// Files which each file refers to in the analyzed symbol table
+fn reference_graph() -> BTreeMap<PathBuf, BTreeSet<PathBuf>> {
+    let mut ret: BTreeMap<PathBuf, BTreeSet<PathBuf>> = BTreeMap::new();
+    for symbol in symbol_table::get_all() {
+        let TokenSource::File(defined) = symbol.token.source else {
+            continue;
+        };
+        let defined = PathBuf::from(format!("{}", defined));
+        for reference in &symbol.references {
+            if let TokenSource::File(x) = reference.source {
+                let referred = PathBuf::from(format!("{}", x));
+                if referred != defined {
+                    ret.entry(referred).or_default().insert(defined.clone());
+                }
+            }
+        }
+    }
+    ret
+}
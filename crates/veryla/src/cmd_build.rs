@@ -1,3 +1,4 @@
+use crate::analysis_cache::{AnalysisCache, CachedDiagnostic};
 use crate::cmd_check::CheckError;
 use crate::OptBuild;
 use log::{debug, info};
@@ -12,7 +13,9 @@ use veryla_analyzer::namespace::Namespace;
 use veryla_analyzer::symbol::SymbolKind;
 use veryla_analyzer::{symbol_table, type_dag, Analyzer};
 use veryla_emitter::Emitter;
-use veryla_metadata::{FilelistType, Metadata, SourceMapTarget, Target};
+use veryla_metadata::{
+    corner_constants, corner_package, FilelistType, Metadata, SourceMapTarget, Target,
+};
 use veryla_parser::{resource_table, veryla_token::TokenSource, Parser};
 use veryla_path::PathSet;
 
@@ -28,7 +31,8 @@ impl CmdBuild {
     pub fn exec(&self, metadata: &mut Metadata, include_tests: bool) -> Result<bool> {
         let paths = metadata.paths(&self.opt.files, true)?;
 
-        let mut check_error = CheckError::default();
+        let mut cache = AnalysisCache::load(metadata);
+        let mut check_error = CheckError::new(metadata, self.opt.deny_warnings);
         let mut contexts = Vec::new();
 
         for path in &paths {
@@ -37,6 +41,7 @@ impl CmdBuild {
             let input = fs::read_to_string(&path.src)
                 .into_diagnostic()
                 .wrap_err("")?;
+            cache.add_file(&path.src, &input);
             let parser = Parser::parse(&input, &path.src)?;
 
             let analyzer = Analyzer::new(metadata);
@@ -53,33 +58,47 @@ impl CmdBuild {
             check_error = check_error.append(&mut errors).check_err()?;
         }
 
+        let dirty_files = cache.dirty_files();
+        let mut diagnostics = HashMap::new();
         for (path, input, parser, analyzer) in &contexts {
+            if !dirty_files.contains(&path.src) {
+                let mut errors = cache.diagnostics(&path.src, input);
+                check_error = check_error.append(&mut errors).check_err()?;
+                continue;
+            }
             let mut errors = analyzer.analyze_pass3(&path.prj, input, &path.src, &parser.veryla);
+            let cached = errors.iter().map(CachedDiagnostic::new).collect();
+            diagnostics.insert(path.src.clone(), cached);
             check_error = check_error.append(&mut errors).check_err()?;
         }
 
+        // Elaboration spans all files, so it runs regardless of the cache
+        let inputs: Vec<_> = contexts
+            .iter()
+            .map(|(_, input, parser, _)| (input.as_str(), &parser.veryla))
+            .collect();
+        let analyzer = Analyzer::new(metadata);
+        let mut errors = analyzer.analyze_elaboration(&metadata.project.name, &inputs);
+        check_error = check_error.append(&mut errors).check_err()?;
+
+        cache.update(&diagnostics);
+        cache.save()?;
+
         let temp_dir = if let Target::Bundle { .. } = &metadata.build.target {
             Some(TempDir::new().into_diagnostic()?)
         } else {
             None
         };
 
+        let output_dir = if let Some(ref temp_dir) = temp_dir {
+            Some(temp_dir.path())
+        } else {
+            metadata.build.output_dir.as_deref()
+        };
+
         for (path, input, parser, _) in &contexts {
-            let (dst, map) = if let Some(ref temp_dir) = temp_dir {
-                let dst_temp = temp_dir.path().join(
-                    path.dst
-                        .strip_prefix(metadata.project_path())
-                        .into_diagnostic()?,
-                );
-                let map_temp = temp_dir.path().join(
-                    path.map
-                        .strip_prefix(metadata.project_path())
-                        .into_diagnostic()?,
-                );
-                (dst_temp, map_temp)
-            } else {
-                (path.dst.clone(), path.map.clone())
-            };
+            let dst = Self::output_path(metadata, output_dir, &path.dst)?;
+            let map = Self::output_path(metadata, output_dir, &path.map)?;
 
             let mut emitter = Emitter::new(metadata, &path.src, &dst, &map);
             emitter.emit(&path.prj, &parser.veryla);
@@ -130,16 +149,31 @@ impl CmdBuild {
         Ok(true)
     }
 
+    /// Rebase an output path of the project into `output_dir`
+    fn output_path(metadata: &Metadata, output_dir: Option<&Path>, path: &Path) -> Result<PathBuf> {
+        if let Some(output_dir) = output_dir {
+            let relative = path
+                .strip_prefix(metadata.project_path())
+                .into_diagnostic()?;
+            Ok(output_dir.join(relative))
+        } else {
+            Ok(path.to_path_buf())
+        }
+    }
+
     fn gen_filelist_line(&self, metadata: &Metadata, path: &Path) -> Result<String> {
         let base_path = metadata.project_path();
         let path = path.canonicalize().into_diagnostic()?;
+        if metadata.build.filelist_type == FilelistType::Absolute {
+            return Ok(format!("{}\n", path.to_string_lossy()));
+        }
+
         let relative = path.strip_prefix(&base_path).into_diagnostic()?;
         Ok(match metadata.build.filelist_type {
-            FilelistType::Absolute => format!("{}\n", path.to_string_lossy()),
-            FilelistType::Relative => format!("{}\n", relative.to_string_lossy()),
             FilelistType::Flgen => {
                 format!("source_file '{}'\n", relative.to_string_lossy())
             }
+            _ => format!("{}\n", relative.to_string_lossy()),
         })
     }
 
@@ -155,19 +189,32 @@ impl CmdBuild {
 
         let paths = Self::sort_filelist(metadata, paths, include_tests);
 
+        // Test runners provide the `$corner` package of the selected corner
+        let corner_package = if include_tests || metadata.corners.is_empty() {
+            None
+        } else {
+            Some(corner_package(&corner_constants(&metadata.corners, None)))
+        };
+
         let text = if let Target::Bundle { path } = &metadata.build.target {
             let temp_dir = temp_dir.unwrap();
-            let mut text = String::new();
-            let target_path = base_path.join(path);
+            let mut text = corner_package.unwrap_or_default();
+            let target_path = Self::output_path(
+                metadata,
+                metadata.build.output_dir.as_deref(),
+                &base_path.join(path),
+            )?;
 
             for path in paths {
-                let dst = temp_dir
-                    .path()
-                    .join(path.dst.strip_prefix(&base_path).into_diagnostic()?);
-
+                let dst = Self::output_path(metadata, Some(temp_dir.path()), &path.dst)?;
                 text.push_str(&fs::read_to_string(&dst).into_diagnostic()?);
             }
 
+            let target_dir = target_path.parent().unwrap();
+            if !target_dir.exists() {
+                std::fs::create_dir_all(target_dir).into_diagnostic()?;
+            }
+
             debug!("Output file ({})", target_path.to_string_lossy());
             let mut file = OpenOptions::new()
                 .create(true)
@@ -181,8 +228,16 @@ impl CmdBuild {
             self.gen_filelist_line(metadata, &target_path)?
         } else {
             let mut text = String::new();
+            if let Some(corner_package) = corner_package {
+                let corner_path = metadata.corner_package_path();
+                debug!("Output file ({})", corner_path.to_string_lossy());
+                fs::write(&corner_path, corner_package).into_diagnostic()?;
+                text.push_str(&self.gen_filelist_line(metadata, &corner_path)?);
+            }
             for path in paths {
-                let line = self.gen_filelist_line(metadata, &path.dst)?;
+                let dst =
+                    Self::output_path(metadata, metadata.build.output_dir.as_deref(), &path.dst)?;
+                let line = self.gen_filelist_line(metadata, &dst)?;
                 text.push_str(&line);
             }
             text
@@ -1,35 +1,70 @@
+use crate::analysis_cache::{AnalysisCache, CachedDiagnostic};
 use crate::OptCheck;
-use log::info;
+use log::{info, warn};
 use miette::{self, Diagnostic, IntoDiagnostic, Result, Severity, WrapErr};
+use std::collections::{BTreeMap, HashMap};
 use std::fs;
+use std::path::PathBuf;
 use thiserror::Error;
-use veryla_analyzer::{Analyzer, AnalyzerError};
-use veryla_metadata::Metadata;
+use veryla_analyzer::{Analyzer, AnalyzerError, TextEdit};
+use veryla_formatter::Formatter;
+use veryla_metadata::{Lint, LintLevel, Metadata};
 use veryla_parser::Parser;
 
 pub struct CmdCheck {
     opt: OptCheck,
 }
 
+/// Diagnostic of the analysis, or replayed from the analysis cache
+#[derive(Error, Diagnostic, Debug)]
+pub enum CheckDiagnostic {
+    #[error(transparent)]
+    #[diagnostic(transparent)]
+    Analyzer(#[from] AnalyzerError),
+    #[error(transparent)]
+    #[diagnostic(transparent)]
+    Cached(#[from] CachedDiagnostic),
+}
+
 #[derive(Error, Diagnostic, Debug, Default)]
 #[error("veryla check failed")]
 pub struct CheckError {
     #[related]
-    pub related: Vec<AnalyzerError>,
+    pub related: Vec<CheckDiagnostic>,
+    lint_opt: Lint,
+    deny_warnings: bool,
 }
 
 impl CheckError {
-    pub fn append(mut self, x: &mut Vec<AnalyzerError>) -> Self {
-        self.related.append(x);
+    pub fn new(metadata: &Metadata, deny_warnings: bool) -> Self {
+        Self {
+            related: Vec::new(),
+            lint_opt: metadata.lint.clone(),
+            deny_warnings,
+        }
+    }
+
+    /// Whether `x` fails the check under the configured lint levels
+    fn is_denied(&self, x: &CheckDiagnostic) -> bool {
+        let level = x.code().and_then(|x| self.lint_opt.level(&x.to_string()));
+        match level {
+            Some(LintLevel::Allow) => false,
+            Some(LintLevel::Warn) => self.deny_warnings,
+            Some(LintLevel::Deny) => true,
+            None => match x.severity() {
+                Some(Severity::Warning) | Some(Severity::Advice) => self.deny_warnings,
+                Some(Severity::Error) | None => true,
+            },
+        }
+    }
+
+    pub fn append<T: Into<CheckDiagnostic>>(mut self, x: &mut Vec<T>) -> Self {
+        self.related.extend(x.drain(..).map(|x| x.into()));
         self
     }
 
     pub fn check_err(self) -> Result<Self> {
-        if self
-            .related
-            .iter()
-            .all(|x| !matches!(x.severity(), Some(Severity::Error) | None))
-        {
+        if self.related.iter().all(|x| !self.is_denied(x)) {
             Ok(self)
         } else {
             Err(self.into())
@@ -51,21 +86,38 @@ impl CmdCheck {
     }
 
     pub fn exec(&self, metadata: &mut Metadata) -> Result<bool> {
-        let paths = metadata.paths(&self.opt.files, true)?;
+        let mut fixes = Fixes::default();
+        let ret = self.analyze(metadata, &mut fixes);
+        if self.opt.fix {
+            fixes.apply(metadata)?;
+        }
+        ret
+    }
 
-        let mut check_error = CheckError::default();
-        let mut contexts = Vec::new();
+    fn analyze(&self, metadata: &mut Metadata, fixes: &mut Fixes) -> Result<bool> {
+        let paths = metadata.paths(&self.opt.files, true)?;
 
+        let mut cache = AnalysisCache::load(metadata);
+        let mut inputs = Vec::new();
         for path in &paths {
-            info!("Processing file ({})", path.src.to_string_lossy());
-
             let input = fs::read_to_string(&path.src)
                 .into_diagnostic()
                 .wrap_err("")?;
+            cache.add_file(&path.src, &input);
+            inputs.push((path, input));
+        }
+
+        let mut check_error = CheckError::new(metadata, self.opt.deny_warnings);
+        let mut contexts = Vec::new();
+
+        for (path, input) in inputs {
+            info!("Processing file ({})", path.src.to_string_lossy());
+
             let parser = Parser::parse(&input, &path.src)?;
 
             let analyzer = Analyzer::new(metadata);
             let mut errors = analyzer.analyze_pass1(&path.prj, &input, &path.src, &parser.veryla);
+            fixes.add(&errors);
             check_error = check_error.append(&mut errors).check_err()?;
 
             contexts.push((path, input, parser, analyzer));
@@ -75,15 +127,94 @@ impl CmdCheck {
 
         for (path, input, parser, analyzer) in &contexts {
             let mut errors = analyzer.analyze_pass2(&path.prj, input, &path.src, &parser.veryla);
+            fixes.add(&errors);
             check_error = check_error.append(&mut errors).check_err()?;
         }
 
+        // Fixes need diagnostics of all files, which are not cached
+        let dirty_files = if self.opt.fix {
+            contexts.iter().map(|x| x.0.src.clone()).collect()
+        } else {
+            cache.dirty_files()
+        };
+        if dirty_files.is_empty() {
+            info!("All files are up to date");
+        }
+
+        let mut diagnostics = HashMap::new();
         for (path, input, parser, analyzer) in &contexts {
+            if !dirty_files.contains(&path.src) {
+                let mut errors = cache.diagnostics(&path.src, input);
+                check_error = check_error.append(&mut errors).check_err()?;
+                continue;
+            }
             let mut errors = analyzer.analyze_pass3(&path.prj, input, &path.src, &parser.veryla);
+            let cached = errors.iter().map(CachedDiagnostic::new).collect();
+            diagnostics.insert(path.src.clone(), cached);
+            fixes.add(&errors);
             check_error = check_error.append(&mut errors).check_err()?;
         }
 
+        // Elaboration spans all files, so it runs regardless of the cache
+        let inputs: Vec<_> = contexts
+            .iter()
+            .map(|(_, input, parser, _)| (input.as_str(), &parser.veryla))
+            .collect();
+        let analyzer = Analyzer::new(metadata);
+        let mut errors = analyzer.analyze_elaboration(&metadata.project.name, &inputs);
+        fixes.add(&errors);
+        check_error = check_error.append(&mut errors).check_err()?;
+
+        cache.update(&diagnostics);
+        cache.save()?;
+
         let _ = check_error.check_all()?;
         Ok(true)
     }
 }
+
+/// Fixes of diagnostics collected for each file
+#[derive(Default)]
+struct Fixes(BTreeMap<PathBuf, Vec<Vec<TextEdit>>>);
+
+impl Fixes {
+    fn add(&mut self, errors: &[AnalyzerError]) {
+        for x in errors {
+            if x.fixes().is_empty() {
+                continue;
+            }
+            if let Some((path, _, _)) = x.location() {
+                self.0
+                    .entry(PathBuf::from(path))
+                    .or_default()
+                    .push(x.fixes().to_vec());
+            }
+        }
+    }
+
+    /// Apply fixes and format the touched files
+    fn apply(&self, metadata: &Metadata) -> Result<()> {
+        for (path, fixes) in &self.0 {
+            let input = fs::read_to_string(path).into_diagnostic()?;
+            let fixes: Vec<_> = fixes.iter().map(|x| x.as_slice()).collect();
+            let (output, count) = TextEdit::apply(&input, &fixes);
+            if count == 0 {
+                continue;
+            }
+
+            let Ok(parser) = Parser::parse(&output, path) else {
+                warn!(
+                    "Skipped fixes because the result can't be parsed ({})",
+                    path.to_string_lossy()
+                );
+                continue;
+            };
+            let mut formatter = Formatter::new(metadata);
+            formatter.format(&parser.veryla);
+
+            fs::write(path, formatter.as_str()).into_diagnostic()?;
+            info!("Fixed {} issue(s) ({})", count, path.to_string_lossy());
+        }
+        Ok(())
+    }
+}
@@ -41,6 +41,16 @@ impl CmdClean {
             fs::remove_file(&filelist_path).into_diagnostic()?;
         }
 
+        let analysis_cache_dir = metadata
+            .analysis_cache_path()
+            .parent()
+            .unwrap()
+            .to_path_buf();
+        if analysis_cache_dir.exists() {
+            info!("Removing dir  ({})", analysis_cache_dir.to_string_lossy());
+            fs::remove_dir_all(&analysis_cache_dir).into_diagnostic()?;
+        }
+
         let doc_path = metadata.doc_path();
         if doc_path.exists() {
             info!("Removing dir  ({})", doc_path.to_string_lossy());
@@ -67,6 +67,16 @@ impl CmdDump {
             println!("{}", veryla_analyzer::unsafe_table::dump());
         }
 
+        if self.opt.elaborated {
+            let inputs: Vec<_> = contexts
+                .iter()
+                .map(|(_, input, parser, _)| (input.as_str(), &parser.veryla))
+                .collect();
+            let (tree, _) =
+                Analyzer::elaborate(&metadata.project.name, &inputs, self.opt.top.as_deref());
+            println!("{}", tree.dump());
+        }
+
         Ok(true)
     }
 }
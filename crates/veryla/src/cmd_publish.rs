@@ -38,7 +38,7 @@ impl CmdPublish {
             }
         }
 
-        let mut check_error = CheckError::default();
+        let mut check_error = CheckError::new(metadata, false);
         let mut contexts = Vec::new();
 
         for path in &paths {
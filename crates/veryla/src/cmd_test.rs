@@ -1,11 +1,12 @@
 use crate::cmd_build::CmdBuild;
-use crate::runner::{Cocotb, CocotbSource, Vcs, Verilator, Vivado};
-use crate::{OptBuild, OptTest};
+use crate::runner::{Cocotb, CocotbSource, SweepResult, TestPoint, Vcs, Verilator, Vivado};
+use crate::{MessageFormat, OptBuild, OptTest};
 use log::{error, info};
-use miette::Result;
+use miette::{bail, IntoDiagnostic, Result};
+use tempfile::TempDir;
 use veryla_analyzer::symbol::{SymbolKind, TestType};
-use veryla_analyzer::symbol_table;
-use veryla_metadata::{FilelistType, Metadata, SimType};
+use veryla_analyzer::{symbol_table, Analyzer};
+use veryla_metadata::{Corner, FilelistType, Metadata, SimType};
 
 pub struct CmdTest {
     opt: OptTest,
@@ -17,11 +18,66 @@ impl CmdTest {
     }
 
     pub fn exec(&self, metadata: &mut Metadata) -> Result<bool> {
+        let corners = self.corners(metadata)?;
+
         // force filelist_type to absolute which can be refered from temporary directory
         metadata.build.filelist_type = FilelistType::Absolute;
 
+        if let Some(runs) = self.opt.monte_carlo {
+            // sampled builds must not overwrite the nominal outputs of the project
+            let temp_dir = TempDir::new().into_diagnostic()?;
+            metadata.build.output_dir = Some(temp_dir.path().to_path_buf());
+
+            let mut failing_seeds = Vec::new();
+            for i in 0..runs {
+                let seed = self.opt.seed.wrapping_add(i);
+                info!("Monte Carlo run {}/{} (seed {})", i + 1, runs, seed);
+
+                // re-elaborate with values sampled by the seed
+                metadata.build.sample_seed = Some(seed);
+                Analyzer::new(metadata).clear();
+
+                let (_, failure) = self.run(metadata, &corners)?;
+                if failure != 0 {
+                    failing_seeds.push(seed);
+                }
+            }
+
+            metadata.build.sample_seed = None;
+            metadata.build.output_dir = None;
+
+            let passed = runs - failing_seeds.len() as u64;
+            let ratio = passed as f64 / runs as f64 * 100.0;
+            if failing_seeds.is_empty() {
+                info!("Monte Carlo yield : {:.1}% ({}/{})", ratio, passed, runs);
+                Ok(true)
+            } else {
+                let seeds: Vec<_> = failing_seeds.iter().map(|x| x.to_string()).collect();
+                error!("Monte Carlo yield : {:.1}% ({}/{})", ratio, passed, runs);
+                error!("Failing seeds : {}", seeds.join(", "));
+                Ok(false)
+            }
+        } else {
+            let (success, failure) = self.run(metadata, &corners)?;
+            if failure == 0 {
+                info!("Completed tests : {} passed, {} failed", success, failure);
+                Ok(true)
+            } else {
+                error!("Completed tests : {} passed, {} failed", success, failure);
+                Ok(false)
+            }
+        }
+    }
+
+    fn run(
+        &self,
+        metadata: &mut Metadata,
+        corners: &[Option<(String, Corner)>],
+    ) -> Result<(usize, usize)> {
         let build = CmdBuild::new(OptBuild {
             files: self.opt.files.clone(),
+            deny_warnings: false,
+            message_format: MessageFormat::Human,
         });
         build.exec(metadata, true)?;
 
@@ -48,6 +104,7 @@ impl CmdTest {
 
         let mut success = 0;
         let mut failure = 0;
+        let mut results = Vec::new();
         for (test, property) in &tests {
             let mut runner = match property.r#type {
                 TestType::Inline => match sim_type {
@@ -59,19 +116,154 @@ impl CmdTest {
                 TestType::CocotbInclude(x) => Cocotb::new(CocotbSource::Include(x)).runner(),
             };
 
-            if runner.run(metadata, *test, property.top, property.path, self.opt.wave)? {
-                success += 1;
-            } else {
-                failure += 1;
+            let mut test_results = Vec::new();
+            for corner in corners {
+                let point = TestPoint::new(corner.clone());
+
+                if let Some((name, _)) = corner {
+                    info!("Selected corner ({})", name);
+                }
+
+                if property.sweep.is_empty() {
+                    runner.metrics().clear();
+                    let result = runner.run(
+                        metadata,
+                        *test,
+                        property.top,
+                        property.path,
+                        &point,
+                        self.opt.wave,
+                    )?;
+                    if result {
+                        success += 1;
+                    } else {
+                        failure += 1;
+                    }
+                    if corner.is_some() {
+                        test_results.push(SweepResult {
+                            corner: point.corner.map(|x| x.0),
+                            overrides: Vec::new(),
+                            success: result,
+                            metrics: runner.metrics().clone(),
+                        });
+                    }
+                } else {
+                    let mut sweep_results = runner.sweep(
+                        metadata,
+                        *test,
+                        property.top,
+                        property.path,
+                        &point,
+                        &property.sweep,
+                        self.opt.wave,
+                    )?;
+                    for result in &sweep_results {
+                        if result.success {
+                            success += 1;
+                        } else {
+                            failure += 1;
+                        }
+                    }
+                    test_results.append(&mut sweep_results);
+                }
+            }
+
+            if !test_results.is_empty() {
+                results.push((*test, test_results));
+            }
+        }
+
+        for (test, results) in &results {
+            info!("Results ({})", test);
+            for line in format_results(results) {
+                info!("{}", line);
             }
         }
 
-        if failure == 0 {
-            info!("Completed tests : {} passed, {} failed", success, failure);
-            Ok(true)
+        Ok((success, failure))
+    }
+
+    fn corners(&self, metadata: &Metadata) -> Result<Vec<Option<(String, Corner)>>> {
+        if self.opt.all_corners {
+            if metadata.corners.is_empty() {
+                bail!("no corner is defined in [corners]");
+            }
+            Ok(metadata
+                .corners
+                .iter()
+                .map(|(name, corner)| Some((name.clone(), corner.clone())))
+                .collect())
+        } else if let Some(name) = &self.opt.corner {
+            if let Some(corner) = metadata.corners.get(name) {
+                Ok(vec![Some((name.clone(), corner.clone()))])
+            } else {
+                bail!("corner \"{}\" is not defined in [corners]", name);
+            }
         } else {
-            error!("Completed tests : {} passed, {} failed", success, failure);
-            Ok(false)
+            Ok(vec![None])
+        }
+    }
+}
+
+fn format_results(results: &[SweepResult]) -> Vec<String> {
+    let mut header: Vec<String> = Vec::new();
+    let with_corner = results.iter().any(|x| x.corner.is_some());
+    if with_corner {
+        header.push("corner".to_string());
+    }
+    if let Some(result) = results.first() {
+        for (name, _) in &result.overrides {
+            header.push(name.to_string());
         }
     }
+    header.push("result".to_string());
+
+    let mut metric_names: Vec<String> = Vec::new();
+    for result in results {
+        for (name, _) in &result.metrics {
+            if !metric_names.contains(name) {
+                metric_names.push(name.clone());
+            }
+        }
+    }
+    header.append(&mut metric_names.clone());
+
+    let mut rows = vec![header];
+    for result in results {
+        let mut row: Vec<String> = Vec::new();
+        if with_corner {
+            row.push(result.corner.clone().unwrap_or("-".to_string()));
+        }
+        row.append(&mut result.overrides.iter().map(|x| x.1.clone()).collect());
+        row.push(if result.success { "pass" } else { "fail" }.to_string());
+        for name in &metric_names {
+            let value = result
+                .metrics
+                .iter()
+                .rev()
+                .find(|x| &x.0 == name)
+                .map(|x| x.1.clone())
+                .unwrap_or("-".to_string());
+            row.push(value);
+        }
+        rows.push(row);
+    }
+
+    let mut widths = vec![0; rows[0].len()];
+    for row in &rows {
+        for (i, x) in row.iter().enumerate() {
+            widths[i] = widths[i].max(x.len());
+        }
+    }
+
+    rows.iter()
+        .map(|row| {
+            let cells: Vec<_> = row
+                .iter()
+                .enumerate()
+                .map(|(i, x)| format!("{:width$}", x, width = widths[i]))
+                .collect();
+            format!("  {}", cells.join(" | ").trim_end())
+        })
+        .collect()
 }
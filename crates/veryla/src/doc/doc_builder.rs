@@ -1,16 +1,21 @@
+use crate::doc::utils::escape_html;
 use crate::doc::{Mermaid, Wavedrom};
 use handlebars::Handlebars;
 use mdbook::{Config, MDBook};
 use miette::{IntoDiagnostic, Result};
 use serde::Serialize;
-use std::collections::HashSet;
+use std::collections::{HashMap, HashSet};
 use std::fs::{self, File};
 use std::io::Write;
 use std::path::PathBuf;
 use tempfile::TempDir;
-use veryla_analyzer::symbol::{PowerDomain, ParameterKind, Symbol, SymbolKind};
+use veryla_analyzer::symbol::{
+    Direction, ParameterKind, PowerDomain, Symbol, SymbolKind, TypeKind,
+};
 use veryla_analyzer::symbol_table;
 use veryla_metadata::Metadata;
+use veryla_parser::resource_table::StrId;
+use veryla_parser::veryla_grammar_trait::EntityType;
 use veryla_parser::veryla_token::Token;
 
 const SUMMARY_TMPL: &str = r###"
@@ -24,6 +29,9 @@ const SUMMARY_TMPL: &str = r###"
 - [Entitys](entitys.md)
   {{#each entitys}}
   - [{{this.0}}]({{this.1}}.md)
+    {{#if this.2}}
+    - [Interface Control Document]({{this.1}}_icd.md)
+    {{/if}}
   {{/each}}
 
 - [Entity Prototypes](proto_entitys.md)
@@ -46,7 +54,7 @@ const SUMMARY_TMPL: &str = r###"
 struct SummaryData {
     name: String,
     version: String,
-    entitys: Vec<(String, String)>,
+    entitys: Vec<(String, String, bool)>,
     proto_entitys: Vec<(String, String)>,
     interfaces: Vec<(String, String)>,
     packages: Vec<(String, String)>,
@@ -198,6 +206,15 @@ const MODULE_TMPL: &str = r#"
 </tbody>
 </table>
 {{/if}}
+
+{{#if diagram}}
+### Block Diagram
+---
+
+```mermaid
+{{diagram}}
+```
+{{/if}}
 "#;
 
 #[derive(Serialize)]
@@ -208,6 +225,7 @@ struct EntityData {
     parameters: Vec<ParameterData>,
     power_domains: Vec<String>,
     ports: Vec<PortData>,
+    diagram: String,
 }
 
 #[derive(Serialize)]
@@ -232,6 +250,74 @@ struct PortData {
     description: Option<String>,
 }
 
+const ICD_TMPL: &str = r#"
+## {{name}} Interface Control Document
+
+{{description}}
+
+[Download as CSV]({{csv_file}})
+
+{{#if ports}}
+<table class="table_list">
+<thead>
+<tr>
+    <th class="table_list_item">Port</th>
+    <th class="table_list_item">Direction</th>
+    <th class="table_list_item">Type</th>
+    <th class="table_list_item">Power Domain</th>
+    <th class="table_list_item">Discipline</th>
+    <th class="table_list_item">Connected To</th>
+</tr>
+</thead>
+<tbody>
+{{#each ports}}
+<tr>
+    <th class="table_list_item">{{this.name}}</th>
+    <td class="table_list_item"><span class="hljs-keyword">{{this.direction}}</span></td>
+    <td class="table_list_item"><span class="hljs-type">{{this.typ}}</span></td>
+    <td class="table_list_item"><span class="hljs-attribute">{{this.power_domain}}</span></td>
+    <td class="table_list_item">{{this.discipline}}</td>
+    <td class="table_list_item">{{#each this.peers}}{{this}}<br>{{/each}}</td>
+</tr>
+{{/each}}
+</tbody>
+</table>
+{{/if}}
+"#;
+
+#[derive(Serialize)]
+struct IcdData {
+    name: String,
+    description: String,
+    csv_file: String,
+    ports: Vec<IcdPortData>,
+}
+
+#[derive(Clone, Serialize)]
+struct IcdPortData {
+    name: String,
+    direction: String,
+    typ: String,
+    power_domain: String,
+    discipline: String,
+    peers: Vec<String>,
+}
+
+impl IcdPortData {
+    fn to_csv_row(&self) -> String {
+        let fields = [
+            self.name.clone(),
+            self.direction.clone(),
+            self.typ.clone(),
+            self.power_domain.clone(),
+            self.discipline.clone(),
+            self.peers.join(" "),
+        ];
+        let fields: Vec<_> = fields.iter().map(|x| escape_csv(x)).collect();
+        fields.join(",")
+    }
+}
+
 const PROTO_MODULE_TMPL: &str = r#"
 ## {{name}}
 
@@ -404,6 +490,17 @@ impl DocBuilder {
         for x in &self.entitys {
             let file = format!("{}.md", x.file_name);
             self.build_component(&file, self.build_entity(&x.html_name, &x.symbol))?;
+
+            if has_icd(&x.symbol) {
+                let ports = self.collect_icd_ports(&x.symbol);
+                let file = format!("{}_icd.md", x.file_name);
+                let csv_file = format!("{}_icd.csv", x.file_name);
+                self.build_component(
+                    &file,
+                    self.build_icd(&x.html_name, &csv_file, &x.symbol, &ports),
+                )?;
+                self.build_component(&csv_file, self.build_icd_csv(&ports))?;
+            }
         }
 
         for x in &self.proto_entitys {
@@ -505,7 +602,10 @@ impl DocBuilder {
             .entitys
             .iter()
             .cloned()
-            .map(|x| (x.html_name, x.file_name))
+            .map(|x| {
+                let icd = has_icd(&x.symbol);
+                (x.html_name, x.file_name, icd)
+            })
             .collect();
         let proto_entitys: Vec<_> = self
             .proto_entitys
@@ -699,6 +799,8 @@ impl DocBuilder {
                 })
                 .collect();
 
+            let diagram = build_block_diagram(symbol, &power_domains);
+
             let data = EntityData {
                 name: name.to_string(),
                 description: symbol.doc_comment.format(false),
@@ -706,6 +808,7 @@ impl DocBuilder {
                 parameters,
                 power_domains,
                 ports,
+                diagram,
             };
 
             let mut handlebars = Handlebars::new();
@@ -716,6 +819,91 @@ impl DocBuilder {
         }
     }
 
+    fn collect_icd_ports(&self, symbol: &Symbol) -> Vec<IcdPortData> {
+        let SymbolKind::Entity(property) = &symbol.kind else {
+            return Vec::new();
+        };
+
+        let instances = get_child_instances(symbol);
+
+        property
+            .ports
+            .iter()
+            .map(|x| {
+                let port = x.property();
+                let power_domain = if let PowerDomain::Explicit(_) = port.power_domain {
+                    port.power_domain.to_string()
+                } else {
+                    String::new()
+                };
+                let (typ, discipline) = if let Some(r#type) = &port.r#type {
+                    (format!("{}", r#type), discipline(&r#type.kind).to_string())
+                } else {
+                    (String::new(), String::new())
+                };
+
+                let mut peers = Vec::new();
+                for instance in &instances {
+                    if let SymbolKind::Instance(ref inst) = instance.kind {
+                        let mut connects: Vec<_> = inst.connects.iter().collect();
+                        connects.sort_by_key(|(token, _)| token.id);
+                        for (token, targets) in connects {
+                            if targets
+                                .iter()
+                                .any(|target| target.path().first() == Some(&x.name()))
+                            {
+                                peers.push(format!("{}.{}", instance.token, token));
+                            }
+                        }
+                    }
+                }
+
+                IcdPortData {
+                    name: x.name().to_string(),
+                    direction: format!("{}", port.direction),
+                    typ,
+                    power_domain,
+                    discipline,
+                    peers,
+                }
+            })
+            .collect()
+    }
+
+    fn build_icd(
+        &self,
+        name: &str,
+        csv_file: &str,
+        symbol: &Symbol,
+        ports: &[IcdPortData],
+    ) -> String {
+        let data = IcdData {
+            name: name.to_string(),
+            description: symbol.doc_comment.format(true),
+            csv_file: csv_file.to_string(),
+            ports: ports
+                .iter()
+                .map(|x| IcdPortData {
+                    typ: escape_html(&x.typ),
+                    ..x.clone()
+                })
+                .collect(),
+        };
+
+        let mut handlebars = Handlebars::new();
+        handlebars.register_escape_fn(handlebars::no_escape);
+        handlebars.render_template(ICD_TMPL, &data).unwrap()
+    }
+
+    fn build_icd_csv(&self, ports: &[IcdPortData]) -> String {
+        let mut ret = String::from("port,direction,type,power_domain,discipline,connected_to\n");
+        for port in ports {
+            ret.push_str(&port.to_csv_row());
+            ret.push('\n');
+        }
+        ret
+    }
+
     fn build_proto_entity(&self, name: &str, symbol: &Symbol) -> String {
         if let SymbolKind::ProtoEntity(property) = &symbol.kind {
             let parameters: Vec<_> = property
@@ -823,6 +1011,255 @@ impl DocBuilder {
     }
 }
 
+const DIAGRAM_DOMAIN_COLORS: [&str; 6] = [
+    "#cce5ff", "#d4edda", "#fff3cd", "#f8d7da", "#e2d9f3", "#d1ecf1",
+];
+
+const DIAGRAM_ENTITY_CLASSES: &str = r#"
+    classDef system fill:#f5f5f5,stroke:#333,stroke-width:3px
+    classDef subsystem fill:#e8eaf6,stroke:#3949ab,stroke-width:2px
+    classDef component fill:#e0f2f1,stroke:#00897b
+    classDef subcomponent fill:#f1f8e9,stroke:#7cb342
+    classDef element fill:#fff8e1,stroke:#ffa000
+    classDef requirement fill:#fce4ec,stroke:#d81b60,stroke-dasharray:4
+"#;
+
+enum DiagramPin {
+    Boundary(StrId),
+    Child(StrId),
+}
+
+impl DiagramPin {
+    fn node(&self) -> String {
+        match self {
+            DiagramPin::Boundary(x) => format!("port_{}", x),
+            DiagramPin::Child(x) => format!("inst_{}", x),
+        }
+    }
+}
+
+/// Pins attached to a net, split into drivers, loads and bidirectional pins
+struct DiagramNet {
+    name: StrId,
+    drivers: Vec<DiagramPin>,
+    loads: Vec<DiagramPin>,
+    bidirs: Vec<DiagramPin>,
+}
+
+impl DiagramNet {
+    fn get(nets: &mut Vec<DiagramNet>, name: StrId) -> &mut DiagramNet {
+        let i = if let Some(i) = nets.iter().position(|x| x.name == name) {
+            i
+        } else {
+            nets.push(DiagramNet {
+                name,
+                drivers: Vec::new(),
+                loads: Vec::new(),
+                bidirs: Vec::new(),
+            });
+            nets.len() - 1
+        };
+        &mut nets[i]
+    }
+
+    /// Boundary inputs drive the net from outside while child inputs load it,
+    /// so the role of a pin is flipped for child instances.
+    fn push(&mut self, pin: DiagramPin, direction: &Direction, child: bool) {
+        match (direction, child) {
+            (Direction::Input, false) | (Direction::Output, true) => self.drivers.push(pin),
+            (Direction::Output, false) | (Direction::Input, true) => self.loads.push(pin),
+            _ => self.bidirs.push(pin),
+        }
+    }
+
+    fn edges(&self) -> String {
+        let mut ret = String::new();
+        let sinks: Vec<_> = self.loads.iter().chain(self.bidirs.iter()).collect();
+        if self.drivers.is_empty() {
+            for pair in sinks.windows(2) {
+                ret.push_str(&format!(
+                    "    {} -- \"{}\" --- {}\n",
+                    pair[0].node(),
+                    self.name,
+                    pair[1].node()
+                ));
+            }
+        } else {
+            for driver in &self.drivers {
+                for sink in &sinks {
+                    ret.push_str(&format!(
+                        "    {} -- \"{}\" --> {}\n",
+                        driver.node(),
+                        self.name,
+                        sink.node()
+                    ));
+                }
+            }
+        }
+        ret
+    }
+}
+
+/// Renders a mermaid flowchart of the entity: boundary ports are drawn as
+/// stadium nodes coloured by power domain, child instances as boxes
+/// coloured by entity type, and each net as edges from its drivers to its loads.
+fn build_block_diagram(symbol: &Symbol, power_domains: &[String]) -> String {
+    let SymbolKind::Entity(property) = &symbol.kind else {
+        return String::new();
+    };
+
+    let instances = get_child_instances(symbol);
+    let mut ret = String::from("flowchart LR\n");
+    ret.push_str(DIAGRAM_ENTITY_CLASSES.trim_start_matches('\n'));
+    for (i, _) in power_domains.iter().enumerate() {
+        let color = DIAGRAM_DOMAIN_COLORS[i % DIAGRAM_DOMAIN_COLORS.len()];
+        ret.push_str(&format!("    classDef domain_{} fill:{}\n", i, color));
+    }
+
+    let mut nets: Vec<DiagramNet> = Vec::new();
+
+    for port in &property.ports {
+        let port_property = port.property();
+        let name = port.name();
+        let (open, close) = match port_property.direction {
+            Direction::Input => ("([", "])"),
+            Direction::Output => ("[[", "]]"),
+            _ => ("{{", "}}"),
+        };
+        ret.push_str(&format!("    port_{}{}\"{}\"{}\n", name, open, name, close));
+        if let PowerDomain::Explicit(_) = port_property.power_domain {
+            let domain = port_property.power_domain.to_string();
+            if let Some(i) = power_domains.iter().position(|x| *x == domain) {
+                ret.push_str(&format!("    class port_{} domain_{}\n", name, i));
+            }
+        }
+
+        let pin = DiagramPin::Boundary(name);
+        DiagramNet::get(&mut nets, name).push(pin, &port_property.direction, false);
+    }
+
+    if instances.is_empty() {
+        ret.push_str(&format!(
+            "    entity_{}[\"{}\"]:::{}\n",
+            symbol.token,
+            symbol.token,
+            entity_type_name(&property.entity_type)
+        ));
+        for port in &property.ports {
+            let edge = match port.property().direction {
+                Direction::Input => {
+                    format!("    port_{} --> entity_{}\n", port.name(), symbol.token)
+                }
+                Direction::Output => {
+                    format!("    entity_{} --> port_{}\n", symbol.token, port.name())
+                }
+                _ => format!("    entity_{} <--> port_{}\n", symbol.token, port.name()),
+            };
+            ret.push_str(&edge);
+        }
+        return ret;
+    }
+
+    for instance in &instances {
+        let SymbolKind::Instance(ref inst) = instance.kind else {
+            continue;
+        };
+
+        let mut directions = HashMap::new();
+        let mut class = None;
+        if let Ok(x) = symbol_table::resolve((&inst.type_name.mangled_path(), &instance.namespace))
+        {
+            if let SymbolKind::Entity(ref x) = x.found.kind {
+                for port in &x.ports {
+                    directions.insert(port.name(), port.property().direction);
+                }
+                class = Some(entity_type_name(&x.entity_type));
+            }
+        }
+
+        ret.push_str(&format!(
+            "    inst_{}[\"{}: {}\"]",
+            instance.token,
+            instance.token,
+            inst.type_name.to_string().trim()
+        ));
+        if let Some(class) = class {
+            ret.push_str(&format!(":::{}", class));
+        }
+        ret.push('\n');
+
+        let mut connects: Vec<_> = inst.connects.iter().collect();
+        connects.sort_by_key(|(token, _)| token.id);
+        for (token, targets) in connects {
+            for target in targets {
+                let Some(net) = target.path().first().copied() else {
+                    continue;
+                };
+                let pin = DiagramPin::Child(instance.token.text);
+                let direction = directions.get(&token.text).unwrap_or(&Direction::Inout);
+                DiagramNet::get(&mut nets, net).push(pin, direction, true);
+            }
+        }
+    }
+
+    for net in &nets {
+        ret.push_str(&net.edges());
+    }
+
+    ret
+}
+
+fn get_child_instances(symbol: &Symbol) -> Vec<Symbol> {
+    let namespace = symbol.inner_namespace();
+    let mut ret: Vec<_> = symbol_table::get_all()
+        .into_iter()
+        .filter(|x| matches!(x.kind, SymbolKind::Instance(_)) && x.namespace.included(&namespace))
+        .collect();
+    ret.sort_by_key(|x| x.token.id);
+    ret
+}
+
+fn entity_type_name(entity_type: &EntityType) -> &'static str {
+    match entity_type {
+        EntityType::System(_) => "system",
+        EntityType::Subsystem(_) => "subsystem",
+        EntityType::Component(_) => "component",
+        EntityType::Subcomponent(_) => "subcomponent",
+        EntityType::Element(_) => "element",
+        EntityType::Requirement(_) => "requirement",
+    }
+}
+
+fn has_icd(symbol: &Symbol) -> bool {
+    if let SymbolKind::Entity(x) = &symbol.kind {
+        matches!(
+            x.entity_type,
+            EntityType::System(_) | EntityType::Subsystem(_)
+        )
+    } else {
+        false
+    }
+}
+
+fn discipline(kind: &TypeKind) -> &'static str {
+    match kind {
+        TypeKind::Analog => "analog",
+        TypeKind::F32 | TypeKind::F64 => "real",
+        TypeKind::Bit | TypeKind::Logic => "digital",
+        x if x.is_power() => "power",
+        x if x.is_enable() => "enable",
+        _ => "",
+    }
+}
+
+fn escape_csv(s: &str) -> String {
+    if s.contains([',', '"', '\n']) {
+        format!("\"{}\"", s.replace('"', "\"\""))
+    } else {
+        s.to_string()
+    }
+}
+
 fn get_comment_from_token(token: &Token) -> Option<String> {
     if let Ok(symbol) = symbol_table::resolve(token) {
         Some(symbol.found.doc_comment.format(false))
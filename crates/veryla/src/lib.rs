@@ -1,6 +1,7 @@
 use clap::{Args, Parser, Subcommand, ValueEnum};
 use std::path::PathBuf;
 
+pub mod analysis_cache;
 pub mod cmd_build;
 pub mod cmd_check;
 pub mod cmd_clean;
@@ -14,7 +15,10 @@ pub mod cmd_publish;
 pub mod cmd_test;
 pub mod cmd_update;
 pub mod doc;
+pub mod message_format;
 pub mod runner;
+#[cfg(test)]
+mod tests;
 
 // ---------------------------------------------------------------------------------------------------------------------
 // Opt
@@ -95,6 +99,17 @@ pub struct OptFmt {
 pub struct OptCheck {
     /// Target files
     pub files: Vec<PathBuf>,
+
+    /// Treat warnings as errors
+    #[arg(long)]
+    pub deny_warnings: bool,
+
+    /// Apply suggested fixes of diagnostics
+    #[arg(long)]
+    pub fix: bool,
+    /// Output format of diagnostics
+    #[arg(long, value_enum, default_value_t)]
+    pub message_format: MessageFormat,
 }
 
 /// Build the target codes corresponding to the current project
@@ -102,6 +117,13 @@ pub struct OptCheck {
 pub struct OptBuild {
     /// Target files
     pub files: Vec<PathBuf>,
+
+    /// Treat warnings as errors
+    #[arg(long)]
+    pub deny_warnings: bool,
+    /// Output format of diagnostics
+    #[arg(long, value_enum, default_value_t)]
+    pub message_format: MessageFormat,
 }
 
 /// Clean-up the current project
@@ -140,6 +162,33 @@ pub struct OptTest {
     /// Dump waveform
     #[arg(long)]
     pub wave: bool,
+
+    /// Run at the specified PVT corner
+    #[arg(long, conflicts_with = "all_corners")]
+    pub corner: Option<String>,
+
+    /// Run at all PVT corners
+    #[arg(long)]
+    pub all_corners: bool,
+
+    /// Number of Monte Carlo runs with sampled distribution values
+    #[arg(long, value_parser = clap::value_parser!(u64).range(1..))]
+    pub monte_carlo: Option<u64>,
+
+    /// Seed of the first Monte Carlo run
+    #[arg(long, default_value_t = 1, requires = "monte_carlo")]
+    pub seed: u64,
+}
+
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq, ValueEnum)]
+pub enum MessageFormat {
+    /// Rendered text
+    #[default]
+    Human,
+    /// JSON object per diagnostic
+    Json,
+    /// SARIF 2.1.0 log
+    Sarif,
 }
 
 #[derive(Clone, Copy, Debug, ValueEnum)]
@@ -231,4 +280,12 @@ pub struct OptDump {
     /// output unsafe table
     #[arg(long)]
     pub unsafe_table: bool,
+
+    /// output elaborated instance tree
+    #[arg(long)]
+    pub elaborated: bool,
+
+    /// top entity of elaboration
+    #[arg(long, requires = "elaborated")]
+    pub top: Option<String>,
 }
@@ -85,8 +85,16 @@ fn main() -> Result<ExitCode> {
         Commands::New(x) => cmd_new::CmdNew::new(x).exec()?,
         Commands::Init(x) => cmd_init::CmdInit::new(x).exec()?,
         Commands::Fmt(x) => cmd_fmt::CmdFmt::new(x).exec(&mut metadata)?,
-        Commands::Check(x) => cmd_check::CmdCheck::new(x).exec(&mut metadata)?,
-        Commands::Build(x) => cmd_build::CmdBuild::new(x).exec(&mut metadata, false)?,
+        Commands::Check(x) => {
+            let format = x.message_format;
+            let ret = cmd_check::CmdCheck::new(x).exec(&mut metadata);
+            message_format::emit(format, ret, &metadata.project_path())?
+        }
+        Commands::Build(x) => {
+            let format = x.message_format;
+            let ret = cmd_build::CmdBuild::new(x).exec(&mut metadata, false);
+            message_format::emit(format, ret, &metadata.project_path())?
+        }
         Commands::Clean(x) => cmd_clean::CmdClean::new(x).exec(&mut metadata)?,
         Commands::Update(x) => cmd_update::CmdUpdate::new(x).exec(&mut metadata)?,
         Commands::Publish(x) => cmd_publish::CmdPublish::new(x).exec(&mut metadata)?,
@@ -0,0 +1,200 @@
+//! Machine-readable output of diagnostics for `--message-format json|sarif`
+
+use crate::MessageFormat;
+use miette::{Diagnostic, IntoDiagnostic, Report, Result, Severity, SourceSpan};
+use serde::Serialize;
+use serde_json::json;
+use std::collections::BTreeMap;
+use std::path::Path;
+
+#[derive(Serialize)]
+pub struct Message {
+    pub code: Option<String>,
+    pub severity: &'static str,
+    pub message: String,
+    /// The first span is the primary location
+    pub spans: Vec<Span>,
+    pub help: Option<String>,
+    pub url: Option<String>,
+}
+
+#[derive(Serialize)]
+pub struct Span {
+    pub file: String,
+    pub line: usize,
+    pub column: usize,
+    pub end_line: usize,
+    pub end_column: usize,
+    pub label: Option<String>,
+    pub primary: bool,
+}
+
+fn position(diagnostic: &dyn Diagnostic, offset: usize) -> Option<(Option<String>, usize, usize)> {
+    let span = SourceSpan::new(offset.into(), 0);
+    let contents = diagnostic.source_code()?.read_span(&span, 0, 0).ok()?;
+    Some((
+        contents.name().map(|x| x.to_string()),
+        contents.line() + 1,
+        contents.column() + 1,
+    ))
+}
+
+impl Message {
+    pub fn new(diagnostic: &dyn Diagnostic, base: &Path) -> Self {
+        let severity = match diagnostic.severity() {
+            Some(Severity::Advice) => "advice",
+            Some(Severity::Warning) => "warning",
+            Some(Severity::Error) | None => "error",
+        };
+        let non_empty = |x: String| if x.is_empty() { None } else { Some(x) };
+
+        let mut spans = Vec::new();
+        for label in diagnostic.labels().into_iter().flatten() {
+            let beg = position(diagnostic, label.offset());
+            let end = position(diagnostic, label.offset() + label.len());
+            if let (Some((Some(file), line, column)), Some((_, end_line, end_column))) = (beg, end)
+            {
+                let file = Path::new(&file);
+                let file = file.strip_prefix(base).unwrap_or(file);
+                spans.push(Span {
+                    file: file.to_string_lossy().replace('\\', "/"),
+                    line,
+                    column,
+                    end_line,
+                    end_column,
+                    label: label.label().map(|x| x.to_string()),
+                    primary: spans.is_empty(),
+                });
+            }
+        }
+
+        Self {
+            code: diagnostic.code().map(|x| x.to_string()),
+            severity,
+            message: diagnostic.to_string(),
+            spans,
+            help: diagnostic.help().and_then(|x| non_empty(x.to_string())),
+            url: diagnostic.url().and_then(|x| non_empty(x.to_string())),
+        }
+    }
+
+    fn sarif_location(span: &Span) -> serde_json::Value {
+        json!({
+            "physicalLocation": {
+                "artifactLocation": { "uri": span.file },
+                "region": {
+                    "startLine": span.line,
+                    "startColumn": span.column,
+                    "endLine": span.end_line,
+                    "endColumn": span.end_column,
+                }
+            }
+        })
+    }
+
+    fn sarif_result(&self) -> serde_json::Value {
+        let level = match self.severity {
+            "error" => "error",
+            "warning" => "warning",
+            _ => "note",
+        };
+        let locations: Vec<_> = self
+            .spans
+            .iter()
+            .filter(|x| x.primary)
+            .map(Message::sarif_location)
+            .collect();
+        let related: Vec<_> = self
+            .spans
+            .iter()
+            .filter(|x| !x.primary)
+            .enumerate()
+            .map(|(i, x)| {
+                let mut location = Message::sarif_location(x);
+                location["id"] = json!(i);
+                if let Some(ref label) = x.label {
+                    location["message"] = json!({ "text": label });
+                }
+                location
+            })
+            .collect();
+
+        let mut ret = json!({
+            "level": level,
+            "message": { "text": self.message },
+            "locations": locations,
+            "relatedLocations": related,
+        });
+        if let Some(ref code) = self.code {
+            ret["ruleId"] = json!(code);
+        }
+        ret
+    }
+}
+
+/// Diagnostics carried by the result of `check`/`build`
+fn messages(report: &Report, base: &Path) -> Vec<Message> {
+    let diagnostic: &dyn Diagnostic = report.as_ref();
+    match diagnostic.related() {
+        Some(related) => related.map(|x| Message::new(x, base)).collect(),
+        None => vec![Message::new(diagnostic, base)],
+    }
+}
+
+fn sarif(messages: &[Message]) -> serde_json::Value {
+    let mut rules = BTreeMap::new();
+    for x in messages {
+        if let Some(ref code) = x.code {
+            let mut rule = json!({ "id": code });
+            if let Some(ref help) = x.help {
+                rule["help"] = json!({ "text": help });
+            }
+            if let Some(ref url) = x.url {
+                rule["helpUri"] = json!(url);
+            }
+            rules.insert(code.clone(), rule);
+        }
+    }
+    let rules: Vec<_> = rules.into_values().collect();
+    let results: Vec<_> = messages.iter().map(|x| x.sarif_result()).collect();
+
+    json!({
+        "$schema": "https://json.schemastore.org/sarif-2.1.0.json",
+        "version": "2.1.0",
+        "runs": [{
+            "tool": {
+                "driver": {
+                    "name": "veryla",
+                    "version": env!("CARGO_PKG_VERSION"),
+                    "informationUri": env!("CARGO_PKG_REPOSITORY"),
+                    "rules": rules,
+                }
+            },
+            "results": results,
+        }]
+    })
+}
+
+/// Print diagnostics of `result` in `format` to stdout.
+/// The result of human format is returned as is to be rendered by miette.
+pub fn emit(format: MessageFormat, result: Result<bool>, base: &Path) -> Result<bool> {
+    let (ret, messages) = match result {
+        Ok(x) => (x, Vec::new()),
+        Err(x) if format != MessageFormat::Human => (false, messages(&x, base)),
+        Err(x) => return Err(x),
+    };
+
+    match format {
+        MessageFormat::Human => (),
+        MessageFormat::Json => {
+            for x in &messages {
+                println!("{}", serde_json::to_string(x).into_diagnostic()?);
+            }
+        }
+        MessageFormat::Sarif => {
+            let sarif = serde_json::to_string_pretty(&sarif(&messages)).into_diagnostic()?;
+            println!("{sarif}");
+        }
+    }
+    Ok(ret)
+}
@@ -5,8 +5,11 @@ use once_cell::sync::Lazy;
 use regex::Regex;
 use std::fs;
 use std::path::{Path, PathBuf};
-use veryla_metadata::{Metadata, WaveFormTarget};
-use veryla_parser::resource_table::{PathId, StrId};
+use veryla_analyzer::attribute::SweepItem;
+use veryla_metadata::{
+    corner_constants, corner_package, Corner, Metadata, WaveFormTarget, CORNER_PACKAGE,
+};
+use veryla_parser::resource_table::{self, PathId, StrId};
 use veryla_sourcemap::SourceMap;
 
 mod cocotb;
@@ -18,6 +21,71 @@ pub use vcs::*;
 pub use verilator::*;
 pub use vivado::*;
 
+pub struct SweepResult {
+    pub corner: Option<String>,
+    pub overrides: Vec<(StrId, String)>,
+    pub success: bool,
+    pub metrics: Vec<(String, String)>,
+}
+
+#[derive(Clone, Debug, Default)]
+pub struct TestPoint {
+    pub corner: Option<(String, Corner)>,
+    pub overrides: Vec<(StrId, String)>,
+}
+
+impl TestPoint {
+    pub fn new(corner: Option<(String, Corner)>) -> Self {
+        Self {
+            corner,
+            overrides: Vec::new(),
+        }
+    }
+
+    /// Parameter overrides of the corner followed by sweep overrides
+    pub fn parameters(&self) -> Vec<(StrId, String)> {
+        let mut ret = Vec::new();
+        if let Some((_, corner)) = &self.corner {
+            for (name, value) in &corner.parameters {
+                ret.push((resource_table::insert_str(name), value.sv_literal()));
+            }
+        }
+        ret.append(&mut self.overrides.clone());
+        ret
+    }
+
+    pub fn defines(&self) -> Vec<(String, String)> {
+        if let Some((_, corner)) = &self.corner {
+            corner
+                .defines
+                .iter()
+                .map(|(name, value)| (name.clone(), value.sv_literal()))
+                .collect()
+        } else {
+            Vec::new()
+        }
+    }
+
+    /// Write the `$corner` built-in package into `dir`
+    ///
+    /// The nominal package is written if no corner is selected.
+    pub fn write_corner_package(&self, metadata: &Metadata, dir: &Path) -> Result<Option<PathBuf>> {
+        if metadata.corners.is_empty() {
+            return Ok(None);
+        }
+
+        let selected = self
+            .corner
+            .as_ref()
+            .map(|(name, corner)| (name.as_str(), corner));
+        let constants = corner_constants(&metadata.corners, selected);
+
+        let path = dir.join(format!("{}.sv", CORNER_PACKAGE));
+        fs::write(&path, corner_package(&constants)).into_diagnostic()?;
+        Ok(Some(path))
+    }
+}
+
 pub trait Runner {
     fn run(
         &mut self,
@@ -25,6 +93,7 @@ pub trait Runner {
         test: StrId,
         top: Option<StrId>,
         path: PathId,
+        point: &TestPoint,
         wave: bool,
     ) -> Result<bool>;
 
@@ -32,13 +101,60 @@ pub trait Runner {
 
     fn failure(&mut self);
 
+    fn metrics(&mut self) -> &mut Vec<(String, String)>;
+
+    fn sweep(
+        &mut self,
+        metadata: &Metadata,
+        test: StrId,
+        top: Option<StrId>,
+        path: PathId,
+        base: &TestPoint,
+        sweep: &[SweepItem],
+        wave: bool,
+    ) -> Result<Vec<SweepResult>> {
+        let mut ret = Vec::new();
+        for overrides in expand_sweep(sweep) {
+            let mut point = base.clone();
+            point.overrides = overrides;
+            self.metrics().clear();
+            let success = self.run(metadata, test, top, path, &point, wave)?;
+            let metrics = self.metrics().clone();
+            ret.push(SweepResult {
+                corner: point.corner.map(|x| x.0),
+                overrides: point.overrides,
+                success,
+                metrics,
+            });
+        }
+        Ok(ret)
+    }
+
+    /// Record a metric which is printed by the simulator in the form of
+    /// `VERYLA_METRIC <name> = <value>` in any output line
+    /// (e.g. `$display("VERYLA_METRIC gain = %f", gain);` or `$info(...)`).
+    ///
+    /// Recorded metrics are shown as columns of the sweep and corner result table.
+    fn metric(&mut self, line: &str) {
+        static RE: Lazy<Regex> = Lazy::new(|| {
+            Regex::new(r"\bVERYLA_METRIC\s+(?<name>[a-zA-Z_][0-9a-zA-Z_]*)\s*=\s*(?<value>\S+)")
+                .unwrap()
+        });
+
+        if let Some(caps) = RE.captures(line) {
+            let name = caps["name"].to_string();
+            let value = caps["value"].to_string();
+            self.metrics().push((name, value));
+        }
+    }
+
     fn debug(&self, line: &str) {
         if log_enabled!(Level::Debug) {
             debug!("{} : {}", self.name(), line);
         }
     }
 
-    fn info(&self, line: &str) {
+    fn info(&mut self, line: &str) {
         static STYLE: Lazy<Style> =
             Lazy::new(|| Style::new().fg_color(Some(AnsiColor::Green.into())));
         if !log_enabled!(Level::Debug) {
@@ -73,6 +189,22 @@ pub trait Runner {
     }
 }
 
+pub fn expand_sweep(sweep: &[SweepItem]) -> Vec<Vec<(StrId, String)>> {
+    let mut ret = vec![vec![]];
+    for item in sweep {
+        let mut points = Vec::new();
+        for point in &ret {
+            for value in &item.values {
+                let mut point = point.clone();
+                point.push((item.name, value.clone()));
+                points.push(point);
+            }
+        }
+        ret = points;
+    }
+    ret
+}
+
 pub fn remap_msg_by_regex(line: &str, re: &Regex) -> String {
     let mut ret = line.to_string();
 
@@ -1,4 +1,4 @@
-use crate::runner::Runner;
+use crate::runner::{Runner, TestPoint};
 use futures::prelude::*;
 use log::{error, info};
 use miette::{IntoDiagnostic, Result, WrapErr};
@@ -29,6 +29,7 @@ pub struct Cocotb {
     source: CocotbSource,
     state: State,
     success: bool,
+    metrics: Vec<(String, String)>,
 }
 
 impl Cocotb {
@@ -37,6 +38,7 @@ impl Cocotb {
             source,
             state: State::Idle,
             success: true,
+            metrics: Vec::new(),
         }
     }
 
@@ -44,8 +46,9 @@ impl Cocotb {
         Box::new(self) as Box<dyn Runner>
     }
 
-    fn parse_line(&mut self, line: &str) {
+    pub(crate) fn parse_line(&mut self, line: &str) {
         self.debug(line);
+        self.metric(line);
 
         if !line.starts_with("                ") {
             self.state = State::Idle;
@@ -103,6 +106,7 @@ impl Runner for Cocotb {
         test: StrId,
         top: Option<StrId>,
         path: PathId,
+        point: &TestPoint,
         _wave: bool,
     ) -> Result<bool> {
         self.success = true;
@@ -140,6 +144,9 @@ impl Runner for Cocotb {
 
         let file_list = fs::read_to_string(metadata.filelist_path()).into_diagnostic()?;
         let mut sources = String::new();
+        if let Some(x) = point.write_corner_package(metadata, temp_dir.path())? {
+            sources.push_str(&format!("\"{}\",", x.to_string_lossy()));
+        }
         for line in file_list.lines() {
             sources.push_str(&format!("\"{line}\","));
         }
@@ -147,6 +154,18 @@ impl Runner for Cocotb {
 
         let module = format!("{}_{}", metadata.project.name, top.unwrap());
 
+        let mut parameters = String::new();
+        for (name, value) in point.parameters() {
+            parameters.push_str(&format!("\"{name}\": {value},"));
+        }
+        let parameters = format!("{{{}}}", parameters);
+
+        let mut defines = String::new();
+        for (name, value) in point.defines() {
+            defines.push_str(&format!("\"{name}\": {value:?},"));
+        }
+        let defines = format!("{{{}}}", defines);
+
         let runner_path = temp_dir.path().join("runner.py");
         let runner_text = format!(
             r#"
@@ -159,6 +178,8 @@ runner = cocotb.runner.get_runner("verilator")
 runner.build(
     verilog_sources=sources,
     hdl_toplevel="{module}",
+    parameters={parameters},
+    defines={defines},
     always=True,
 )
 
@@ -209,4 +230,8 @@ runner.test(
     fn failure(&mut self) {
         self.success = false;
     }
+
+    fn metrics(&mut self) -> &mut Vec<(String, String)> {
+        &mut self.metrics
+    }
 }
@@ -1,4 +1,4 @@
-use crate::runner::{copy_wave, remap_msg_by_regex, Runner};
+use crate::runner::{copy_wave, remap_msg_by_regex, Runner, TestPoint};
 use futures::prelude::*;
 use log::{error, info};
 use miette::{IntoDiagnostic, Result, WrapErr};
@@ -25,6 +25,7 @@ enum State {
 pub struct Vcs {
     state: State,
     success: bool,
+    metrics: Vec<(String, String)>,
 }
 
 fn remap_msg(line: &str) -> String {
@@ -39,6 +40,7 @@ impl Vcs {
         Self {
             state: State::Idle,
             success: true,
+            metrics: Vec::new(),
         }
     }
 
@@ -46,8 +48,9 @@ impl Vcs {
         Box::new(self) as Box<dyn Runner>
     }
 
-    fn parse_line(&mut self, line: &str) {
+    pub(crate) fn parse_line(&mut self, line: &str) {
         self.debug(line);
+        self.metric(line);
 
         match self.state {
             State::Idle => {
@@ -124,6 +127,7 @@ impl Runner for Vcs {
         test: StrId,
         _top: Option<StrId>,
         path: PathId,
+        point: &TestPoint,
         wave: bool,
     ) -> Result<bool> {
         self.success = true;
@@ -144,14 +148,28 @@ impl Runner for Vcs {
             ));
         }
 
+        for (name, value) in point.defines() {
+            defines.push(format!("+define+{}={}", name, value));
+        }
+
+        let generics: Vec<_> = point
+            .parameters()
+            .iter()
+            .map(|(name, value)| format!("-pvalue+{}.{}={}", test, name, value))
+            .collect();
+
+        let corner_package = point.write_corner_package(metadata, temp_dir.path())?;
+
         let rt = Runtime::new().unwrap();
 
         rt.block_on(async {
             let compile = Command::new("vcs")
                 .arg("-sverilog")
+                .args(&corner_package)
                 .arg("-f")
                 .arg(metadata.filelist_path())
                 .args(&defines)
+                .args(&generics)
                 .args(&metadata.test.vcs.compile_args)
                 .current_dir(temp_dir.path())
                 .stdout(Stdio::piped())
@@ -203,4 +221,8 @@ impl Runner for Vcs {
     fn failure(&mut self) {
         self.success = false;
     }
+
+    fn metrics(&mut self) -> &mut Vec<(String, String)> {
+        &mut self.metrics
+    }
 }
@@ -1,4 +1,4 @@
-use crate::runner::{copy_wave, remap_msg_by_regex, Runner};
+use crate::runner::{copy_wave, remap_msg_by_regex, Runner, TestPoint};
 use futures::prelude::*;
 use log::{error, info};
 use miette::{IntoDiagnostic, Result, WrapErr};
@@ -21,6 +21,7 @@ enum State {
 pub struct Verilator {
     state: State,
     success: bool,
+    metrics: Vec<(String, String)>,
 }
 
 fn parse_msg(line: &str) -> String {
@@ -46,6 +47,7 @@ impl Verilator {
         Self {
             state: State::Idle,
             success: true,
+            metrics: Vec::new(),
         }
     }
 
@@ -53,8 +55,9 @@ impl Verilator {
         Box::new(self) as Box<dyn Runner>
     }
 
-    fn parse_line(&mut self, line: &str) {
+    pub(crate) fn parse_line(&mut self, line: &str) {
         self.debug(line);
+        self.metric(line);
 
         match self.state {
             State::Idle => {
@@ -134,6 +137,7 @@ impl Runner for Verilator {
         test: StrId,
         _top: Option<StrId>,
         path: PathId,
+        point: &TestPoint,
         wave: bool,
     ) -> Result<bool> {
         self.success = true;
@@ -154,22 +158,36 @@ impl Runner for Verilator {
             ));
         }
 
+        for (name, value) in point.defines() {
+            defines.push(format!("+define+{}={}", name, value));
+        }
+
         let mut opt = vec!["--assert", "--binary", "-Wno-MULTITOP"];
 
         if wave {
             opt.push("--trace");
         }
 
+        let generics: Vec<_> = point
+            .parameters()
+            .iter()
+            .map(|(name, value)| format!("-G{}={}", name, value))
+            .collect();
+
+        let corner_package = point.write_corner_package(metadata, temp_dir.path())?;
+
         let rt = Runtime::new().unwrap();
 
         rt.block_on(async {
             let compile = Command::new("verilator")
                 .args(&opt)
+                .args(&corner_package)
                 .arg("-f")
                 .arg(metadata.filelist_path())
                 .arg("-o")
                 .arg("simv")
                 .args(&defines)
+                .args(&generics)
                 .args(&metadata.test.verilator.compile_args)
                 .current_dir(temp_dir.path())
                 .stdout(Stdio::piped())
@@ -221,4 +239,8 @@ impl Runner for Verilator {
     fn failure(&mut self) {
         self.success = false;
     }
+
+    fn metrics(&mut self) -> &mut Vec<(String, String)> {
+        &mut self.metrics
+    }
 }
@@ -1,4 +1,4 @@
-use crate::runner::{copy_wave, remap_msg_by_regex, Runner};
+use crate::runner::{copy_wave, remap_msg_by_regex, Runner, TestPoint};
 use futures::prelude::*;
 use log::{error, info};
 use miette::{IntoDiagnostic, Result, WrapErr};
@@ -23,6 +23,7 @@ enum State {
 pub struct Vivado {
     state: State,
     success: bool,
+    metrics: Vec<(String, String)>,
 }
 
 fn remap_msg(line: &str) -> String {
@@ -38,6 +39,7 @@ impl Vivado {
         Self {
             state: State::Idle,
             success: true,
+            metrics: Vec::new(),
         }
     }
 
@@ -45,8 +47,9 @@ impl Vivado {
         Box::new(self) as Box<dyn Runner>
     }
 
-    fn parse_line(&mut self, line: &str) {
+    pub(crate) fn parse_line(&mut self, line: &str) {
         self.debug(line);
+        self.metric(line);
 
         match self.state {
             State::Idle => {
@@ -107,6 +110,7 @@ impl Runner for Vivado {
         test: StrId,
         _top: Option<StrId>,
         path: PathId,
+        point: &TestPoint,
         wave: bool,
     ) -> Result<bool> {
         self.success = true;
@@ -128,11 +132,19 @@ impl Runner for Vivado {
             ));
         }
 
+        for (name, value) in point.defines() {
+            defines.push("-d".to_string());
+            defines.push(format!("{}={}", name, value));
+        }
+
+        let corner_package = point.write_corner_package(metadata, temp_dir.path())?;
+
         let rt = Runtime::new().unwrap();
 
         rt.block_on(async {
             let compile = Command::new("xvlog")
                 .arg("--sv")
+                .args(&corner_package)
                 .arg("-f")
                 .arg(metadata.filelist_path())
                 .args(&defines)
@@ -156,6 +168,12 @@ impl Runner for Vivado {
 
         let opt = if wave { vec!["-debug", "all"] } else { vec![] };
 
+        let mut generics = Vec::new();
+        for (name, value) in point.parameters() {
+            generics.push("--generic_top".to_string());
+            generics.push(format!("{}={}", name, value));
+        }
+
         let mut top = vec![test.to_string()];
         if wave {
             top.push("__veryla_wavedump".to_string());
@@ -165,6 +183,7 @@ impl Runner for Vivado {
             let elaborate = Command::new("xelab")
                 .args(top)
                 .args(opt)
+                .args(&generics)
                 .arg("-s")
                 .arg("simv")
                 .args(&metadata.test.vivado.elaborate_args)
@@ -220,4 +239,8 @@ impl Runner for Vivado {
     fn failure(&mut self) {
         self.success = false;
     }
+
+    fn metrics(&mut self) -> &mut Vec<(String, String)> {
+        &mut self.metrics
+    }
 }
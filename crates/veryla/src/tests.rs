@@ -0,0 +1,94 @@
+use crate::analysis_cache::CachedDiagnostic;
+use crate::runner::{Cocotb, CocotbSource, Runner, Vcs, Verilator, Vivado};
+use miette::{Diagnostic, Severity};
+use veryla_analyzer::Analyzer;
+use veryla_metadata::Metadata;
+use veryla_parser::Parser;
+
+fn metrics(runner: &mut dyn Runner) -> Vec<(String, String)> {
+    std::mem::take(runner.metrics())
+}
+
+fn expect() -> Vec<(String, String)> {
+    vec![
+        ("gain".to_string(), "1.500000".to_string()),
+        ("offset".to_string(), "-3".to_string()),
+    ]
+}
+
+#[test]
+fn metric_verilator() {
+    let mut runner = Verilator::new();
+    runner.parse_line("VERYLA_METRIC gain = 1.500000");
+    runner.parse_line("[0] -Info: test.sv:12: prj_test: VERYLA_METRIC offset = -3");
+    runner.parse_line("[0] -Info: test.sv:13: prj_test: done");
+    assert_eq!(metrics(&mut runner), expect());
+}
+
+#[test]
+fn metric_vcs() {
+    let mut runner = Vcs::new();
+    runner.parse_line("VERYLA_METRIC gain = 1.500000");
+    runner.parse_line("Info: \"test.sv\", 12: prj_test: at time 0 ps");
+    runner.parse_line("VERYLA_METRIC offset = -3");
+    assert_eq!(metrics(&mut runner), expect());
+}
+
+#[test]
+fn metric_vivado() {
+    let mut runner = Vivado::new();
+    runner.parse_line("VERYLA_METRIC gain = 1.500000");
+    runner.parse_line("Info: VERYLA_METRIC offset = -3");
+    runner.parse_line("Time: 0 ps  Iteration: 0  Process: /prj_test/Initial12_0  File: test.sv");
+    assert_eq!(metrics(&mut runner), expect());
+}
+
+#[test]
+fn metric_cocotb() {
+    let mut runner = Cocotb::new(CocotbSource::Embed("test".into()));
+    runner.parse_line("     0.00ns INFO     cocotb.prj_test    VERYLA_METRIC gain = 1.500000");
+    runner.parse_line("    10.00ns INFO     cocotb.prj_test    VERYLA_METRIC offset = -3");
+    runner.parse_line("    10.00ns INFO     cocotb.prj_test    NOT_VERYLA_METRIC x = 1");
+    assert_eq!(metrics(&mut runner), expect());
+}
+
+#[test]
+fn cached_diagnostic() {
+    let code = r#"
+    pub entity EntityA of component {
+        let a: logic = 1;
+    }
+    "#;
+
+    let metadata: Metadata = Metadata::create_default_toml("prj")
+        .unwrap()
+        .parse()
+        .unwrap();
+    let parser = Parser::parse(&code, &"test.veryla").unwrap();
+    let analyzer = Analyzer::new(&metadata);
+    analyzer.analyze_pass1(&"prj", &code, &"test.veryla", &parser.veryla);
+    Analyzer::analyze_post_pass1();
+    analyzer.analyze_pass2(&"prj", &code, &"test.veryla", &parser.veryla);
+    let errors = analyzer.analyze_pass3(&"prj", &code, &"test.veryla", &parser.veryla);
+    assert!(!errors.is_empty());
+
+    let expect = &errors[0];
+    let cached = CachedDiagnostic::new(expect);
+    let json = serde_json::to_string(&cached).unwrap();
+    let actual: CachedDiagnostic = serde_json::from_str(&json).unwrap();
+
+    assert_eq!(actual.to_string(), expect.to_string());
+    assert_eq!(
+        actual.code().map(|x| x.to_string()),
+        expect.code().map(|x| x.to_string())
+    );
+    assert_eq!(actual.severity(), Some(Severity::Warning));
+    let labels = |x: &dyn Diagnostic| -> Vec<_> {
+        x.labels()
+            .into_iter()
+            .flatten()
+            .map(|x| (x.offset(), x.len()))
+            .collect()
+    };
+    assert_eq!(labels(&actual), labels(expect));
+}